@@ -0,0 +1,132 @@
+//
+//  Anchor.swift
+//  
+//
+//  Created by agent on 18.10.2026.
+//
+
+import Foundation
+import CCardano
+
+public typealias AnchorDataHash = CCardano.AnchorDataHash
+
+extension AnchorDataHash: CType {}
+
+extension AnchorDataHash {
+    public init(bytes: Data) throws {
+        self = try bytes.withCData { bytes in
+            RustResult<Self>.wrap { res, err in
+                cardano_anchor_data_hash_from_bytes(bytes, res, err)
+            }
+        }.get()
+    }
+    
+    public func data() throws -> Data {
+        var data = try RustResult<CData>.wrap { res, err in
+            cardano_anchor_data_hash_to_bytes(self, res, err)
+        }.get()
+        return data.owned()
+    }
+    
+    public var bytesArray: [UInt8] {
+        withUnsafeBytes(of: _0) { ptr in
+            Array(ptr.bindMemory(to: UInt8.self).prefix(32))
+        }
+    }
+}
+
+extension AnchorDataHash: Equatable {
+    public static func == (lhs: AnchorDataHash, rhs: AnchorDataHash) -> Bool {
+        lhs.bytesArray == rhs.bytesArray
+    }
+}
+
+extension AnchorDataHash: Hashable {
+    public func hash(into hasher: inout Hasher) {
+        hasher.combine(bytesArray)
+    }
+}
+
+public struct Anchor: Equatable {
+    public private(set) var url: String
+    public private(set) var dataHash: AnchorDataHash
+    
+    init(anchor: CCardano.Anchor) {
+        url = anchor.url.copied()
+        dataHash = anchor.data_hash
+    }
+    
+    public init(url: String, dataHash: AnchorDataHash) {
+        self.url = url
+        self.dataHash = dataHash
+    }
+    
+    public init(bytes: Data) throws {
+        var anchor = try CCardano.Anchor(bytes: bytes)
+        self = anchor.owned()
+    }
+    
+    public func bytes() throws -> Data {
+        try withCAnchor { try $0.bytes() }
+    }
+    
+    func clonedCAnchor() throws -> CCardano.Anchor {
+        try withCAnchor { try $0.clone() }
+    }
+    
+    func withCAnchor<T>(
+        fn: @escaping (CCardano.Anchor) throws -> T
+    ) rethrows -> T {
+        try url.withCString { url in
+            try fn(CCardano.Anchor(url: url, data_hash: dataHash))
+        }
+    }
+}
+
+extension CCardano.Anchor: CPtr {
+    typealias Val = Anchor
+    
+    func copied() -> Anchor {
+        Anchor(anchor: self)
+    }
+    
+    mutating func free() {
+        cardano_anchor_free(&self)
+    }
+}
+
+extension CCardano.Anchor {
+    public init(bytes: Data) throws {
+        self = try bytes.withCData { bytes in
+            RustResult<Self>.wrap { result, error in
+                cardano_anchor_from_bytes(bytes, result, error)
+            }
+        }.get()
+    }
+    
+    public func bytes() throws -> Data {
+        var bytes = try RustResult<CData>.wrap { result, error in
+            cardano_anchor_to_bytes(self, result, error)
+        }.get()
+        return bytes.owned()
+    }
+    
+    public func clone() throws -> Self {
+        try RustResult<Self>.wrap { result, error in
+            cardano_anchor_clone(self, result, error)
+        }.get()
+    }
+}
+
+extension COption_Anchor: COption {
+    typealias Tag = COption_Anchor_Tag
+    typealias Value = CCardano.Anchor
+
+    func someTag() -> Tag {
+        Some_Anchor
+    }
+
+    func noneTag() -> Tag {
+        None_Anchor
+    }
+}
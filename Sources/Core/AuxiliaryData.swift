@@ -291,11 +291,18 @@ extension NativeScripts {
     }
 }
 
-public struct PlutusScript {
+public struct PlutusScript: Equatable {
     public let data: Data
+    public let language: Language
     
     init(plutusScript: CCardano.PlutusScript) {
-        data = plutusScript._0.copied()
+        data = plutusScript.bytes.copied()
+        language = Language(language: plutusScript.language)
+    }
+    
+    public init(data: Data, language: Language) {
+        self.data = data
+        self.language = language
     }
     
     func clonedCPlutusScript() throws -> CCardano.PlutusScript {
@@ -306,7 +313,9 @@ public struct PlutusScript {
         fn: @escaping (CCardano.PlutusScript) throws -> T
     ) rethrows -> T {
         try data.withCData { data in
-            try fn(CCardano.PlutusScript(_0: data))
+            try language.withCLanguage { language in
+                try fn(CCardano.PlutusScript(bytes: data, language: language))
+            }
         }
     }
 }
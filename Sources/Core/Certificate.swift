@@ -16,6 +16,18 @@ public enum Certificate {
     case poolRetirement(PoolRetirement)
     case genesisKeyDelegation(GenesisKeyDelegation)
     case moveInstantaneousRewardsCert(MoveInstantaneousRewardsCert)
+    case stakeRegistrationWithDeposit(StakeRegistrationWithDeposit)
+    case stakeDeregistrationWithDeposit(StakeDeregistrationWithDeposit)
+    case voteDelegation(VoteDelegation)
+    case stakeAndVoteDelegation(StakeAndVoteDelegation)
+    case stakeRegistrationAndDelegation(StakeRegistrationAndDelegation)
+    case voteRegistrationAndDelegation(VoteRegistrationAndDelegation)
+    case stakeVoteRegistrationAndDelegation(StakeVoteRegistrationAndDelegation)
+    case committeeHotAuth(CommitteeHotAuth)
+    case committeeColdResign(CommitteeColdResign)
+    case drepRegistration(DRepRegistration)
+    case drepDeregistration(DRepDeregistration)
+    case drepUpdate(DRepUpdate)
     
     init(certificate: CCardano.Certificate) {
         switch certificate.tag {
@@ -26,6 +38,25 @@ public enum Certificate {
         case PoolRetirementKind: self = .poolRetirement(certificate.pool_retirement_kind)
         case GenesisKeyDelegationKind: self = .genesisKeyDelegation(certificate.genesis_key_delegation_kind)
         case MoveInstantaneousRewardsCertKind: self = .moveInstantaneousRewardsCert(certificate.move_instantaneous_rewards_cert_kind.copied())
+        case StakeRegistrationWithDepositKind: self = .stakeRegistrationWithDeposit(certificate.stake_registration_with_deposit_kind)
+        case StakeDeregistrationWithDepositKind: self = .stakeDeregistrationWithDeposit(certificate.stake_deregistration_with_deposit_kind)
+        case VoteDelegationKind:
+            self = .voteDelegation(VoteDelegation(voteDelegation: certificate.vote_delegation_kind))
+        case StakeAndVoteDelegationKind:
+            self = .stakeAndVoteDelegation(StakeAndVoteDelegation(stakeAndVoteDelegation: certificate.stake_and_vote_delegation_kind))
+        case StakeRegistrationAndDelegationKind: self = .stakeRegistrationAndDelegation(certificate.stake_registration_and_delegation_kind)
+        case VoteRegistrationAndDelegationKind:
+            self = .voteRegistrationAndDelegation(VoteRegistrationAndDelegation(voteRegistrationAndDelegation: certificate.vote_registration_and_delegation_kind))
+        case StakeVoteRegistrationAndDelegationKind:
+            self = .stakeVoteRegistrationAndDelegation(StakeVoteRegistrationAndDelegation(stakeVoteRegistrationAndDelegation: certificate.stake_vote_registration_and_delegation_kind))
+        case CommitteeHotAuthKind: self = .committeeHotAuth(certificate.committee_hot_auth_kind)
+        case CommitteeColdResignKind:
+            self = .committeeColdResign(CommitteeColdResign(committeeColdResign: certificate.committee_cold_resign_kind))
+        case DRepRegistrationKind:
+            self = .drepRegistration(DRepRegistration(drepRegistration: certificate.d_rep_registration_kind))
+        case DRepDeregistrationKind: self = .drepDeregistration(certificate.d_rep_deregistration_kind)
+        case DRepUpdateKind:
+            self = .drepUpdate(DRepUpdate(drepUpdate: certificate.d_rep_update_kind))
         default: fatalError("Unknown Certificate type")
         }
     }
@@ -77,6 +108,80 @@ public enum Certificate {
                 certificate.move_instantaneous_rewards_cert_kind = mirsCert
                 return try fn(certificate)
             }
+        case .stakeRegistrationWithDeposit(let stakeRegistrationWithDeposit):
+            var certificate = CCardano.Certificate()
+            certificate.tag = StakeRegistrationWithDepositKind
+            certificate.stake_registration_with_deposit_kind = stakeRegistrationWithDeposit
+            return try fn(certificate)
+        case .stakeDeregistrationWithDeposit(let stakeDeregistrationWithDeposit):
+            var certificate = CCardano.Certificate()
+            certificate.tag = StakeDeregistrationWithDepositKind
+            certificate.stake_deregistration_with_deposit_kind = stakeDeregistrationWithDeposit
+            return try fn(certificate)
+        case .voteDelegation(let voteDelegation):
+            return try voteDelegation.withCVoteDelegation { voteDelegation in
+                var certificate = CCardano.Certificate()
+                certificate.tag = VoteDelegationKind
+                certificate.vote_delegation_kind = voteDelegation
+                return try fn(certificate)
+            }
+        case .stakeAndVoteDelegation(let stakeAndVoteDelegation):
+            return try stakeAndVoteDelegation.withCStakeAndVoteDelegation { stakeAndVoteDelegation in
+                var certificate = CCardano.Certificate()
+                certificate.tag = StakeAndVoteDelegationKind
+                certificate.stake_and_vote_delegation_kind = stakeAndVoteDelegation
+                return try fn(certificate)
+            }
+        case .stakeRegistrationAndDelegation(let stakeRegistrationAndDelegation):
+            var certificate = CCardano.Certificate()
+            certificate.tag = StakeRegistrationAndDelegationKind
+            certificate.stake_registration_and_delegation_kind = stakeRegistrationAndDelegation
+            return try fn(certificate)
+        case .voteRegistrationAndDelegation(let voteRegistrationAndDelegation):
+            return try voteRegistrationAndDelegation.withCVoteRegistrationAndDelegation { voteRegistrationAndDelegation in
+                var certificate = CCardano.Certificate()
+                certificate.tag = VoteRegistrationAndDelegationKind
+                certificate.vote_registration_and_delegation_kind = voteRegistrationAndDelegation
+                return try fn(certificate)
+            }
+        case .stakeVoteRegistrationAndDelegation(let stakeVoteRegistrationAndDelegation):
+            return try stakeVoteRegistrationAndDelegation.withCStakeVoteRegistrationAndDelegation { stakeVoteRegistrationAndDelegation in
+                var certificate = CCardano.Certificate()
+                certificate.tag = StakeVoteRegistrationAndDelegationKind
+                certificate.stake_vote_registration_and_delegation_kind = stakeVoteRegistrationAndDelegation
+                return try fn(certificate)
+            }
+        case .committeeHotAuth(let committeeHotAuth):
+            var certificate = CCardano.Certificate()
+            certificate.tag = CommitteeHotAuthKind
+            certificate.committee_hot_auth_kind = committeeHotAuth
+            return try fn(certificate)
+        case .committeeColdResign(let committeeColdResign):
+            return try committeeColdResign.withCCommitteeColdResign { committeeColdResign in
+                var certificate = CCardano.Certificate()
+                certificate.tag = CommitteeColdResignKind
+                certificate.committee_cold_resign_kind = committeeColdResign
+                return try fn(certificate)
+            }
+        case .drepRegistration(let drepRegistration):
+            return try drepRegistration.withCDRepRegistration { drepRegistration in
+                var certificate = CCardano.Certificate()
+                certificate.tag = DRepRegistrationKind
+                certificate.d_rep_registration_kind = drepRegistration
+                return try fn(certificate)
+            }
+        case .drepDeregistration(let drepDeregistration):
+            var certificate = CCardano.Certificate()
+            certificate.tag = DRepDeregistrationKind
+            certificate.d_rep_deregistration_kind = drepDeregistration
+            return try fn(certificate)
+        case .drepUpdate(let drepUpdate):
+            return try drepUpdate.withCDRepUpdate { drepUpdate in
+                var certificate = CCardano.Certificate()
+                certificate.tag = DRepUpdateKind
+                certificate.d_rep_update_kind = drepUpdate
+                return try fn(certificate)
+            }
         }
     }
 }
@@ -0,0 +1,39 @@
+//
+//  CommitteeColdResign.swift
+//  
+//
+//  Created by agent on 18.10.2026.
+//
+
+import Foundation
+import CCardano
+
+public struct CommitteeColdResign {
+    public private(set) var committeeColdCredential: StakeCredential
+    public private(set) var anchor: Anchor?
+    
+    init(committeeColdResign: CCardano.CommitteeColdResign) {
+        committeeColdCredential = StakeCredential(credential: committeeColdResign.committee_cold_credential)
+        anchor = committeeColdResign.anchor.get()?.copied()
+    }
+    
+    public init(committeeColdCredential: StakeCredential, anchor: Anchor?) {
+        self.committeeColdCredential = committeeColdCredential
+        self.anchor = anchor
+    }
+    
+    func withCCommitteeColdResign<T>(
+        fn: @escaping (CCardano.CommitteeColdResign) throws -> T
+    ) rethrows -> T {
+        try committeeColdCredential.withCCredential { committeeColdCredential in
+            try anchor.withCOption(
+                with: { try $0.withCAnchor(fn: $1) }
+            ) { anchor in
+                try fn(CCardano.CommitteeColdResign(
+                    committee_cold_credential: committeeColdCredential,
+                    anchor: anchor
+                ))
+            }
+        }
+    }
+}
@@ -0,0 +1,34 @@
+//
+//  CommitteeHotAuth.swift
+//  
+//
+//  Created by agent on 18.10.2026.
+//
+
+import Foundation
+import CCardano
+
+public typealias CommitteeHotAuth = CCardano.CommitteeHotAuth
+
+extension CommitteeHotAuth: CType {}
+
+extension CommitteeHotAuth {
+    public var committeeColdCredential: StakeCredential {
+        StakeCredential(credential: committee_cold_credential)
+    }
+    
+    public var committeeHotCredential: StakeCredential {
+        StakeCredential(credential: committee_hot_credential)
+    }
+    
+    public init(committeeColdCredential: StakeCredential, committeeHotCredential: StakeCredential) {
+        self = committeeColdCredential.withCCredential { committeeColdCredential in
+            committeeHotCredential.withCCredential { committeeHotCredential in
+                Self(
+                    committee_cold_credential: committeeColdCredential,
+                    committee_hot_credential: committeeHotCredential
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,62 @@
+//
+//  DRep.swift
+//  
+//
+//  Created by agent on 18.10.2026.
+//
+
+import Foundation
+import CCardano
+
+public enum DRep: Equatable, Hashable {
+    case keyHash(Ed25519KeyHash)
+    case scriptHash(ScriptHash)
+    case alwaysAbstain
+    case alwaysNoConfidence
+    
+    init(drep: CCardano.DRep) {
+        switch drep.tag {
+        case KeyHashKind: self = .keyHash(drep.key_hash_kind)
+        case ScriptHashKind: self = .scriptHash(drep.script_hash_kind)
+        case AlwaysAbstainKind: self = .alwaysAbstain
+        case AlwaysNoConfidenceKind: self = .alwaysNoConfidence
+        default: fatalError("Unknown DRep type")
+        }
+    }
+    
+    public init(bytes: Data) throws {
+        self = DRep(drep: try bytes.withCData { bytes in
+            RustResult<CCardano.DRep>.wrap { result, error in
+                cardano_drep_from_bytes(bytes, result, error)
+            }
+        }.get())
+    }
+    
+    public func data() throws -> Data {
+        var data = try withCDRep { drep in
+            RustResult<CData>.wrap { result, error in
+                cardano_drep_to_bytes(drep, result, error)
+            }
+        }.get()
+        return data.owned()
+    }
+    
+    func withCDRep<T>(
+        fn: @escaping (CCardano.DRep) throws -> T
+    ) rethrows -> T {
+        var drep = CCardano.DRep()
+        switch self {
+        case .keyHash(let hash):
+            drep.tag = KeyHashKind
+            drep.key_hash_kind = hash
+        case .scriptHash(let hash):
+            drep.tag = ScriptHashKind
+            drep.script_hash_kind = hash
+        case .alwaysAbstain:
+            drep.tag = AlwaysAbstainKind
+        case .alwaysNoConfidence:
+            drep.tag = AlwaysNoConfidenceKind
+        }
+        return try fn(drep)
+    }
+}
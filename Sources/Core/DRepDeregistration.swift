@@ -0,0 +1,25 @@
+//
+//  DRepDeregistration.swift
+//  
+//
+//  Created by agent on 18.10.2026.
+//
+
+import Foundation
+import CCardano
+
+public typealias DRepDeregistration = CCardano.DRepDeregistration
+
+extension DRepDeregistration: CType {}
+
+extension DRepDeregistration {
+    public var votingCredential: StakeCredential {
+        StakeCredential(credential: voting_credential)
+    }
+    
+    public init(votingCredential: StakeCredential, deposit: Coin) {
+        self = votingCredential.withCCredential { votingCredential in
+            Self(voting_credential: votingCredential, deposit: deposit)
+        }
+    }
+}
@@ -0,0 +1,43 @@
+//
+//  DRepRegistration.swift
+//  
+//
+//  Created by agent on 18.10.2026.
+//
+
+import Foundation
+import CCardano
+
+public struct DRepRegistration {
+    public private(set) var votingCredential: StakeCredential
+    public private(set) var deposit: Coin
+    public private(set) var anchor: Anchor?
+    
+    init(drepRegistration: CCardano.DRepRegistration) {
+        votingCredential = StakeCredential(credential: drepRegistration.voting_credential)
+        deposit = drepRegistration.deposit
+        anchor = drepRegistration.anchor.get()?.copied()
+    }
+    
+    public init(votingCredential: StakeCredential, deposit: Coin, anchor: Anchor?) {
+        self.votingCredential = votingCredential
+        self.deposit = deposit
+        self.anchor = anchor
+    }
+    
+    func withCDRepRegistration<T>(
+        fn: @escaping (CCardano.DRepRegistration) throws -> T
+    ) rethrows -> T {
+        try votingCredential.withCCredential { votingCredential in
+            try anchor.withCOption(
+                with: { try $0.withCAnchor(fn: $1) }
+            ) { anchor in
+                try fn(CCardano.DRepRegistration(
+                    voting_credential: votingCredential,
+                    deposit: deposit,
+                    anchor: anchor
+                ))
+            }
+        }
+    }
+}
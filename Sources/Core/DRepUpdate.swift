@@ -0,0 +1,36 @@
+//
+//  DRepUpdate.swift
+//  
+//
+//  Created by agent on 18.10.2026.
+//
+
+import Foundation
+import CCardano
+
+public struct DRepUpdate {
+    public private(set) var votingCredential: StakeCredential
+    public private(set) var anchor: Anchor?
+    
+    init(drepUpdate: CCardano.DRepUpdate) {
+        votingCredential = StakeCredential(credential: drepUpdate.voting_credential)
+        anchor = drepUpdate.anchor.get()?.copied()
+    }
+    
+    public init(votingCredential: StakeCredential, anchor: Anchor?) {
+        self.votingCredential = votingCredential
+        self.anchor = anchor
+    }
+    
+    func withCDRepUpdate<T>(
+        fn: @escaping (CCardano.DRepUpdate) throws -> T
+    ) rethrows -> T {
+        try votingCredential.withCCredential { votingCredential in
+            try anchor.withCOption(
+                with: { try $0.withCAnchor(fn: $1) }
+            ) { anchor in
+                try fn(CCardano.DRepUpdate(voting_credential: votingCredential, anchor: anchor))
+            }
+        }
+    }
+}
@@ -0,0 +1,359 @@
+//
+//  GovernanceAction.swift
+//  
+//
+//  Created by agent on 18.10.2026.
+//
+
+import Foundation
+import CCardano
+
+public struct ParameterChangeAction {
+    public var govActionId: GovernanceActionId?
+    public var protocolParamUpdate: ConwayProtocolParamUpdate
+    public var policyHash: ScriptHash?
+    
+    init(parameterChangeAction: CCardano.ParameterChangeAction) {
+        govActionId = parameterChangeAction.gov_action_id.get()
+        protocolParamUpdate = parameterChangeAction.protocol_param_update.copied()
+        policyHash = parameterChangeAction.policy_hash.get()
+    }
+    
+    public init(
+        govActionId: GovernanceActionId?,
+        protocolParamUpdate: ConwayProtocolParamUpdate,
+        policyHash: ScriptHash?
+    ) {
+        self.govActionId = govActionId
+        self.protocolParamUpdate = protocolParamUpdate
+        self.policyHash = policyHash
+    }
+    
+    func withCParameterChangeAction<T>(
+        fn: @escaping (CCardano.ParameterChangeAction) throws -> T
+    ) rethrows -> T {
+        try protocolParamUpdate.withCConwayProtocolParamUpdate { protocolParamUpdate in
+            try fn(CCardano.ParameterChangeAction(
+                gov_action_id: govActionId.cOption(),
+                protocol_param_update: protocolParamUpdate,
+                policy_hash: policyHash.cOption()
+            ))
+        }
+    }
+}
+
+public struct HardForkInitiationAction {
+    public var govActionId: GovernanceActionId?
+    public var protocolVersion: ProtocolVersion
+    
+    init(hardForkInitiationAction: CCardano.HardForkInitiationAction) {
+        govActionId = hardForkInitiationAction.gov_action_id.get()
+        protocolVersion = hardForkInitiationAction.protocol_version
+    }
+    
+    public init(govActionId: GovernanceActionId?, protocolVersion: ProtocolVersion) {
+        self.govActionId = govActionId
+        self.protocolVersion = protocolVersion
+    }
+    
+    func withCHardForkInitiationAction<T>(
+        fn: @escaping (CCardano.HardForkInitiationAction) throws -> T
+    ) rethrows -> T {
+        try fn(CCardano.HardForkInitiationAction(
+            gov_action_id: govActionId.cOption(),
+            protocol_version: protocolVersion
+        ))
+    }
+}
+
+public struct TreasuryWithdrawalsAction {
+    public var withdrawals: Withdrawals
+    public var policyHash: ScriptHash?
+    
+    init(treasuryWithdrawalsAction: CCardano.TreasuryWithdrawalsAction) {
+        withdrawals = Dictionary(
+            uniqueKeysWithValues: treasuryWithdrawalsAction.withdrawals.copiedDictionary().map {
+                key, value in (key.copied(), value)
+            }
+        )
+        policyHash = treasuryWithdrawalsAction.policy_hash.get()
+    }
+    
+    public init(withdrawals: Withdrawals, policyHash: ScriptHash?) {
+        self.withdrawals = withdrawals
+        self.policyHash = policyHash
+    }
+    
+    func withCTreasuryWithdrawalsAction<T>(
+        fn: @escaping (CCardano.TreasuryWithdrawalsAction) throws -> T
+    ) rethrows -> T {
+        try withdrawals.withCKVArray { withdrawals in
+            try fn(CCardano.TreasuryWithdrawalsAction(
+                withdrawals: withdrawals,
+                policy_hash: policyHash.cOption()
+            ))
+        }
+    }
+}
+
+public struct NoConfidenceAction {
+    public var govActionId: GovernanceActionId?
+    
+    init(noConfidenceAction: CCardano.NoConfidenceAction) {
+        govActionId = noConfidenceAction.gov_action_id.get()
+    }
+    
+    public init(govActionId: GovernanceActionId?) {
+        self.govActionId = govActionId
+    }
+    
+    func withCNoConfidenceAction<T>(
+        fn: @escaping (CCardano.NoConfidenceAction) throws -> T
+    ) rethrows -> T {
+        try fn(CCardano.NoConfidenceAction(gov_action_id: govActionId.cOption()))
+    }
+}
+
+public typealias CommitteeMembers = Dictionary<StakeCredential, Epoch>
+
+extension CCardano.CommitteeMembersKeyValue: CType {}
+
+extension CCardano.CommitteeMembersKeyValue: CKeyValue {
+    typealias Key = CCardano.StakeCredential
+    typealias Value = Epoch
+}
+
+extension CCardano.CommitteeMembers: CArray {
+    typealias CElement = CCardano.CommitteeMembersKeyValue
+    typealias Val = [CCardano.CommitteeMembersKeyValue]
+    
+    mutating func free() {
+        cardano_committee_members_free(&self)
+    }
+}
+
+extension CommitteeMembers {
+    func withCKVArray<T>(fn: @escaping (CCardano.CommitteeMembers) throws -> T) rethrows -> T {
+        try withCKVArray(withKey: { try $0.withCCredential(fn: $1) }, fn: fn)
+    }
+}
+
+public struct UpdateCommitteeAction {
+    public var govActionId: GovernanceActionId?
+    public var membersToRemove: StakeCredentials
+    public var membersToAdd: CommitteeMembers
+    public var quorumThreshold: UnitInterval
+    
+    init(updateCommitteeAction: CCardano.UpdateCommitteeAction) {
+        govActionId = updateCommitteeAction.gov_action_id.get()
+        membersToRemove = updateCommitteeAction.members_to_remove.copied().map { $0.copied() }
+        membersToAdd = Dictionary(
+            uniqueKeysWithValues: updateCommitteeAction.members_to_add.copiedDictionary().map {
+                key, value in (key.copied(), value)
+            }
+        )
+        quorumThreshold = updateCommitteeAction.quorum_threshold
+    }
+    
+    public init(
+        govActionId: GovernanceActionId?,
+        membersToRemove: StakeCredentials,
+        membersToAdd: CommitteeMembers,
+        quorumThreshold: UnitInterval
+    ) {
+        self.govActionId = govActionId
+        self.membersToRemove = membersToRemove
+        self.membersToAdd = membersToAdd
+        self.quorumThreshold = quorumThreshold
+    }
+    
+    func withCUpdateCommitteeAction<T>(
+        fn: @escaping (CCardano.UpdateCommitteeAction) throws -> T
+    ) rethrows -> T {
+        try membersToRemove.withCArray { membersToRemove in
+            try membersToAdd.withCKVArray { membersToAdd in
+                try fn(CCardano.UpdateCommitteeAction(
+                    gov_action_id: govActionId.cOption(),
+                    members_to_remove: membersToRemove,
+                    members_to_add: membersToAdd,
+                    quorum_threshold: quorumThreshold
+                ))
+            }
+        }
+    }
+}
+
+public struct Constitution {
+    public var anchor: Anchor
+    public var scriptHash: ScriptHash?
+    
+    init(constitution: CCardano.Constitution) {
+        anchor = constitution.anchor.copied()
+        scriptHash = constitution.script_hash.get()
+    }
+    
+    public init(anchor: Anchor, scriptHash: ScriptHash?) {
+        self.anchor = anchor
+        self.scriptHash = scriptHash
+    }
+    
+    func withCConstitution<T>(
+        fn: @escaping (CCardano.Constitution) throws -> T
+    ) rethrows -> T {
+        try anchor.withCAnchor { anchor in
+            try fn(CCardano.Constitution(anchor: anchor, script_hash: scriptHash.cOption()))
+        }
+    }
+}
+
+public struct NewConstitutionAction {
+    public var govActionId: GovernanceActionId?
+    public var constitution: Constitution
+    
+    init(newConstitutionAction: CCardano.NewConstitutionAction) {
+        govActionId = newConstitutionAction.gov_action_id.get()
+        constitution = Constitution(constitution: newConstitutionAction.constitution)
+    }
+    
+    public init(govActionId: GovernanceActionId?, constitution: Constitution) {
+        self.govActionId = govActionId
+        self.constitution = constitution
+    }
+    
+    func withCNewConstitutionAction<T>(
+        fn: @escaping (CCardano.NewConstitutionAction) throws -> T
+    ) rethrows -> T {
+        try constitution.withCConstitution { constitution in
+            try fn(CCardano.NewConstitutionAction(
+                gov_action_id: govActionId.cOption(),
+                constitution: constitution
+            ))
+        }
+    }
+}
+
+public enum GovernanceAction {
+    case parameterChangeAction(ParameterChangeAction)
+    case hardForkInitiationAction(HardForkInitiationAction)
+    case treasuryWithdrawalsAction(TreasuryWithdrawalsAction)
+    case noConfidenceAction(NoConfidenceAction)
+    case updateCommitteeAction(UpdateCommitteeAction)
+    case newConstitutionAction(NewConstitutionAction)
+    case infoAction
+    
+    init(governanceAction: CCardano.GovernanceAction) {
+        switch governanceAction.tag {
+        case ParameterChangeActionKind:
+            self = .parameterChangeAction(ParameterChangeAction(
+                parameterChangeAction: governanceAction.parameter_change_action_kind
+            ))
+        case HardForkInitiationActionKind:
+            self = .hardForkInitiationAction(HardForkInitiationAction(
+                hardForkInitiationAction: governanceAction.hard_fork_initiation_action_kind
+            ))
+        case TreasuryWithdrawalsActionKind:
+            self = .treasuryWithdrawalsAction(TreasuryWithdrawalsAction(
+                treasuryWithdrawalsAction: governanceAction.treasury_withdrawals_action_kind
+            ))
+        case NoConfidenceActionKind:
+            self = .noConfidenceAction(NoConfidenceAction(
+                noConfidenceAction: governanceAction.no_confidence_action_kind
+            ))
+        case UpdateCommitteeActionKind:
+            self = .updateCommitteeAction(UpdateCommitteeAction(
+                updateCommitteeAction: governanceAction.update_committee_action_kind
+            ))
+        case NewConstitutionActionKind:
+            self = .newConstitutionAction(NewConstitutionAction(
+                newConstitutionAction: governanceAction.new_constitution_action_kind
+            ))
+        case InfoActionKind: self = .infoAction
+        default: fatalError("Unknown GovernanceAction type")
+        }
+    }
+    
+    public init(bytes: Data) throws {
+        var governanceAction = try CCardano.GovernanceAction(bytes: bytes)
+        self = governanceAction.owned()
+    }
+    
+    public func bytes() throws -> Data {
+        try withCGovernanceAction { try $0.bytes() }
+    }
+    
+    func withCGovernanceAction<T>(
+        fn: @escaping (CCardano.GovernanceAction) throws -> T
+    ) rethrows -> T {
+        var governanceAction = CCardano.GovernanceAction()
+        switch self {
+        case .parameterChangeAction(let action):
+            return try action.withCParameterChangeAction { action in
+                governanceAction.tag = ParameterChangeActionKind
+                governanceAction.parameter_change_action_kind = action
+                return try fn(governanceAction)
+            }
+        case .hardForkInitiationAction(let action):
+            return try action.withCHardForkInitiationAction { action in
+                governanceAction.tag = HardForkInitiationActionKind
+                governanceAction.hard_fork_initiation_action_kind = action
+                return try fn(governanceAction)
+            }
+        case .treasuryWithdrawalsAction(let action):
+            return try action.withCTreasuryWithdrawalsAction { action in
+                governanceAction.tag = TreasuryWithdrawalsActionKind
+                governanceAction.treasury_withdrawals_action_kind = action
+                return try fn(governanceAction)
+            }
+        case .noConfidenceAction(let action):
+            return try action.withCNoConfidenceAction { action in
+                governanceAction.tag = NoConfidenceActionKind
+                governanceAction.no_confidence_action_kind = action
+                return try fn(governanceAction)
+            }
+        case .updateCommitteeAction(let action):
+            return try action.withCUpdateCommitteeAction { action in
+                governanceAction.tag = UpdateCommitteeActionKind
+                governanceAction.update_committee_action_kind = action
+                return try fn(governanceAction)
+            }
+        case .newConstitutionAction(let action):
+            return try action.withCNewConstitutionAction { action in
+                governanceAction.tag = NewConstitutionActionKind
+                governanceAction.new_constitution_action_kind = action
+                return try fn(governanceAction)
+            }
+        case .infoAction:
+            governanceAction.tag = InfoActionKind
+            return try fn(governanceAction)
+        }
+    }
+}
+
+extension CCardano.GovernanceAction: CPtr {
+    typealias Val = GovernanceAction
+    
+    func copied() -> GovernanceAction {
+        GovernanceAction(governanceAction: self)
+    }
+    
+    mutating func free() {
+        cardano_governance_action_free(&self)
+    }
+}
+
+extension CCardano.GovernanceAction {
+    public init(bytes: Data) throws {
+        self = try bytes.withCData { bytes in
+            RustResult<Self>.wrap { result, error in
+                cardano_governance_action_from_bytes(bytes, result, error)
+            }
+        }.get()
+    }
+    
+    public func bytes() throws -> Data {
+        var bytes = try RustResult<CData>.wrap { result, error in
+            cardano_governance_action_to_bytes(self, result, error)
+        }.get()
+        return bytes.owned()
+    }
+}
@@ -0,0 +1,120 @@
+//
+//  ProposalProcedure.swift
+//  
+//
+//  Created by agent on 18.10.2026.
+//
+
+import Foundation
+import CCardano
+
+public struct ProposalProcedure {
+    public var deposit: Coin
+    public var rewardAccount: RewardAddress
+    public var governanceAction: GovernanceAction
+    public var anchor: Anchor
+    
+    init(proposalProcedure: CCardano.ProposalProcedure) {
+        deposit = proposalProcedure.deposit
+        rewardAccount = proposalProcedure.reward_account.copied()
+        governanceAction = proposalProcedure.governance_action.copied()
+        anchor = proposalProcedure.anchor.copied()
+    }
+    
+    public init(
+        deposit: Coin,
+        rewardAccount: RewardAddress,
+        governanceAction: GovernanceAction,
+        anchor: Anchor
+    ) {
+        self.deposit = deposit
+        self.rewardAccount = rewardAccount
+        self.governanceAction = governanceAction
+        self.anchor = anchor
+    }
+    
+    public init(bytes: Data) throws {
+        var proposalProcedure = try CCardano.ProposalProcedure(bytes: bytes)
+        self = proposalProcedure.owned()
+    }
+    
+    public func bytes() throws -> Data {
+        try withCProposalProcedure { try $0.bytes() }
+    }
+    
+    func withCProposalProcedure<T>(
+        fn: @escaping (CCardano.ProposalProcedure) throws -> T
+    ) rethrows -> T {
+        try rewardAccount.withCRewardAddress { rewardAccount in
+            try governanceAction.withCGovernanceAction { governanceAction in
+                try anchor.withCAnchor { anchor in
+                    try fn(CCardano.ProposalProcedure(
+                        deposit: deposit,
+                        reward_account: rewardAccount,
+                        governance_action: governanceAction,
+                        anchor: anchor
+                    ))
+                }
+            }
+        }
+    }
+}
+
+extension CCardano.ProposalProcedure: CPtr {
+    typealias Val = ProposalProcedure
+    
+    func copied() -> ProposalProcedure {
+        ProposalProcedure(proposalProcedure: self)
+    }
+    
+    mutating func free() {
+        cardano_proposal_procedure_free(&self)
+    }
+}
+
+extension CCardano.ProposalProcedure {
+    public init(bytes: Data) throws {
+        self = try bytes.withCData { bytes in
+            RustResult<Self>.wrap { result, error in
+                cardano_proposal_procedure_from_bytes(bytes, result, error)
+            }
+        }.get()
+    }
+    
+    public func bytes() throws -> Data {
+        var bytes = try RustResult<CData>.wrap { result, error in
+            cardano_proposal_procedure_to_bytes(self, result, error)
+        }.get()
+        return bytes.owned()
+    }
+}
+
+public typealias ProposalProcedures = Array<ProposalProcedure>
+
+extension CCardano.ProposalProcedures: CArray {
+    typealias CElement = CCardano.ProposalProcedure
+    typealias Val = [CCardano.ProposalProcedure]
+    
+    mutating func free() {
+        cardano_proposal_procedures_free(&self)
+    }
+}
+
+extension ProposalProcedures {
+    func withCArray<T>(fn: @escaping (CCardano.ProposalProcedures) throws -> T) rethrows -> T {
+        try withCArray(with: { try $0.withCProposalProcedure(fn: $1) }, fn: fn)
+    }
+}
+
+extension COption_ProposalProcedures: COption {
+    typealias Tag = COption_ProposalProcedures_Tag
+    typealias Value = CCardano.ProposalProcedures
+    
+    func someTag() -> Tag {
+        Some_ProposalProcedures
+    }
+    
+    func noneTag() -> Tag {
+        None_ProposalProcedures
+    }
+}
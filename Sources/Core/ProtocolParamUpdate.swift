@@ -55,10 +55,14 @@ extension ProtocolVersions {
 
 public enum Language {
     case plutusV1
+    case plutusV2
+    case plutusV3
 
     init(language: CCardano.Language) {
         switch language {
         case PlutusV1: self = .plutusV1
+        case PlutusV2: self = .plutusV2
+        case PlutusV3: self = .plutusV3
         default: fatalError("Unknown Language type")
         }
     }
@@ -68,6 +72,8 @@ public enum Language {
     ) rethrows -> T {
         switch self {
         case .plutusV1: return try fn(PlutusV1)
+        case .plutusV2: return try fn(PlutusV2)
+        case .plutusV3: return try fn(PlutusV3)
         }
     }
 }
@@ -374,3 +380,212 @@ extension CCardano.ProtocolParamUpdate {
         }.get()
     }
 }
+
+public typealias PoolVotingThresholds = CCardano.PoolVotingThresholds
+
+extension PoolVotingThresholds: CType {}
+
+public typealias DRepVotingThresholds = CCardano.DRepVotingThresholds
+
+extension DRepVotingThresholds: CType {}
+
+extension COption_PoolVotingThresholds: COption {
+    typealias Tag = COption_PoolVotingThresholds_Tag
+    typealias Value = PoolVotingThresholds
+
+    func someTag() -> Tag {
+        Some_PoolVotingThresholds
+    }
+
+    func noneTag() -> Tag {
+        None_PoolVotingThresholds
+    }
+}
+
+extension COption_DRepVotingThresholds: COption {
+    typealias Tag = COption_DRepVotingThresholds_Tag
+    typealias Value = DRepVotingThresholds
+
+    func someTag() -> Tag {
+        Some_DRepVotingThresholds
+    }
+
+    func noneTag() -> Tag {
+        None_DRepVotingThresholds
+    }
+}
+
+extension COption_SubCoin: COption {
+    typealias Tag = COption_SubCoin_Tag
+    typealias Value = SubCoin
+
+    func someTag() -> Tag {
+        Some_SubCoin
+    }
+
+    func noneTag() -> Tag {
+        None_SubCoin
+    }
+}
+
+public struct ConwayProtocolParamUpdate {
+    public var minfeeA: Coin?
+    public var minfeeB: Coin?
+    public var maxBlockBodySize: UInt32?
+    public var maxTxSize: UInt32?
+    public var maxBlockHeaderSize: UInt32?
+    public var keyDeposit: Coin?
+    public var poolDeposit: Coin?
+    public var maxEpoch: Epoch?
+    public var nOpt: UInt32?
+    public var poolPledgeInfluence: Rational?
+    public var expansionRate: UnitInterval?
+    public var treasuryGrowthRate: UnitInterval?
+    public var minPoolCost: Coin?
+    public var adaPerUtxoByte: Coin?
+    public var costModels: Costmdls?
+    public var executionCosts: ExUnitPrices?
+    public var maxTxExUnits: ExUnits?
+    public var maxBlockExUnits: ExUnits?
+    public var maxValueSize: UInt32?
+    public var collateralPercentage: UInt32?
+    public var maxCollateralInputs: UInt32?
+    public var poolVotingThresholds: PoolVotingThresholds?
+    public var drepVotingThresholds: DRepVotingThresholds?
+    public var minCommitteeSize: UInt32?
+    public var committeeTermLimit: Epoch?
+    public var governanceActionValidityPeriod: Epoch?
+    public var governanceActionDeposit: Coin?
+    public var drepDeposit: Coin?
+    public var drepInactivityPeriod: Epoch?
+    public var minFeeRefScriptCostPerByte: SubCoin?
+    
+    init(protocolParamUpdate: CCardano.ConwayProtocolParamUpdate) {
+        minfeeA = protocolParamUpdate.minfee_a.get()
+        minfeeB = protocolParamUpdate.minfee_b.get()
+        maxBlockBodySize = protocolParamUpdate.max_block_body_size.get()
+        maxTxSize = protocolParamUpdate.max_tx_size.get()
+        maxBlockHeaderSize = protocolParamUpdate.max_block_header_size.get()
+        keyDeposit = protocolParamUpdate.key_deposit.get()
+        poolDeposit = protocolParamUpdate.pool_deposit.get()
+        maxEpoch = protocolParamUpdate.max_epoch.get()
+        nOpt = protocolParamUpdate.n_opt.get()
+        poolPledgeInfluence = protocolParamUpdate.pool_pledge_influence.get()
+        expansionRate = protocolParamUpdate.expansion_rate.get()
+        treasuryGrowthRate = protocolParamUpdate.treasury_growth_rate.get()
+        minPoolCost = protocolParamUpdate.min_pool_cost.get()
+        adaPerUtxoByte = protocolParamUpdate.ada_per_utxo_byte.get()
+        let costModels = protocolParamUpdate.cost_models.get()?.copiedDictionary().map { key, value in
+            (Language(language: key), value.copied().map { $0.bigInt })
+        }
+        if let costModels = costModels {
+            self.costModels = Dictionary(uniqueKeysWithValues: costModels)
+        }
+        executionCosts = protocolParamUpdate.execution_costs.get()
+        maxTxExUnits = protocolParamUpdate.max_tx_ex_units.get()
+        maxBlockExUnits = protocolParamUpdate.max_block_ex_units.get()
+        maxValueSize = protocolParamUpdate.max_value_size.get()
+        collateralPercentage = protocolParamUpdate.collateral_percentage.get()
+        maxCollateralInputs = protocolParamUpdate.max_collateral_inputs.get()
+        poolVotingThresholds = protocolParamUpdate.pool_voting_thresholds.get()
+        drepVotingThresholds = protocolParamUpdate.drep_voting_thresholds.get()
+        minCommitteeSize = protocolParamUpdate.min_committee_size.get()
+        committeeTermLimit = protocolParamUpdate.committee_term_limit.get()
+        governanceActionValidityPeriod = protocolParamUpdate.governance_action_validity_period.get()
+        governanceActionDeposit = protocolParamUpdate.governance_action_deposit.get()
+        drepDeposit = protocolParamUpdate.drep_deposit.get()
+        drepInactivityPeriod = protocolParamUpdate.drep_inactivity_period.get()
+        minFeeRefScriptCostPerByte = protocolParamUpdate.min_fee_ref_script_cost_per_byte.get()
+    }
+    
+    public init() {}
+    
+    public init(bytes: Data) throws {
+        var protocolParamUpdate = try CCardano.ConwayProtocolParamUpdate(bytes: bytes)
+        self = protocolParamUpdate.owned()
+    }
+    
+    public func bytes() throws -> Data {
+        try withCConwayProtocolParamUpdate { try $0.bytes() }
+    }
+    
+    func clonedCConwayProtocolParamUpdate() throws -> CCardano.ConwayProtocolParamUpdate {
+        try withCConwayProtocolParamUpdate { try $0.clone() }
+    }
+    
+    func withCConwayProtocolParamUpdate<T>(
+        fn: @escaping (CCardano.ConwayProtocolParamUpdate) throws -> T
+    ) rethrows -> T {
+        try costModels.withCOption(with: {
+            try $0.withCKVArray(fn: $1)
+        }) { costModels in
+            try fn(CCardano.ConwayProtocolParamUpdate(
+                minfee_a: minfeeA.cOption(),
+                minfee_b: minfeeB.cOption(),
+                max_block_body_size: maxBlockBodySize.cOption(),
+                max_tx_size: maxTxSize.cOption(),
+                max_block_header_size: maxBlockHeaderSize.cOption(),
+                key_deposit: keyDeposit.cOption(),
+                pool_deposit: poolDeposit.cOption(),
+                max_epoch: maxEpoch.cOption(),
+                n_opt: nOpt.cOption(),
+                pool_pledge_influence: poolPledgeInfluence.cOption(),
+                expansion_rate: expansionRate.cOption(),
+                treasury_growth_rate: treasuryGrowthRate.cOption(),
+                min_pool_cost: minPoolCost.cOption(),
+                ada_per_utxo_byte: adaPerUtxoByte.cOption(),
+                cost_models: costModels,
+                execution_costs: executionCosts.cOption(),
+                max_tx_ex_units: maxTxExUnits.cOption(),
+                max_block_ex_units: maxBlockExUnits.cOption(),
+                max_value_size: maxValueSize.cOption(),
+                collateral_percentage: collateralPercentage.cOption(),
+                max_collateral_inputs: maxCollateralInputs.cOption(),
+                pool_voting_thresholds: poolVotingThresholds.cOption(),
+                drep_voting_thresholds: drepVotingThresholds.cOption(),
+                min_committee_size: minCommitteeSize.cOption(),
+                committee_term_limit: committeeTermLimit.cOption(),
+                governance_action_validity_period: governanceActionValidityPeriod.cOption(),
+                governance_action_deposit: governanceActionDeposit.cOption(),
+                drep_deposit: drepDeposit.cOption(),
+                drep_inactivity_period: drepInactivityPeriod.cOption(),
+                min_fee_ref_script_cost_per_byte: minFeeRefScriptCostPerByte.cOption()
+            ))
+        }
+    }
+}
+
+extension CCardano.ConwayProtocolParamUpdate: CPtr {
+    typealias Val = ConwayProtocolParamUpdate
+    
+    func copied() -> ConwayProtocolParamUpdate {
+        ConwayProtocolParamUpdate(protocolParamUpdate: self)
+    }
+    
+    mutating func free() {
+        cardano_conway_protocol_param_update_free(&self)
+    }
+}
+
+extension CCardano.ConwayProtocolParamUpdate {
+    public init(bytes: Data) throws {
+        self = try bytes.withCData { bytes in
+            RustResult<Self>.wrap { result, error in
+                cardano_conway_protocol_param_update_from_bytes(bytes, result, error)
+            }
+        }.get()
+    }
+    
+    public func bytes() throws -> Data {
+        var bytes = try RustResult<CData>.wrap { bytes, error in
+            cardano_conway_protocol_param_update_to_bytes(self, bytes, error)
+        }.get()
+        return bytes.owned()
+    }
+    
+    public func clone() throws -> Self {
+        try RustResult<CCardano.ConwayProtocolParamUpdate>.wrap { result, error in
+            cardano_conway_protocol_param_update_clone(self, result, error)
+        }.get()
+    }
+}
@@ -0,0 +1,41 @@
+//
+//  StakeAndVoteDelegation.swift
+//  
+//
+//  Created by agent on 18.10.2026.
+//
+
+import Foundation
+import CCardano
+
+public struct StakeAndVoteDelegation {
+    public private(set) var stakeCredential: StakeCredential
+    public private(set) var poolKeyhash: Ed25519KeyHash
+    public private(set) var drep: DRep
+    
+    init(stakeAndVoteDelegation: CCardano.StakeAndVoteDelegation) {
+        stakeCredential = StakeCredential(credential: stakeAndVoteDelegation.stake_credential)
+        poolKeyhash = stakeAndVoteDelegation.pool_keyhash
+        drep = DRep(drep: stakeAndVoteDelegation.drep)
+    }
+    
+    public init(stakeCredential: StakeCredential, poolKeyhash: Ed25519KeyHash, drep: DRep) {
+        self.stakeCredential = stakeCredential
+        self.poolKeyhash = poolKeyhash
+        self.drep = drep
+    }
+    
+    func withCStakeAndVoteDelegation<T>(
+        fn: @escaping (CCardano.StakeAndVoteDelegation) throws -> T
+    ) rethrows -> T {
+        try stakeCredential.withCCredential { stakeCredential in
+            try drep.withCDRep { drep in
+                try fn(CCardano.StakeAndVoteDelegation(
+                    stake_credential: stakeCredential,
+                    pool_keyhash: poolKeyhash,
+                    drep: drep
+                ))
+            }
+        }
+    }
+}
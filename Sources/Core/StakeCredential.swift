@@ -194,3 +194,33 @@ extension CCardano.StakeCredential: Hashable {
         self.copied().hash(into: &hasher)
     }
 }
+
+public typealias StakeCredentials = Array<StakeCredential>
+
+extension CCardano.StakeCredentials: CArray {
+    typealias CElement = CCardano.StakeCredential
+    typealias Val = [CCardano.StakeCredential]
+
+    mutating func free() {
+        cardano_stake_credentials_free(&self)
+    }
+}
+
+extension StakeCredentials {
+    func withCArray<T>(fn: @escaping (CCardano.StakeCredentials) throws -> T) rethrows -> T {
+        try withCArray(with: { try $0.withCCredential(fn: $1) }, fn: fn)
+    }
+}
+
+extension COption_ScriptHash: COption {
+    typealias Tag = COption_ScriptHash_Tag
+    typealias Value = ScriptHash
+
+    func someTag() -> Tag {
+        Some_ScriptHash
+    }
+
+    func noneTag() -> Tag {
+        None_ScriptHash
+    }
+}
@@ -0,0 +1,25 @@
+//
+//  StakeDeregistrationWithDeposit.swift
+//  
+//
+//  Created by agent on 18.10.2026.
+//
+
+import Foundation
+import CCardano
+
+public typealias StakeDeregistrationWithDeposit = CCardano.StakeDeregistrationWithDeposit
+
+extension StakeDeregistrationWithDeposit: CType {}
+
+extension StakeDeregistrationWithDeposit {
+    public var stakeCredential: StakeCredential {
+        StakeCredential(credential: stake_credential)
+    }
+    
+    public init(stakeCredential: StakeCredential, deposit: Coin) {
+        self = stakeCredential.withCCredential { stakeCredential in
+            Self(stake_credential: stakeCredential, deposit: deposit)
+        }
+    }
+}
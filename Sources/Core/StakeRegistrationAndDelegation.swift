@@ -0,0 +1,29 @@
+//
+//  StakeRegistrationAndDelegation.swift
+//  
+//
+//  Created by agent on 18.10.2026.
+//
+
+import Foundation
+import CCardano
+
+public typealias StakeRegistrationAndDelegation = CCardano.StakeRegistrationAndDelegation
+
+extension StakeRegistrationAndDelegation: CType {}
+
+extension StakeRegistrationAndDelegation {
+    public var stakeCredential: StakeCredential {
+        StakeCredential(credential: stake_credential)
+    }
+    
+    public var poolKeyhash: Ed25519KeyHash {
+        pool_keyhash
+    }
+    
+    public init(stakeCredential: StakeCredential, poolKeyhash: Ed25519KeyHash, deposit: Coin) {
+        self = stakeCredential.withCCredential { stakeCredential in
+            Self(stake_credential: stakeCredential, pool_keyhash: poolKeyhash, deposit: deposit)
+        }
+    }
+}
@@ -0,0 +1,25 @@
+//
+//  StakeRegistrationWithDeposit.swift
+//  
+//
+//  Created by agent on 18.10.2026.
+//
+
+import Foundation
+import CCardano
+
+public typealias StakeRegistrationWithDeposit = CCardano.StakeRegistrationWithDeposit
+
+extension StakeRegistrationWithDeposit: CType {}
+
+extension StakeRegistrationWithDeposit {
+    public var stakeCredential: StakeCredential {
+        StakeCredential(credential: stake_credential)
+    }
+    
+    public init(stakeCredential: StakeCredential, deposit: Coin) {
+        self = stakeCredential.withCCredential { stakeCredential in
+            Self(stake_credential: stakeCredential, deposit: deposit)
+        }
+    }
+}
@@ -0,0 +1,50 @@
+//
+//  StakeVoteRegistrationAndDelegation.swift
+//  
+//
+//  Created by agent on 18.10.2026.
+//
+
+import Foundation
+import CCardano
+
+public struct StakeVoteRegistrationAndDelegation {
+    public private(set) var stakeCredential: StakeCredential
+    public private(set) var poolKeyhash: Ed25519KeyHash
+    public private(set) var drep: DRep
+    public private(set) var deposit: Coin
+    
+    init(stakeVoteRegistrationAndDelegation: CCardano.StakeVoteRegistrationAndDelegation) {
+        stakeCredential = StakeCredential(credential: stakeVoteRegistrationAndDelegation.stake_credential)
+        poolKeyhash = stakeVoteRegistrationAndDelegation.pool_keyhash
+        drep = DRep(drep: stakeVoteRegistrationAndDelegation.drep)
+        deposit = stakeVoteRegistrationAndDelegation.deposit
+    }
+    
+    public init(
+        stakeCredential: StakeCredential,
+        poolKeyhash: Ed25519KeyHash,
+        drep: DRep,
+        deposit: Coin
+    ) {
+        self.stakeCredential = stakeCredential
+        self.poolKeyhash = poolKeyhash
+        self.drep = drep
+        self.deposit = deposit
+    }
+    
+    func withCStakeVoteRegistrationAndDelegation<T>(
+        fn: @escaping (CCardano.StakeVoteRegistrationAndDelegation) throws -> T
+    ) rethrows -> T {
+        try stakeCredential.withCCredential { stakeCredential in
+            try drep.withCDRep { drep in
+                try fn(CCardano.StakeVoteRegistrationAndDelegation(
+                    stake_credential: stakeCredential,
+                    pool_keyhash: poolKeyhash,
+                    drep: drep,
+                    deposit: deposit
+                ))
+            }
+        }
+    }
+}
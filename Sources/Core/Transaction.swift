@@ -26,12 +26,14 @@ public struct Transaction {
     public let witnessSet: TransactionWitnessSet
     public let isValid: Bool
     public let auxiliaryData: AuxiliaryData?
+    public let preAlonzo: Bool
     
     init(transaction: CCardano.Transaction) {
         body = transaction.body.copied()
         witnessSet = transaction.witness_set.copied()
         isValid = transaction.is_valid
         auxiliaryData = transaction.auxiliary_data.get()?.copied()
+        preAlonzo = transaction.pre_alonzo
     }
     
     public init(body: TransactionBody, witnessSet: TransactionWitnessSet, auxiliaryData: AuxiliaryData?) {
@@ -39,6 +41,7 @@ public struct Transaction {
         self.witnessSet = witnessSet
         isValid = true
         self.auxiliaryData = auxiliaryData
+        preAlonzo = false
     }
     
     public init(bytes: Data) throws {
@@ -70,7 +73,8 @@ public struct Transaction {
                         body: body,
                         witness_set: witnessSet,
                         is_valid: isValid,
-                        auxiliary_data: auxiliaryData
+                        auxiliary_data: auxiliaryData,
+                        pre_alonzo: preAlonzo
                     ))
                 }
             }
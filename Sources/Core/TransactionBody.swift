@@ -341,6 +341,13 @@ public struct TransactionBody {
     public var collateral: TransactionInputs?
     public var requiredSigners: RequiredSigners?
     public var networkId: NetworkId?
+    public var collateralReturn: TransactionOutput?
+    public var totalCollateral: Coin?
+    public var referenceInputs: TransactionInputs?
+    public var votingProcedures: VotingProcedures?
+    public var proposalProcedures: ProposalProcedures?
+    public var currentTreasuryValue: Coin?
+    public var donation: Coin?
     
     init(transactionBody: CCardano.TransactionBody) {
         inputs = transactionBody.inputs.copied()
@@ -365,6 +372,15 @@ public struct TransactionBody {
         if let networkId = transactionBody.network_id.get() {
             self.networkId = NetworkId(networkId: networkId)
         }
+        collateralReturn = transactionBody.collateral_return.get()?.copied()
+        totalCollateral = transactionBody.total_collateral.get()
+        referenceInputs = transactionBody.reference_inputs.get()?.copied()
+        votingProcedures = transactionBody.voting_procedures.get()?.copiedVotingProcedures()
+        proposalProcedures = transactionBody.proposal_procedures.get()?.copied().map {
+            $0.copied()
+        }
+        currentTreasuryValue = transactionBody.current_treasury_value.get()
+        donation = transactionBody.donation.get()
     }
     
     public init(
@@ -415,22 +431,45 @@ public struct TransactionBody {
                                         try networkId.withCOption(
                                             with: { try $0.withCNetworkId(fn: $1) }
                                         ) { networkId in
-                                            try fn(CCardano.TransactionBody(
-                                                inputs: inputs,
-                                                outputs: outputs,
-                                                fee: fee,
-                                                ttl: ttl.cOption(),
-                                                certs: certs,
-                                                withdrawals: withdrawals,
-                                                update: update,
-                                                auxiliary_data_hash: auxiliaryDataHash.cOption(),
-                                                validity_start_interval: validityStartInterval.cOption(),
-                                                mint: mint,
-                                                script_data_hash: scriptDataHash.cOption(),
-                                                collateral: collateral,
-                                                required_signers: requiredSigners,
-                                                network_id: networkId
-                                            ))
+                                            try collateralReturn.withCOption(
+                                                with: { try $0.withCTransactionOutput(fn: $1) }
+                                            ) { collateralReturn in
+                                                try referenceInputs.withCOption(
+                                                    with: { try $0.withCArray(fn: $1) }
+                                                ) { referenceInputs in
+                                                    try votingProcedures.withCOption(
+                                                        with: { try $0.withCKVArray(fn: $1) }
+                                                    ) { votingProcedures in
+                                                        try proposalProcedures.withCOption(
+                                                            with: { try $0.withCArray(fn: $1) }
+                                                        ) { proposalProcedures in
+                                                            try fn(CCardano.TransactionBody(
+                                                                inputs: inputs,
+                                                                outputs: outputs,
+                                                                fee: fee,
+                                                                ttl: ttl.cOption(),
+                                                                certs: certs,
+                                                                withdrawals: withdrawals,
+                                                                update: update,
+                                                                auxiliary_data_hash: auxiliaryDataHash.cOption(),
+                                                                validity_start_interval: validityStartInterval.cOption(),
+                                                                mint: mint,
+                                                                script_data_hash: scriptDataHash.cOption(),
+                                                                collateral: collateral,
+                                                                required_signers: requiredSigners,
+                                                                network_id: networkId,
+                                                                collateral_return: collateralReturn,
+                                                                total_collateral: totalCollateral.cOption(),
+                                                                reference_inputs: referenceInputs,
+                                                                voting_procedures: votingProcedures,
+                                                                proposal_procedures: proposalProcedures,
+                                                                current_treasury_value: currentTreasuryValue.cOption(),
+                                                                donation: donation.cOption()
+                                                            ))
+                                                        }
+                                                    }
+                                                }
+                                            }
                                         }
                                     }
                                 }
@@ -92,17 +92,108 @@ extension CCardano.MockWitnessSet {
     }
 }
 
-public typealias TransactionBuilderConfig = CCardano.TransactionBuilderConfig
+public struct TransactionBuilderConfig {
+    public var feeAlgo: LinearFee
+    public var poolDeposit: BigNum
+    public var keyDeposit: BigNum
+    public var maxValueSize: UInt32
+    public var maxTxSize: UInt32
+    public var coinsPerUtxoWord: Coin
+    public var preferPureChange: Bool
+    public var exUnitPrices: ExUnitPrices?
+    public var costModels: Costmdls?
+    public var refScriptCoinsPerByte: SubCoin?
+    public var coinsPerUtxoByte: Coin?
+    
+    init(config: CCardano.TransactionBuilderConfig) {
+        feeAlgo = config.fee_algo
+        poolDeposit = config.pool_deposit
+        keyDeposit = config.key_deposit
+        maxValueSize = config.max_value_size
+        maxTxSize = config.max_tx_size
+        coinsPerUtxoWord = config.coins_per_utxo_word
+        preferPureChange = config.prefer_pure_change
+        exUnitPrices = config.ex_unit_prices.get()
+        let costModels = config.cost_models.get()?.copiedDictionary().map { key, value in
+            (Language(language: key), value.copied().map { $0.bigInt })
+        }
+        if let costModels = costModels {
+            self.costModels = Dictionary(uniqueKeysWithValues: costModels)
+        }
+        refScriptCoinsPerByte = config.ref_script_coins_per_byte.get()
+        coinsPerUtxoByte = config.coins_per_utxo_byte.get()
+    }
+    
+    public init(
+        feeAlgo: LinearFee,
+        poolDeposit: BigNum,
+        keyDeposit: BigNum,
+        maxValueSize: UInt32,
+        maxTxSize: UInt32,
+        coinsPerUtxoWord: Coin,
+        preferPureChange: Bool,
+        exUnitPrices: ExUnitPrices? = nil,
+        costModels: Costmdls? = nil,
+        refScriptCoinsPerByte: SubCoin? = nil,
+        coinsPerUtxoByte: Coin? = nil
+    ) {
+        self.feeAlgo = feeAlgo
+        self.poolDeposit = poolDeposit
+        self.keyDeposit = keyDeposit
+        self.maxValueSize = maxValueSize
+        self.maxTxSize = maxTxSize
+        self.coinsPerUtxoWord = coinsPerUtxoWord
+        self.preferPureChange = preferPureChange
+        self.exUnitPrices = exUnitPrices
+        self.costModels = costModels
+        self.refScriptCoinsPerByte = refScriptCoinsPerByte
+        self.coinsPerUtxoByte = coinsPerUtxoByte
+    }
+    
+    func withCTransactionBuilderConfig<T>(
+        fn: @escaping (CCardano.TransactionBuilderConfig) throws -> T
+    ) rethrows -> T {
+        try costModels.withCOption(with: {
+            try $0.withCKVArray(fn: $1)
+        }) { costModels in
+            try fn(CCardano.TransactionBuilderConfig(
+                fee_algo: feeAlgo,
+                pool_deposit: poolDeposit,
+                key_deposit: keyDeposit,
+                max_value_size: maxValueSize,
+                max_tx_size: maxTxSize,
+                coins_per_utxo_word: coinsPerUtxoWord,
+                prefer_pure_change: preferPureChange,
+                ex_unit_prices: exUnitPrices.cOption(),
+                cost_models: costModels,
+                ref_script_coins_per_byte: refScriptCoinsPerByte.cOption(),
+                coins_per_utxo_byte: coinsPerUtxoByte.cOption()
+            ))
+        }
+    }
+}
 
-extension TransactionBuilderConfig: CType {}
+extension CCardano.TransactionBuilderConfig: CPtr {
+    typealias Val = TransactionBuilderConfig
+    
+    func copied() -> TransactionBuilderConfig {
+        TransactionBuilderConfig(config: self)
+    }
+    
+    mutating func free() {
+        cardano_transaction_builder_config_free(&self)
+    }
+}
 
 public struct TxBuilderInput {
     public private(set) var input: TransactionInput
     public private(set) var amount: Value
+    public private(set) var scriptSize: UInt
     
     init(txBuilderInput: CCardano.TxBuilderInput) {
         input = txBuilderInput.input
         amount = txBuilderInput.amount.copied()
+        scriptSize = txBuilderInput.script_size
     }
     
     func clonedCTxBuilderInput() throws -> CCardano.TxBuilderInput {
@@ -113,7 +204,7 @@ public struct TxBuilderInput {
         fn: @escaping (CCardano.TxBuilderInput) throws -> T
     ) rethrows -> T {
         try amount.withCValue { amount in
-            try fn(CCardano.TxBuilderInput(input: input, amount: amount))
+            try fn(CCardano.TxBuilderInput(input: input, amount: amount, script_size: scriptSize))
         }
     }
 }
@@ -151,6 +242,202 @@ extension Array where Element == TxBuilderInput {
     }
 }
 
+public enum TxBuilderInputKind {
+    case keyInput(Ed25519KeyHash)
+    case scriptInput(ScriptHash)
+    case bootstrapInput(Data)
+    
+    init(txBuilderInputKind: CCardano.TxBuilderInputKind) {
+        switch txBuilderInputKind.tag {
+        case KeyInputKind: self = .keyInput(txBuilderInputKind.key_input_kind)
+        case ScriptInputKind: self = .scriptInput(txBuilderInputKind.script_input_kind)
+        case BootstrapInputKind:
+            self = .bootstrapInput(txBuilderInputKind.bootstrap_input_kind.copied())
+        default: fatalError("Unknown TxBuilderInputKind type")
+        }
+    }
+    
+    func withCTxBuilderInputKind<T>(
+        fn: @escaping (CCardano.TxBuilderInputKind) throws -> T
+    ) rethrows -> T {
+        var kind = CCardano.TxBuilderInputKind()
+        switch self {
+        case .keyInput(let hash):
+            kind.tag = KeyInputKind
+            kind.key_input_kind = hash
+            return try fn(kind)
+        case .scriptInput(let hash):
+            kind.tag = ScriptInputKind
+            kind.script_input_kind = hash
+            return try fn(kind)
+        case .bootstrapInput(let bytes):
+            return try bytes.withCData { bytes in
+                kind.tag = BootstrapInputKind
+                kind.bootstrap_input_kind = bytes
+                return try fn(kind)
+            }
+        }
+    }
+}
+
+extension CCardano.TxBuilderInputKind: CPtr {
+    typealias Val = TxBuilderInputKind
+    
+    func copied() -> TxBuilderInputKind {
+        TxBuilderInputKind(txBuilderInputKind: self)
+    }
+    
+    mutating func free() {
+        cardano_tx_builder_input_kind_free(&self)
+    }
+}
+
+extension CArray_TxBuilderInputKind: CArray {
+    typealias CElement = CCardano.TxBuilderInputKind
+    typealias Val = [CCardano.TxBuilderInputKind]
+
+    mutating func free() {}
+}
+
+extension Array where Element == TxBuilderInputKind {
+    func withCArray<T>(fn: @escaping (CArray_TxBuilderInputKind) throws -> T) rethrows -> T {
+        try withCArray(with: { try $0.withCTxBuilderInputKind(fn: $1) }, fn: fn)
+    }
+}
+
+public enum PlutusScriptSource {
+    case plutusScript(PlutusScript)
+    case refInput(TransactionInput)
+    
+    init(plutusScriptSource: CCardano.PlutusScriptSource) {
+        switch plutusScriptSource.tag {
+        case PlutusScriptKind: self = .plutusScript(plutusScriptSource.plutus_script_kind.copied())
+        case RefInputKind: self = .refInput(plutusScriptSource.ref_input_kind)
+        default: fatalError("Unknown PlutusScriptSource type")
+        }
+    }
+    
+    func clonedCPlutusScriptSource() throws -> CCardano.PlutusScriptSource {
+        try withCPlutusScriptSource { try $0.clone() }
+    }
+    
+    func withCPlutusScriptSource<T>(
+        fn: @escaping (CCardano.PlutusScriptSource) throws -> T
+    ) rethrows -> T {
+        var plutusScriptSource = CCardano.PlutusScriptSource()
+        switch self {
+        case .plutusScript(let plutusScript):
+            return try plutusScript.withCPlutusScript { plutusScript in
+                plutusScriptSource.tag = PlutusScriptKind
+                plutusScriptSource.plutus_script_kind = plutusScript
+                return try fn(plutusScriptSource)
+            }
+        case .refInput(let input):
+            plutusScriptSource.tag = RefInputKind
+            plutusScriptSource.ref_input_kind = input
+            return try fn(plutusScriptSource)
+        }
+    }
+}
+
+extension CCardano.PlutusScriptSource: CPtr {
+    typealias Val = PlutusScriptSource
+    
+    func copied() -> PlutusScriptSource {
+        PlutusScriptSource(plutusScriptSource: self)
+    }
+    
+    mutating func free() {
+        cardano_plutus_script_source_free(&self)
+    }
+}
+
+extension CCardano.PlutusScriptSource {
+    public func clone() throws -> Self {
+        try RustResult<Self>.wrap { result, error in
+            cardano_plutus_script_source_clone(self, result, error)
+        }.get()
+    }
+}
+
+public struct PlutusWitness {
+    public private(set) var script: PlutusScriptSource
+    public private(set) var datum: PlutusData?
+    public private(set) var redeemer: Redeemer
+    
+    init(plutusWitness: CCardano.PlutusWitness) {
+        script = plutusWitness.script.copied()
+        datum = plutusWitness.datum.get()?.copied()
+        redeemer = plutusWitness.redeemer.copied()
+    }
+    
+    public init(script: PlutusScriptSource, datum: PlutusData?, redeemer: Redeemer) {
+        self.script = script
+        self.datum = datum
+        self.redeemer = redeemer
+    }
+    
+    func clonedCPlutusWitness() throws -> CCardano.PlutusWitness {
+        try withCPlutusWitness { try $0.clone() }
+    }
+    
+    func withCPlutusWitness<T>(
+        fn: @escaping (CCardano.PlutusWitness) throws -> T
+    ) rethrows -> T {
+        try script.withCPlutusScriptSource { script in
+            try datum.withCOption(
+                with: { try $0.withCPlutusData(fn: $1) }
+            ) { datum in
+                try redeemer.withCRedeemer { redeemer in
+                    try fn(CCardano.PlutusWitness(script: script, datum: datum, redeemer: redeemer))
+                }
+            }
+        }
+    }
+}
+
+extension CCardano.PlutusWitness: CPtr {
+    typealias Val = PlutusWitness
+    
+    func copied() -> PlutusWitness {
+        PlutusWitness(plutusWitness: self)
+    }
+    
+    mutating func free() {
+        cardano_plutus_witness_free(&self)
+    }
+}
+
+extension CCardano.PlutusWitness {
+    public func clone() throws -> Self {
+        try RustResult<Self>.wrap { result, error in
+            cardano_plutus_witness_clone(self, result, error)
+        }.get()
+    }
+}
+
+public typealias PlutusWitnesses = Dictionary<TransactionInput, PlutusWitness>
+
+extension CCardano.PlutusWitnessesKeyValue: CType {}
+
+extension CCardano.PlutusWitnessesKeyValue: CKeyValue {
+    typealias Key = TransactionInput
+    typealias Value = CCardano.PlutusWitness
+}
+
+extension CCardano.PlutusWitnesses: CArray {
+    typealias CElement = CCardano.PlutusWitnessesKeyValue
+    typealias Val = [CCardano.PlutusWitnessesKeyValue]
+
+    mutating func free() {}
+}
+
+extension PlutusWitnesses {
+    func withCKVArray<T>(fn: @escaping (CCardano.PlutusWitnesses) throws -> T) rethrows -> T {
+        try withCKVArray(withValue: { try $0.withCPlutusWitness(fn: $1) }, fn: fn)
+    }
+}
+
 extension COption_Coin: COption {
     typealias Tag = COption_Coin_Tag
     typealias Value = Coin
@@ -270,11 +557,22 @@ public struct TransactionBuilder {
     public var auxiliaryData: AuxiliaryData?
     public var validityStartInterval: Slot?
     public let inputTypes: MockWitnessSet
+    public let inputKinds: Array<TxBuilderInputKind>
     public let mint: Mint?
     public let mintScripts: NativeScripts?
+    public let votingProcedures: VotingProcedures?
+    public let proposalProcedures: ProposalProcedures?
+    public let currentTreasuryValue: Coin?
+    public let donation: Coin?
+    public let plutusWitnesses: PlutusWitnesses
+    public let collateral: Array<TxBuilderInput>
+    public let collateralVkeys: Set<Ed25519KeyHash>
+    public let collateralReturn: TransactionOutput?
+    public let requiredSigners: RequiredSigners?
+    public let referenceInputs: TransactionUnspentOutputs
     
     init(transactionBuilder: CCardano.TransactionBuilder) {
-        config = transactionBuilder.config
+        config = transactionBuilder.config.copied()
         inputs = transactionBuilder.inputs.copied().map { $0.copied() }
         outputs = transactionBuilder.outputs.copied().map { $0.copied() }
         fee = transactionBuilder.fee.get()
@@ -288,10 +586,25 @@ public struct TransactionBuilder {
         auxiliaryData = transactionBuilder.auxiliary_data.get()?.copied()
         validityStartInterval = transactionBuilder.validity_start_interval.get()
         inputTypes = transactionBuilder.input_types.copied()
+        inputKinds = transactionBuilder.input_kinds.copied().map { $0.copied() }
         mint = transactionBuilder.mint.get()?.copiedDictionary().mapValues {
             $0.copiedDictionary().mapValues { $0.bigInt }
         }
         mintScripts = transactionBuilder.mint_scripts.get()?.copied().map { $0.copied() }
+        votingProcedures = transactionBuilder.voting_procedures.get()?.copiedVotingProcedures()
+        proposalProcedures = transactionBuilder.proposal_procedures.get()?.copied().map {
+            $0.copied()
+        }
+        currentTreasuryValue = transactionBuilder.current_treasury_value.get()
+        donation = transactionBuilder.donation.get()
+        plutusWitnesses = transactionBuilder.plutus_witnesses.copiedDictionary().mapValues {
+            $0.copied()
+        }
+        collateral = transactionBuilder.collateral.copied().map { $0.copied() }
+        collateralVkeys = Set(transactionBuilder.collateral_vkeys.copied())
+        collateralReturn = transactionBuilder.collateral_return.get()?.copied()
+        requiredSigners = transactionBuilder.required_signers.get()?.copied()
+        referenceInputs = transactionBuilder.reference_inputs.copied().map { $0.copied() }
     }
     
     public init(config: TransactionBuilderConfig) throws {
@@ -306,16 +619,24 @@ public struct TransactionBuilder {
         maxValueSize: UInt32,
         maxTxSize: UInt32,
         coinsPerUtxoWord: Coin,
-        preferPureChange: Bool
+        preferPureChange: Bool,
+        exUnitPrices: ExUnitPrices? = nil,
+        costModels: Costmdls? = nil,
+        refScriptCoinsPerByte: SubCoin? = nil,
+        coinsPerUtxoByte: Coin? = nil
     ) throws {
         try self.init(config: TransactionBuilderConfig(
-            fee_algo: feeAlgo,
-            pool_deposit: poolDeposit,
-            key_deposit: keyDeposit,
-            max_value_size: maxValueSize,
-            max_tx_size: maxTxSize,
-            coins_per_utxo_word: coinsPerUtxoWord,
-            prefer_pure_change: preferPureChange
+            feeAlgo: feeAlgo,
+            poolDeposit: poolDeposit,
+            keyDeposit: keyDeposit,
+            maxValueSize: maxValueSize,
+            maxTxSize: maxTxSize,
+            coinsPerUtxoWord: coinsPerUtxoWord,
+            preferPureChange: preferPureChange,
+            exUnitPrices: exUnitPrices,
+            costModels: costModels,
+            refScriptCoinsPerByte: refScriptCoinsPerByte,
+            coinsPerUtxoByte: coinsPerUtxoByte
         ))
     }
     
@@ -326,27 +647,65 @@ public struct TransactionBuilder {
         }
     }
     
-    public mutating func addKeyInput(hash: Ed25519KeyHash, input: TransactionInput, amount: Value) throws {
+    public mutating func addKeyInput(
+        hash: Ed25519KeyHash, input: TransactionInput, amount: Value, scriptRef: ScriptRef? = nil
+    ) throws {
+        self = try withCTransactionBuilder {
+            try $0.addKeyInput(hash: hash, input: input, amount: amount, scriptRef: scriptRef)
+        }
+    }
+    
+    public mutating func addScriptInput(
+        hash: ScriptHash, input: TransactionInput, amount: Value, scriptRef: ScriptRef? = nil
+    ) throws {
+        self = try withCTransactionBuilder {
+            try $0.addScriptInput(hash: hash, input: input, amount: amount, scriptRef: scriptRef)
+        }
+    }
+    
+    public mutating func addPlutusScriptInput(
+        witness: PlutusWitness, input: TransactionInput, amount: Value, scriptRef: ScriptRef? = nil
+    ) throws {
         self = try withCTransactionBuilder {
-            try $0.addKeyInput(hash: hash, input: input, amount: amount)
+            try $0.addPlutusScriptInput(
+                witness: witness, input: input, amount: amount, scriptRef: scriptRef
+            )
         }
     }
     
-    public mutating func addScriptInput(hash: ScriptHash, input: TransactionInput, amount: Value) throws {
+    public mutating func addCollateral(hash: Ed25519KeyHash, input: TransactionInput, amount: Value) throws {
         self = try withCTransactionBuilder {
-            try $0.addScriptInput(hash: hash, input: input, amount: amount)
+            try $0.addCollateral(hash: hash, input: input, amount: amount)
         }
     }
     
-    public mutating func addBootstrapInput(hash: ByronAddress, input: TransactionInput, amount: Value) throws {
+    public mutating func setCollateralReturn(collateralReturn: TransactionOutput) throws {
+        self = try withCTransactionBuilder {
+            try $0.setCollateralReturn(collateralReturn: collateralReturn)
+        }
+    }
+    
+    public mutating func addRequiredSigner(hash: Ed25519KeyHash) throws {
+        self = try withCTransactionBuilder { try $0.addRequiredSigner(hash: hash) }
+    }
+    
+    public mutating func addReferenceInput(utxo: TransactionUnspentOutput) throws {
+        self = try withCTransactionBuilder { try $0.addReferenceInput(utxo: utxo) }
+    }
+    
+    public mutating func addBootstrapInput(
+        hash: ByronAddress, input: TransactionInput, amount: Value, scriptRef: ScriptRef? = nil
+    ) throws {
         self = try withCTransactionBuilder {
-            try $0.addBootstrapInput(hash: hash, input: input, amount: amount)
+            try $0.addBootstrapInput(hash: hash, input: input, amount: amount, scriptRef: scriptRef)
         }
     }
     
-    public mutating func addInput(address: Address, input: TransactionInput, amount: Value) throws {
+    public mutating func addInput(
+        address: Address, input: TransactionInput, amount: Value, scriptRef: ScriptRef? = nil
+    ) throws {
         self = try withCTransactionBuilder {
-            try $0.addInput(address: address, input: input, amount: amount)
+            try $0.addInput(address: address, input: input, amount: amount, scriptRef: scriptRef)
         }
     }
     
@@ -374,6 +733,28 @@ public struct TransactionBuilder {
         }
     }
     
+    public mutating func setVotingProcedures(votingProcedures: VotingProcedures) throws {
+        self = try withCTransactionBuilder {
+            try $0.setVotingProcedures(votingProcedures: votingProcedures)
+        }
+    }
+    
+    public mutating func setProposalProcedures(proposalProcedures: ProposalProcedures) throws {
+        self = try withCTransactionBuilder {
+            try $0.setProposalProcedures(proposalProcedures: proposalProcedures)
+        }
+    }
+    
+    public mutating func setCurrentTreasuryValue(currentTreasuryValue: Coin) throws {
+        self = try withCTransactionBuilder {
+            try $0.setCurrentTreasuryValue(currentTreasuryValue: currentTreasuryValue)
+        }
+    }
+    
+    public mutating func setDonation(donation: Coin) throws {
+        self = try withCTransactionBuilder { try $0.setDonation(donation: donation) }
+    }
+    
     public func getExplicitInput() throws -> Value {
         try withCTransactionBuilder { try $0.getExplicitInput() }
     }
@@ -410,41 +791,105 @@ public struct TransactionBuilder {
         try withCTransactionBuilder { try $0.clone() }
     }
     
+    private func withCScriptFields<T>(
+        fn: @escaping (
+            CCardano.PlutusWitnesses,
+            CArray_TxBuilderInput,
+            CArray_Ed25519KeyHash,
+            COption_TransactionOutput,
+            COption_RequiredSigners,
+            CCardano.TransactionUnspentOutputs
+        ) throws -> T
+    ) rethrows -> T {
+        try plutusWitnesses.withCKVArray { plutusWitnesses in
+            try collateral.withCArray { collateral in
+                try collateralVkeys.withCArray { collateralVkeys in
+                    try collateralReturn.withCOption(
+                        with: { try $0.withCTransactionOutput(fn: $1) }
+                    ) { collateralReturn in
+                        try requiredSigners.withCOption(
+                            with: { try $0.withCArray(fn: $1) }
+                        ) { requiredSigners in
+                            try referenceInputs.withCArray { referenceInputs in
+                                try fn(
+                                    plutusWitnesses,
+                                    collateral,
+                                    collateralVkeys,
+                                    collateralReturn,
+                                    requiredSigners,
+                                    referenceInputs
+                                )
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    
     func withCTransactionBuilder<T>(
         fn: @escaping (CCardano.TransactionBuilder) throws -> T
     ) rethrows -> T {
-        try inputs.withCArray { inputs in
-            try outputs.withCArray { outputs in
-                try certs.withCOption(
-                    with: { try $0.withCArray(fn: $1) }
-                ) { certs in
-                    try withdrawals.withCOption(
-                        with: { try $0.withCKVArray(fn: $1) }
-                    ) { withdrawals in
-                        try auxiliaryData.withCOption(
-                            with: { try $0.withCAuxiliaryData(fn: $1) }
-                        ) { auxiliaryData in
-                            try inputTypes.withCMockWitnessSet { inputTypes in
-                                try mint.withCOption(
-                                    with: { try $0.withCKVArray(fn: $1) }
-                                ) { mint in
-                                    try mintScripts.withCOption(
-                                        with: { try $0.withCArray(fn: $1) }
-                                    ) { mintScripts in
-                                        try fn(CCardano.TransactionBuilder(
-                                            config: config,
-                                            inputs: inputs,
-                                            outputs: outputs,
-                                            fee: fee.cOption(),
-                                            ttl: ttl.cOption(),
-                                            certs: certs,
-                                            withdrawals: withdrawals,
-                                            auxiliary_data: auxiliaryData,
-                                            validity_start_interval: validityStartInterval.cOption(),
-                                            input_types: inputTypes,
-                                            mint: mint,
-                                            mint_scripts: mintScripts
-                                        ))
+        try config.withCTransactionBuilderConfig { config in
+            try inputs.withCArray { inputs in
+                try outputs.withCArray { outputs in
+                    try certs.withCOption(
+                        with: { try $0.withCArray(fn: $1) }
+                    ) { certs in
+                        try withdrawals.withCOption(
+                            with: { try $0.withCKVArray(fn: $1) }
+                        ) { withdrawals in
+                            try auxiliaryData.withCOption(
+                                with: { try $0.withCAuxiliaryData(fn: $1) }
+                            ) { auxiliaryData in
+                                try inputTypes.withCMockWitnessSet { inputTypes in
+                                    try inputKinds.withCArray { inputKinds in
+                                        try mint.withCOption(
+                                            with: { try $0.withCKVArray(fn: $1) }
+                                        ) { mint in
+                                            try mintScripts.withCOption(
+                                                with: { try $0.withCArray(fn: $1) }
+                                            ) { mintScripts in
+                                                try votingProcedures.withCOption(
+                                                    with: { try $0.withCKVArray(fn: $1) }
+                                                ) { votingProcedures in
+                                                    try proposalProcedures.withCOption(
+                                                        with: { try $0.withCArray(fn: $1) }
+                                                    ) { proposalProcedures in
+                                                        try withCScriptFields {
+                                                            plutusWitnesses, collateral, collateralVkeys,
+                                                            collateralReturn, requiredSigners,
+                                                            referenceInputs in
+                                                            try fn(CCardano.TransactionBuilder(
+                                                                config: config,
+                                                                inputs: inputs,
+                                                                outputs: outputs,
+                                                                fee: fee.cOption(),
+                                                                ttl: ttl.cOption(),
+                                                                certs: certs,
+                                                                withdrawals: withdrawals,
+                                                                auxiliary_data: auxiliaryData,
+                                                                validity_start_interval: validityStartInterval.cOption(),
+                                                                input_types: inputTypes,
+                                                                input_kinds: inputKinds,
+                                                                mint: mint,
+                                                                mint_scripts: mintScripts,
+                                                                voting_procedures: votingProcedures,
+                                                                proposal_procedures: proposalProcedures,
+                                                                current_treasury_value: currentTreasuryValue.cOption(),
+                                                                donation: donation.cOption(),
+                                                                plutus_witnesses: plutusWitnesses,
+                                                                collateral: collateral,
+                                                                collateral_vkeys: collateralVkeys,
+                                                                collateral_return: collateralReturn,
+                                                                required_signers: requiredSigners,
+                                                                reference_inputs: referenceInputs
+                                                            ))
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
                                     }
                                 }
                             }
@@ -472,8 +917,10 @@ extension CCardano.TransactionBuilderBool: CType {}
 
 extension CCardano.TransactionBuilder {
     public init(config: TransactionBuilderConfig) throws {
-        self = try RustResult<Self>.wrap { result, error in
-            cardano_transaction_builder_new(config, result, error)
+        self = try config.withCTransactionBuilderConfig { config in
+            RustResult<Self>.wrap { result, error in
+                cardano_transaction_builder_new(config, result, error)
+            }
         }.get()
     }
     
@@ -488,40 +935,117 @@ extension CCardano.TransactionBuilder {
         return transactionBuilder.owned()
     }
     
-    public func addKeyInput(hash: Ed25519KeyHash, input: TransactionInput, amount: Value) throws -> TransactionBuilder {
+    public func addKeyInput(
+        hash: Ed25519KeyHash, input: TransactionInput, amount: Value, scriptRef: ScriptRef? = nil
+    ) throws -> TransactionBuilder {
         var transactionBuilder = try amount.withCValue { amount in
-            RustResult<Self>.wrap { result, error in
-                cardano_transaction_builder_add_key_input(self, hash, input, amount, result, error)
+            try scriptRef.withCOption(with: { try $0.withCScriptRef(fn: $1) }) { scriptRef in
+                RustResult<Self>.wrap { result, error in
+                    cardano_transaction_builder_add_key_input(
+                        self, hash, input, amount, scriptRef, result, error
+                    )
+                }
             }
         }.get()
         return transactionBuilder.owned()
     }
     
-    public func addScriptInput(hash: ScriptHash, input: TransactionInput, amount: Value) throws -> TransactionBuilder {
+    public func addScriptInput(
+        hash: ScriptHash, input: TransactionInput, amount: Value, scriptRef: ScriptRef? = nil
+    ) throws -> TransactionBuilder {
         var transactionBuilder = try amount.withCValue { amount in
+            try scriptRef.withCOption(with: { try $0.withCScriptRef(fn: $1) }) { scriptRef in
+                RustResult<Self>.wrap { result, error in
+                    cardano_transaction_builder_add_script_input(
+                        self, hash, input, amount, scriptRef, result, error
+                    )
+                }
+            }
+        }.get()
+        return transactionBuilder.owned()
+    }
+    
+    public func addPlutusScriptInput(
+        witness: PlutusWitness, input: TransactionInput, amount: Value, scriptRef: ScriptRef? = nil
+    ) throws -> TransactionBuilder {
+        var transactionBuilder = try witness.withCPlutusWitness { witness in
+            try amount.withCValue { amount in
+                try scriptRef.withCOption(with: { try $0.withCScriptRef(fn: $1) }) { scriptRef in
+                    RustResult<Self>.wrap { result, error in
+                        cardano_transaction_builder_add_plutus_script_input(
+                            self, witness, input, amount, scriptRef, result, error
+                        )
+                    }
+                }
+            }
+        }.get()
+        return transactionBuilder.owned()
+    }
+    
+    public func addCollateral(hash: Ed25519KeyHash, input: TransactionInput, amount: Value) throws -> TransactionBuilder {
+        var transactionBuilder = try amount.withCValue { amount in
+            RustResult<Self>.wrap { result, error in
+                cardano_transaction_builder_add_collateral(self, hash, input, amount, result, error)
+            }
+        }.get()
+        return transactionBuilder.owned()
+    }
+    
+    public func setCollateralReturn(collateralReturn: TransactionOutput) throws -> TransactionBuilder {
+        var transactionBuilder = try collateralReturn.withCTransactionOutput { collateralReturn in
             RustResult<Self>.wrap { result, error in
-                cardano_transaction_builder_add_script_input(self, hash, input, amount, result, error)
+                cardano_transaction_builder_set_collateral_return(
+                    self, collateralReturn, result, error
+                )
             }
         }.get()
         return transactionBuilder.owned()
     }
     
-    public func addBootstrapInput(hash: ByronAddress, input: TransactionInput, amount: Value) throws -> TransactionBuilder {
+    public func addRequiredSigner(hash: Ed25519KeyHash) throws -> TransactionBuilder {
+        var transactionBuilder = try RustResult<Self>.wrap { result, error in
+            cardano_transaction_builder_add_required_signer(self, hash, result, error)
+        }.get()
+        return transactionBuilder.owned()
+    }
+    
+    public func addReferenceInput(utxo: TransactionUnspentOutput) throws -> TransactionBuilder {
+        var transactionBuilder = try utxo.withCTransactionUnspentOutput { utxo in
+            RustResult<Self>.wrap { result, error in
+                cardano_transaction_builder_add_reference_input(self, utxo, result, error)
+            }
+        }.get()
+        return transactionBuilder.owned()
+    }
+    
+    public func addBootstrapInput(
+        hash: ByronAddress, input: TransactionInput, amount: Value, scriptRef: ScriptRef? = nil
+    ) throws -> TransactionBuilder {
         var transactionBuilder = try hash.withCAddress { hash in
-            amount.withCValue { amount in
-                RustResult<Self>.wrap { result, error in
-                    cardano_transaction_builder_add_bootstrap_input(self, hash, input, amount, result, error)
+            try amount.withCValue { amount in
+                try scriptRef.withCOption(with: { try $0.withCScriptRef(fn: $1) }) { scriptRef in
+                    RustResult<Self>.wrap { result, error in
+                        cardano_transaction_builder_add_bootstrap_input(
+                            self, hash, input, amount, scriptRef, result, error
+                        )
+                    }
                 }
             }
         }.get()
         return transactionBuilder.owned()
     }
     
-    public func addInput(address: Address, input: TransactionInput, amount: Value) throws -> TransactionBuilder {
+    public func addInput(
+        address: Address, input: TransactionInput, amount: Value, scriptRef: ScriptRef? = nil
+    ) throws -> TransactionBuilder {
         var transactionBuilder = try address.withCAddress { address in
-            amount.withCValue { amount in
-                RustResult<Self>.wrap { result, error in
-                    cardano_transaction_builder_add_input(self, address, input, amount, result, error)
+            try amount.withCValue { amount in
+                try scriptRef.withCOption(with: { try $0.withCScriptRef(fn: $1) }) { scriptRef in
+                    RustResult<Self>.wrap { result, error in
+                        cardano_transaction_builder_add_input(
+                            self, address, input, amount, scriptRef, result, error
+                        )
+                    }
                 }
             }
         }.get()
@@ -573,6 +1097,46 @@ extension CCardano.TransactionBuilder {
         return transactionBuilder.owned()
     }
     
+    public func setVotingProcedures(votingProcedures: VotingProcedures) throws -> TransactionBuilder {
+        var transactionBuilder = try votingProcedures.withCKVArray { votingProcedures in
+            RustResult<Self>.wrap { result, error in
+                cardano_transaction_builder_set_voting_procedures(
+                    self, votingProcedures, result, error
+                )
+            }
+        }.get()
+        return transactionBuilder.owned()
+    }
+    
+    public func setProposalProcedures(
+        proposalProcedures: ProposalProcedures
+    ) throws -> TransactionBuilder {
+        var transactionBuilder = try proposalProcedures.withCArray { proposalProcedures in
+            RustResult<Self>.wrap { result, error in
+                cardano_transaction_builder_set_proposal_procedures(
+                    self, proposalProcedures, result, error
+                )
+            }
+        }.get()
+        return transactionBuilder.owned()
+    }
+    
+    public func setCurrentTreasuryValue(currentTreasuryValue: Coin) throws -> TransactionBuilder {
+        var transactionBuilder = try RustResult<Self>.wrap { result, error in
+            cardano_transaction_builder_set_current_treasury_value(
+                self, currentTreasuryValue, result, error
+            )
+        }.get()
+        return transactionBuilder.owned()
+    }
+    
+    public func setDonation(donation: Coin) throws -> TransactionBuilder {
+        var transactionBuilder = try RustResult<Self>.wrap { result, error in
+            cardano_transaction_builder_set_donation(self, donation, result, error)
+        }.get()
+        return transactionBuilder.owned()
+    }
+    
     public func getExplicitInput() throws -> Value {
         var value = try RustResult<Value>.wrap { result, error in
             cardano_transaction_builder_get_explicit_input(self, result, error)
@@ -19,6 +19,13 @@ extension TransactionInput: Equatable {
     }
 }
 
+extension TransactionInput: Hashable {
+    public func hash(into hasher: inout Hasher) {
+        hasher.combine(transaction_id)
+        hasher.combine(index)
+    }
+}
+
 extension TransactionInput {
     public init(bytes: Data) throws {
         self = try bytes.withCData { bytes in
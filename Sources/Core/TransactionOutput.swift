@@ -60,20 +60,183 @@ extension COption_DataHash: COption {
     }
 }
 
+public enum DatumOption: Equatable {
+    case dataHash(DataHash)
+    case data(PlutusData)
+    
+    init(datumOption: CCardano.DatumOption) {
+        switch datumOption.tag {
+        case DataHashKind: self = .dataHash(datumOption.data_hash_kind)
+        case DataKind: self = .data(datumOption.data_kind.copied())
+        default: fatalError("Unknown DatumOption type")
+        }
+    }
+    
+    func clonedCDatumOption() throws -> CCardano.DatumOption {
+        try withCDatumOption { try $0.clone() }
+    }
+    
+    func withCDatumOption<T>(
+        fn: @escaping (CCardano.DatumOption) throws -> T
+    ) rethrows -> T {
+        switch self {
+        case .dataHash(let dataHash):
+            var datumOption = CCardano.DatumOption()
+            datumOption.tag = DataHashKind
+            datumOption.data_hash_kind = dataHash
+            return try fn(datumOption)
+        case .data(let data):
+            return try data.withCPlutusData { data in
+                var datumOption = CCardano.DatumOption()
+                datumOption.tag = DataKind
+                datumOption.data_kind = data
+                return try fn(datumOption)
+            }
+        }
+    }
+}
+
+extension CCardano.DatumOption: CPtr {
+    typealias Val = DatumOption
+    
+    func copied() -> DatumOption {
+        DatumOption(datumOption: self)
+    }
+    
+    mutating func free() {
+        cardano_datum_option_free(&self)
+    }
+}
+
+extension CCardano.DatumOption {
+    public func clone() throws -> Self {
+        try RustResult<Self>.wrap { result, error in
+            cardano_datum_option_clone(self, result, error)
+        }.get()
+    }
+}
+
+extension COption_DatumOption: COption {
+    typealias Tag = COption_DatumOption_Tag
+    typealias Value = CCardano.DatumOption
+
+    func someTag() -> Tag {
+        Some_DatumOption
+    }
+
+    func noneTag() -> Tag {
+        None_DatumOption
+    }
+}
+
+public enum ScriptRef {
+    case nativeScript(NativeScript)
+    case plutusScript(PlutusScript)
+    
+    init(scriptRef: CCardano.ScriptRef) {
+        switch scriptRef.tag {
+        case NativeScriptRefKind: self = .nativeScript(scriptRef.native_script_ref_kind.copied())
+        case PlutusScriptRefKind: self = .plutusScript(scriptRef.plutus_script_ref_kind.copied())
+        default: fatalError("Unknown ScriptRef type")
+        }
+    }
+    
+    func clonedCScriptRef() throws -> CCardano.ScriptRef {
+        try withCScriptRef { try $0.clone() }
+    }
+    
+    func withCScriptRef<T>(
+        fn: @escaping (CCardano.ScriptRef) throws -> T
+    ) rethrows -> T {
+        switch self {
+        case .nativeScript(let nativeScript):
+            return try nativeScript.withCNativeScript { nativeScript in
+                var scriptRef = CCardano.ScriptRef()
+                scriptRef.tag = NativeScriptRefKind
+                scriptRef.native_script_ref_kind = nativeScript
+                return try fn(scriptRef)
+            }
+        case .plutusScript(let plutusScript):
+            return try plutusScript.withCPlutusScript { plutusScript in
+                var scriptRef = CCardano.ScriptRef()
+                scriptRef.tag = PlutusScriptRefKind
+                scriptRef.plutus_script_ref_kind = plutusScript
+                return try fn(scriptRef)
+            }
+        }
+    }
+}
+
+extension ScriptRef: Equatable {
+    public static func == (lhs: ScriptRef, rhs: ScriptRef) -> Bool {
+        switch (lhs, rhs) {
+        case (.nativeScript(let lhs), .nativeScript(let rhs)):
+            guard let lhs = try? lhs.hash(namespace: .nativeScript),
+                  let rhs = try? rhs.hash(namespace: .nativeScript) else {
+                return false
+            }
+            return lhs == rhs
+        case (.plutusScript(let lhs), .plutusScript(let rhs)):
+            return lhs == rhs
+        default:
+            return false
+        }
+    }
+}
+
+extension CCardano.ScriptRef: CPtr {
+    typealias Val = ScriptRef
+    
+    func copied() -> ScriptRef {
+        ScriptRef(scriptRef: self)
+    }
+    
+    mutating func free() {
+        cardano_script_ref_free(&self)
+    }
+}
+
+extension CCardano.ScriptRef {
+    public func clone() throws -> Self {
+        try RustResult<Self>.wrap { result, error in
+            cardano_script_ref_clone(self, result, error)
+        }.get()
+    }
+}
+
+extension COption_ScriptRef: COption {
+    typealias Tag = COption_ScriptRef_Tag
+    typealias Value = CCardano.ScriptRef
+
+    func someTag() -> Tag {
+        Some_ScriptRef
+    }
+
+    func noneTag() -> Tag {
+        None_ScriptRef
+    }
+}
+
 public struct TransactionOutput: Equatable {
     public let address: Address
     public let amount: Value
-    public var dataHash: DataHash?
+    public var datum: DatumOption?
+    public var scriptRef: ScriptRef?
     
     init(transactionOutput: CCardano.TransactionOutput) {
         address = transactionOutput.address.copied()
         amount = transactionOutput.amount.copied()
-        dataHash = transactionOutput.data_hash.get()
+        datum = transactionOutput.datum.get()?.copied()
+        scriptRef = transactionOutput.script_ref.get()?.copied()
     }
     
-    public init(address: Address, amount: Value) {
+    public init(
+        address: Address, amount: Value, datum: DatumOption? = nil, scriptRef: ScriptRef? = nil
+    ) {
         self.address = address
         self.amount = amount
+        self.datum = datum
+        self.scriptRef = scriptRef
     }
     
     func clonedCTransactionOutput() throws -> CCardano.TransactionOutput {
@@ -85,11 +248,20 @@ public struct TransactionOutput: Equatable {
     ) rethrows -> T {
         try address.withCAddress { address in
             try amount.withCValue { amount in
-                try fn(CCardano.TransactionOutput(
-                    address: address,
-                    amount: amount,
-                    data_hash: dataHash.cOption()
-                ))
+                try datum.withCOption(
+                    with: { try $0.withCDatumOption(fn: $1) }
+                ) { datum in
+                    try scriptRef.withCOption(
+                        with: { try $0.withCScriptRef(fn: $1) }
+                    ) { scriptRef in
+                        try fn(CCardano.TransactionOutput(
+                            address: address,
+                            amount: amount,
+                            datum: datum,
+                            script_ref: scriptRef
+                        ))
+                    }
+                }
             }
         }
     }
@@ -130,6 +302,19 @@ extension CCardano.TransactionOutput {
     }
 }
 
+extension COption_TransactionOutput: COption {
+    typealias Tag = COption_TransactionOutput_Tag
+    typealias Value = CCardano.TransactionOutput
+
+    func someTag() -> Tag {
+        Some_TransactionOutput
+    }
+
+    func noneTag() -> Tag {
+        None_TransactionOutput
+    }
+}
+
 public typealias TransactionOutputs = Array<TransactionOutput>
 
 extension CCardano.TransactionOutputs: CArray {
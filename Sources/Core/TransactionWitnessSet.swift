@@ -239,6 +239,19 @@ public enum RedeemerTag {
     }
 }
 
+extension COption_PlutusData: COption {
+    typealias Tag = COption_PlutusData_Tag
+    typealias Value = CCardano.PlutusData
+
+    func someTag() -> Tag {
+        Some_PlutusData
+    }
+
+    func noneTag() -> Tag {
+        None_PlutusData
+    }
+}
+
 public struct Redeemer {
     public let tag: RedeemerTag
     public let index: BigNum
@@ -252,6 +265,13 @@ public struct Redeemer {
         exUnits = redeemer.ex_units
     }
     
+    public init(tag: RedeemerTag, index: BigNum, data: PlutusData, exUnits: ExUnits) {
+        self.tag = tag
+        self.index = index
+        self.data = data
+        self.exUnits = exUnits
+    }
+    
     func clonedCRedeemer() throws -> CCardano.Redeemer {
         try withCRedeemer { try $0.clone() }
     }
@@ -0,0 +1,34 @@
+//
+//  VoteDelegation.swift
+//  
+//
+//  Created by agent on 18.10.2026.
+//
+
+import Foundation
+import CCardano
+
+public struct VoteDelegation {
+    public private(set) var stakeCredential: StakeCredential
+    public private(set) var drep: DRep
+    
+    init(voteDelegation: CCardano.VoteDelegation) {
+        stakeCredential = StakeCredential(credential: voteDelegation.stake_credential)
+        drep = DRep(drep: voteDelegation.drep)
+    }
+    
+    public init(stakeCredential: StakeCredential, drep: DRep) {
+        self.stakeCredential = stakeCredential
+        self.drep = drep
+    }
+    
+    func withCVoteDelegation<T>(
+        fn: @escaping (CCardano.VoteDelegation) throws -> T
+    ) rethrows -> T {
+        try stakeCredential.withCCredential { stakeCredential in
+            try drep.withCDRep { drep in
+                try fn(CCardano.VoteDelegation(stake_credential: stakeCredential, drep: drep))
+            }
+        }
+    }
+}
@@ -0,0 +1,41 @@
+//
+//  VoteRegistrationAndDelegation.swift
+//  
+//
+//  Created by agent on 18.10.2026.
+//
+
+import Foundation
+import CCardano
+
+public struct VoteRegistrationAndDelegation {
+    public private(set) var stakeCredential: StakeCredential
+    public private(set) var drep: DRep
+    public private(set) var deposit: Coin
+    
+    init(voteRegistrationAndDelegation: CCardano.VoteRegistrationAndDelegation) {
+        stakeCredential = StakeCredential(credential: voteRegistrationAndDelegation.stake_credential)
+        drep = DRep(drep: voteRegistrationAndDelegation.drep)
+        deposit = voteRegistrationAndDelegation.deposit
+    }
+    
+    public init(stakeCredential: StakeCredential, drep: DRep, deposit: Coin) {
+        self.stakeCredential = stakeCredential
+        self.drep = drep
+        self.deposit = deposit
+    }
+    
+    func withCVoteRegistrationAndDelegation<T>(
+        fn: @escaping (CCardano.VoteRegistrationAndDelegation) throws -> T
+    ) rethrows -> T {
+        try stakeCredential.withCCredential { stakeCredential in
+            try drep.withCDRep { drep in
+                try fn(CCardano.VoteRegistrationAndDelegation(
+                    stake_credential: stakeCredential,
+                    drep: drep,
+                    deposit: deposit
+                ))
+            }
+        }
+    }
+}
@@ -0,0 +1,278 @@
+//
+//  VotingProcedure.swift
+//  
+//
+//  Created by agent on 18.10.2026.
+//
+
+import Foundation
+import CCardano
+
+public enum Voter: Equatable, Hashable {
+    case constitutionalCommitteeHotKeyHash(Ed25519KeyHash)
+    case constitutionalCommitteeHotScriptHash(ScriptHash)
+    case drepKeyHash(Ed25519KeyHash)
+    case drepScriptHash(ScriptHash)
+    case stakingPoolKeyHash(Ed25519KeyHash)
+    
+    init(voter: CCardano.Voter) {
+        switch voter.tag {
+        case ConstitutionalCommitteeHotKeyHash:
+            self = .constitutionalCommitteeHotKeyHash(voter.constitutional_committee_hot_key_hash)
+        case ConstitutionalCommitteeHotScriptHash:
+            self = .constitutionalCommitteeHotScriptHash(
+                voter.constitutional_committee_hot_script_hash
+            )
+        case DRepKeyHash: self = .drepKeyHash(voter.d_rep_key_hash)
+        case DRepScriptHash: self = .drepScriptHash(voter.d_rep_script_hash)
+        case StakingPoolKeyHash: self = .stakingPoolKeyHash(voter.staking_pool_key_hash)
+        default: fatalError("Unknown Voter type")
+        }
+    }
+    
+    func withCVoter<T>(
+        fn: @escaping (CCardano.Voter) throws -> T
+    ) rethrows -> T {
+        var voter = CCardano.Voter()
+        switch self {
+        case .constitutionalCommitteeHotKeyHash(let hash):
+            voter.tag = ConstitutionalCommitteeHotKeyHash
+            voter.constitutional_committee_hot_key_hash = hash
+        case .constitutionalCommitteeHotScriptHash(let hash):
+            voter.tag = ConstitutionalCommitteeHotScriptHash
+            voter.constitutional_committee_hot_script_hash = hash
+        case .drepKeyHash(let hash):
+            voter.tag = DRepKeyHash
+            voter.d_rep_key_hash = hash
+        case .drepScriptHash(let hash):
+            voter.tag = DRepScriptHash
+            voter.d_rep_script_hash = hash
+        case .stakingPoolKeyHash(let hash):
+            voter.tag = StakingPoolKeyHash
+            voter.staking_pool_key_hash = hash
+        }
+        return try fn(voter)
+    }
+}
+
+extension CCardano.Voter: CPtr {
+    typealias Val = Voter
+    
+    func copied() -> Voter {
+        Voter(voter: self)
+    }
+    
+    mutating func free() {}
+}
+
+extension CCardano.Voter: Equatable {
+    public static func == (lhs: CCardano.Voter, rhs: CCardano.Voter) -> Bool {
+        lhs.copied() == rhs.copied()
+    }
+}
+
+extension CCardano.Voter: Hashable {
+    public func hash(into hasher: inout Hasher) {
+        self.copied().hash(into: &hasher)
+    }
+}
+
+public typealias GovernanceActionId = CCardano.GovernanceActionId
+
+extension GovernanceActionId: CType {}
+
+extension GovernanceActionId: Equatable {
+    public static func == (lhs: GovernanceActionId, rhs: GovernanceActionId) -> Bool {
+        lhs.transaction_id == rhs.transaction_id
+        && lhs.index == rhs.index
+    }
+}
+
+extension GovernanceActionId: Hashable {
+    public func hash(into hasher: inout Hasher) {
+        hasher.combine(transaction_id)
+        hasher.combine(index)
+    }
+}
+
+extension GovernanceActionId {
+    public init(bytes: Data) throws {
+        self = try bytes.withCData { bytes in
+            RustResult<Self>.wrap { result, error in
+                cardano_governance_action_id_from_bytes(bytes, result, error)
+            }
+        }.get()
+    }
+    
+    public func bytes() throws -> Data {
+        var bytes = try RustResult<CData>.wrap { result, error in
+            cardano_governance_action_id_to_bytes(self, result, error)
+        }.get()
+        return bytes.owned()
+    }
+}
+
+extension COption_GovernanceActionId: COption {
+    typealias Tag = COption_GovernanceActionId_Tag
+    typealias Value = GovernanceActionId
+    
+    func someTag() -> Tag {
+        Some_GovernanceActionId
+    }
+    
+    func noneTag() -> Tag {
+        None_GovernanceActionId
+    }
+}
+
+public enum Vote {
+    case no
+    case yes
+    case abstain
+    
+    init(vote: CCardano.Vote) {
+        switch vote {
+        case No: self = .no
+        case Yes: self = .yes
+        case Abstain: self = .abstain
+        default: fatalError("Unknown Vote type")
+        }
+    }
+    
+    func withCVote<T>(
+        fn: @escaping (CCardano.Vote) throws -> T
+    ) rethrows -> T {
+        switch self {
+        case .no: return try fn(No)
+        case .yes: return try fn(Yes)
+        case .abstain: return try fn(Abstain)
+        }
+    }
+}
+
+public struct VotingProcedure {
+    public var vote: Vote
+    public var anchor: Anchor?
+    
+    init(votingProcedure: CCardano.VotingProcedure) {
+        vote = Vote(vote: votingProcedure.vote)
+        anchor = votingProcedure.anchor.get()?.copied()
+    }
+    
+    public init(vote: Vote, anchor: Anchor? = nil) {
+        self.vote = vote
+        self.anchor = anchor
+    }
+    
+    func withCVotingProcedure<T>(
+        fn: @escaping (CCardano.VotingProcedure) throws -> T
+    ) rethrows -> T {
+        try anchor.withCOption(with: { try $0.withCAnchor(fn: $1) }) { anchor in
+            try vote.withCVote { vote in
+                try fn(CCardano.VotingProcedure(vote: vote, anchor: anchor))
+            }
+        }
+    }
+}
+
+public typealias GovernanceActionVotes = Dictionary<GovernanceActionId, VotingProcedure>
+
+extension CCardano.GovernanceActionVotesKeyValue: CType {}
+
+extension CCardano.GovernanceActionVotesKeyValue: CKeyValue {
+    typealias Key = GovernanceActionId
+    typealias Value = CCardano.VotingProcedure
+}
+
+extension CCardano.GovernanceActionVotes: CArray {
+    typealias CElement = CCardano.GovernanceActionVotesKeyValue
+    typealias Val = [CCardano.GovernanceActionVotesKeyValue]
+    
+    mutating func free() {
+        cardano_governance_action_votes_free(&self)
+    }
+}
+
+extension GovernanceActionVotes {
+    func withCKVArray<T>(fn: @escaping (CCardano.GovernanceActionVotes) throws -> T) rethrows -> T {
+        try withCKVArray(withValue: { try $0.withCVotingProcedure(fn: $1) }, fn: fn)
+    }
+}
+
+public typealias VotingProcedures = Dictionary<Voter, GovernanceActionVotes>
+
+extension CCardano.VotingProceduresKeyValue: CType {}
+
+extension CCardano.VotingProceduresKeyValue: CKeyValue {
+    typealias Key = CCardano.Voter
+    typealias Value = CCardano.GovernanceActionVotes
+}
+
+extension CCardano.VotingProcedures: CArray {
+    typealias CElement = CCardano.VotingProceduresKeyValue
+    typealias Val = [CCardano.VotingProceduresKeyValue]
+    
+    mutating func free() {
+        cardano_voting_procedures_free(&self)
+    }
+}
+
+extension CCardano.VotingProcedures {
+    func copiedVotingProcedures() -> VotingProcedures {
+        Dictionary(
+            uniqueKeysWithValues: copiedDictionary().map { voter, votes in
+                (voter.copied(), votes.copiedDictionary().mapValues {
+                    VotingProcedure(votingProcedure: $0)
+                })
+            }
+        )
+    }
+    
+    public init(bytes: Data) throws {
+        self = try bytes.withCData { bytes in
+            RustResult<Self>.wrap { result, error in
+                cardano_voting_procedures_from_bytes(bytes, result, error)
+            }
+        }.get()
+    }
+    
+    public func bytes() throws -> Data {
+        var bytes = try RustResult<CData>.wrap { result, error in
+            cardano_voting_procedures_to_bytes(self, result, error)
+        }.get()
+        return bytes.owned()
+    }
+}
+
+extension VotingProcedures {
+    public init(bytes: Data) throws {
+        var votingProcedures = try CCardano.VotingProcedures(bytes: bytes)
+        defer { votingProcedures.free() }
+        self = votingProcedures.copiedVotingProcedures()
+    }
+    
+    public func bytes() throws -> Data {
+        try withCKVArray { try $0.bytes() }
+    }
+    
+    func withCKVArray<T>(fn: @escaping (CCardano.VotingProcedures) throws -> T) rethrows -> T {
+        try withCKVArray(
+            withKey: { try $0.withCVoter(fn: $1) },
+            withValue: { try $0.withCKVArray(fn: $1) },
+            fn: fn
+        )
+    }
+}
+
+extension COption_VotingProcedures: COption {
+    typealias Tag = COption_VotingProcedures_Tag
+    typealias Value = CCardano.VotingProcedures
+    
+    func someTag() -> Tag {
+        Some_VotingProcedures
+    }
+    
+    func noneTag() -> Tag {
+        None_VotingProcedures
+    }
+}
@@ -89,4 +89,23 @@ final class TransactionBodyTests: XCTestCase {
         XCTAssertNoThrow(transactionBody.withCTransactionBody { $0 })
     }
     
+    func testCollateralReturnRoundTrip() throws {
+        let data32 = Data(repeating: 1, count: 32)
+        let address = try Address(bech32: addressExample)
+        var transactionBody = TransactionBody(
+            inputs: [
+                TransactionInput(transaction_id: try TransactionHash(bytes: data32), index: 1)
+            ],
+            outputs: [TransactionOutput(address: address, amount: Value(coin: 2000000))],
+            fee: 200000,
+            ttl: nil
+        )
+        let collateralReturn = TransactionOutput(address: address, amount: Value(coin: 3000000))
+        transactionBody.collateralReturn = collateralReturn
+        transactionBody.totalCollateral = 2000000
+        let decoded = try TransactionBody(bytes: transactionBody.bytes())
+        XCTAssertEqual(decoded.collateralReturn, collateralReturn)
+        XCTAssertEqual(decoded.totalCollateral, 2000000)
+    }
+    
 }
@@ -34,13 +34,13 @@ final class TransactionBuilderTests: XCTestCase {
     func testBuildTxWithChange() throws {
         let linearFee = LinearFee(constant: 2, coefficient: 500)
         let config = TransactionBuilderConfig(
-            fee_algo: linearFee,
-            pool_deposit: 1,
-            key_deposit: 1,
-            max_value_size: maxValueSize,
-            max_tx_size: maxTxSize,
-            coins_per_utxo_word: 1,
-            prefer_pure_change: false
+            feeAlgo: linearFee,
+            poolDeposit: 1,
+            keyDeposit: 1,
+            maxValueSize: maxValueSize,
+            maxTxSize: maxTxSize,
+            coinsPerUtxoWord: 1,
+            preferPureChange: false
         )
         var txBuilder = try TransactionBuilder(config: config)
         let spend = try rootKey15()
@@ -97,13 +97,13 @@ final class TransactionBuilderTests: XCTestCase {
     func testBuildTxWithoutChange() throws {
         let linearFee = LinearFee(constant: 2, coefficient: 500)
         let config = TransactionBuilderConfig(
-            fee_algo: linearFee,
-            pool_deposit: 1,
-            key_deposit: 1,
-            max_value_size: maxValueSize,
-            max_tx_size: maxTxSize,
-            coins_per_utxo_word: 1,
-            prefer_pure_change: false
+            feeAlgo: linearFee,
+            poolDeposit: 1,
+            keyDeposit: 1,
+            maxValueSize: maxValueSize,
+            maxTxSize: maxTxSize,
+            coinsPerUtxoWord: 1,
+            preferPureChange: false
         )
         var txBuilder = try TransactionBuilder(config: config)
         let spend = try rootKey15()
@@ -160,13 +160,13 @@ final class TransactionBuilderTests: XCTestCase {
     func testBuildTxWithCerts() throws {
         let linearFee = LinearFee(constant: 2, coefficient: 500)
         let config = TransactionBuilderConfig(
-            fee_algo: linearFee,
-            pool_deposit: 1,
-            key_deposit: 1_000_000,
-            max_value_size: maxValueSize,
-            max_tx_size: maxTxSize,
-            coins_per_utxo_word: 1,
-            prefer_pure_change: false
+            feeAlgo: linearFee,
+            poolDeposit: 1,
+            keyDeposit: 1_000_000,
+            maxValueSize: maxValueSize,
+            maxTxSize: maxTxSize,
+            coinsPerUtxoWord: 1,
+            preferPureChange: false
         )
         var txBuilder = try TransactionBuilder(config: config)
         let spend = try rootKey15()
@@ -226,13 +226,13 @@ final class TransactionBuilderTests: XCTestCase {
     func testBuildTxExactAmount() throws {
         let linearFee = LinearFee(constant: 0, coefficient: 0)
         let config = TransactionBuilderConfig(
-            fee_algo: linearFee,
-            pool_deposit: 0,
-            key_deposit: 0,
-            max_value_size: maxValueSize,
-            max_tx_size: maxTxSize,
-            coins_per_utxo_word: 1,
-            prefer_pure_change: false
+            feeAlgo: linearFee,
+            poolDeposit: 0,
+            keyDeposit: 0,
+            maxValueSize: maxValueSize,
+            maxTxSize: maxTxSize,
+            coinsPerUtxoWord: 1,
+            preferPureChange: false
         )
         var txBuilder = try TransactionBuilder(config: config)
         let spend = try rootKey15()
@@ -285,13 +285,13 @@ final class TransactionBuilderTests: XCTestCase {
     func testBuildTxExactChange() throws {
         let linearFee = LinearFee(constant: 0, coefficient: 0)
         let config = TransactionBuilderConfig(
-            fee_algo: linearFee,
-            pool_deposit: 0,
-            key_deposit: 0,
-            max_value_size: maxValueSize,
-            max_tx_size: maxTxSize,
-            coins_per_utxo_word: 1,
-            prefer_pure_change: false
+            feeAlgo: linearFee,
+            poolDeposit: 0,
+            keyDeposit: 0,
+            maxValueSize: maxValueSize,
+            maxTxSize: maxTxSize,
+            coinsPerUtxoWord: 1,
+            preferPureChange: false
         )
         var txBuilder = try TransactionBuilder(config: config)
         let spend = try rootKey15()
@@ -345,13 +345,13 @@ final class TransactionBuilderTests: XCTestCase {
     func testBuildTxInsufficientDeposit() throws {
         let linearFee = LinearFee(constant: 0, coefficient: 0)
         let config = TransactionBuilderConfig(
-            fee_algo: linearFee,
-            pool_deposit: 0,
-            key_deposit: 5,
-            max_value_size: maxValueSize,
-            max_tx_size: maxTxSize,
-            coins_per_utxo_word: 1,
-            prefer_pure_change: false
+            feeAlgo: linearFee,
+            poolDeposit: 0,
+            keyDeposit: 5,
+            maxValueSize: maxValueSize,
+            maxTxSize: maxTxSize,
+            coinsPerUtxoWord: 1,
+            preferPureChange: false
         )
         var txBuilder = try TransactionBuilder(config: config)
         let spend = try rootKey15()
@@ -405,13 +405,13 @@ final class TransactionBuilderTests: XCTestCase {
     func testBuildTxWithInputs() throws {
         let linearFee = LinearFee(constant: 2, coefficient: 500)
         let config = TransactionBuilderConfig(
-            fee_algo: linearFee,
-            pool_deposit: 1,
-            key_deposit: 1,
-            max_value_size: maxValueSize,
-            max_tx_size: maxTxSize,
-            coins_per_utxo_word: 1,
-            prefer_pure_change: false
+            feeAlgo: linearFee,
+            poolDeposit: 1,
+            keyDeposit: 1,
+            maxValueSize: maxValueSize,
+            maxTxSize: maxTxSize,
+            coinsPerUtxoWord: 1,
+            preferPureChange: false
         )
         var txBuilder = try TransactionBuilder(config: config)
         let spend = try rootKey15()
@@ -468,13 +468,13 @@ final class TransactionBuilderTests: XCTestCase {
     func testBuildTxWithNativeAssetsChange() throws {
         let linearFee = LinearFee(constant: 1, coefficient: 0)
         let config = TransactionBuilderConfig(
-            fee_algo: linearFee,
-            pool_deposit: 0,
-            key_deposit: 0,
-            max_value_size: maxValueSize,
-            max_tx_size: maxTxSize,
-            coins_per_utxo_word: 1,
-            prefer_pure_change: false
+            feeAlgo: linearFee,
+            poolDeposit: 0,
+            keyDeposit: 0,
+            maxValueSize: maxValueSize,
+            maxTxSize: maxTxSize,
+            coinsPerUtxoWord: 1,
+            preferPureChange: false
         )
         var txBuilder = try TransactionBuilder(config: config)
         let spend = try rootKey15()
@@ -540,13 +540,13 @@ final class TransactionBuilderTests: XCTestCase {
     func testBuildTxLeftoverAssets() throws {
         let linearFee = LinearFee(constant: 2, coefficient: 500)
         let config = TransactionBuilderConfig(
-            fee_algo: linearFee,
-            pool_deposit: 1,
-            key_deposit: 1,
-            max_value_size: maxValueSize,
-            max_tx_size: maxTxSize,
-            coins_per_utxo_word: 1,
-            prefer_pure_change: false
+            feeAlgo: linearFee,
+            poolDeposit: 1,
+            keyDeposit: 1,
+            maxValueSize: maxValueSize,
+            maxTxSize: maxTxSize,
+            coinsPerUtxoWord: 1,
+            preferPureChange: false
         )
         var txBuilder = try TransactionBuilder(config: config)
         let spend = try rootKey15()
@@ -603,13 +603,13 @@ final class TransactionBuilderTests: XCTestCase {
     func testBuildTxBurnLessThanMinAda() throws {
         let linearFee = LinearFee(constant: 155381, coefficient: 44)
         let config = TransactionBuilderConfig(
-            fee_algo: linearFee,
-            pool_deposit: 500000000,
-            key_deposit: 2000000,
-            max_value_size: maxValueSize,
-            max_tx_size: maxTxSize,
-            coins_per_utxo_word: 34_482,
-            prefer_pure_change: false
+            feeAlgo: linearFee,
+            poolDeposit: 500000000,
+            keyDeposit: 2000000,
+            maxValueSize: maxValueSize,
+            maxTxSize: maxTxSize,
+            coinsPerUtxoWord: 34_482,
+            preferPureChange: false
         )
         var txBuilder = try TransactionBuilder(config: config)
         let outputAddr = try ByronAddress(base58: "Ae2tdPwUPEZD9QQf2ZrcYV34pYJwxK4vqXaF8EXkup1eYH73zUScHReM42b")
@@ -636,13 +636,13 @@ final class TransactionBuilderTests: XCTestCase {
     func testBuildTxBurnEmptyAssets() throws {
         let linearFee = LinearFee(constant: 155381, coefficient: 44)
         let config = TransactionBuilderConfig(
-            fee_algo: linearFee,
-            pool_deposit: 500000000,
-            key_deposit: 2000000,
-            max_value_size: maxValueSize,
-            max_tx_size: maxTxSize,
-            coins_per_utxo_word: 34_482,
-            prefer_pure_change: false
+            feeAlgo: linearFee,
+            poolDeposit: 500000000,
+            keyDeposit: 2000000,
+            maxValueSize: maxValueSize,
+            maxTxSize: maxTxSize,
+            coinsPerUtxoWord: 34_482,
+            preferPureChange: false
         )
         var txBuilder = try TransactionBuilder(config: config)
         let outputAddr = try ByronAddress(base58: "Ae2tdPwUPEZD9QQf2ZrcYV34pYJwxK4vqXaF8EXkup1eYH73zUScHReM42b")
@@ -667,4 +667,59 @@ final class TransactionBuilderTests: XCTestCase {
         )
         XCTAssertNoThrow(try txBuilder.build())
     }
+    
+    func testBuildTxWithReferenceInput() throws {
+        let linearFee = LinearFee(constant: 155381, coefficient: 44)
+        let config = TransactionBuilderConfig(
+            feeAlgo: linearFee,
+            poolDeposit: 500000000,
+            keyDeposit: 2000000,
+            maxValueSize: maxValueSize,
+            maxTxSize: maxTxSize,
+            coinsPerUtxoWord: 34_482,
+            preferPureChange: false,
+            refScriptCoinsPerByte: SubCoin(numerator: 15, denominator: 1)
+        )
+        var txBuilder = try TransactionBuilder(config: config)
+        let address = try ByronAddress(base58: "Ae2tdPwUPEZ5uzkzh1o2DHECiUi3iugvnnKHRisPgRRP3CTF4KCMvy54Xd3").toAddress()
+        let referenceInput = TransactionInput(transaction_id: genesisId(), index: 1)
+        try txBuilder.addReferenceInput(
+            utxo: TransactionUnspentOutput(
+                input: referenceInput,
+                output: TransactionOutput(address: address, amount: Value(coin: 2_000_000))
+            )
+        )
+        XCTAssertEqual(txBuilder.referenceInputs.map { $0.input }, [referenceInput])
+        try txBuilder.addInput(
+            address: address,
+            input: TransactionInput(transaction_id: genesisId(), index: 0),
+            amount: Value(coin: 5_000_000)
+        )
+        txBuilder.ttl = 1
+        XCTAssertTrue(try txBuilder.addChangeIfNeeded(address: address))
+        XCTAssertEqual(try txBuilder.build().referenceInputs, [referenceInput])
+    }
+    
+    func testBuildTxBabbageMinAda() throws {
+        let linearFee = LinearFee(constant: 155381, coefficient: 44)
+        let config = TransactionBuilderConfig(
+            feeAlgo: linearFee,
+            poolDeposit: 500000000,
+            keyDeposit: 2000000,
+            maxValueSize: maxValueSize,
+            maxTxSize: maxTxSize,
+            coinsPerUtxoWord: 0,
+            preferPureChange: false,
+            coinsPerUtxoByte: 4310
+        )
+        var txBuilder = try TransactionBuilder(config: config)
+        let address = try Address(bech32: "addr1vx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzers66hrl8")
+        XCTAssertThrowsError(try txBuilder.addOutput(
+            output: TransactionOutput(address: address, amount: Value(coin: 849_069))
+        ))
+        try txBuilder.addOutput(
+            output: TransactionOutput(address: address, amount: Value(coin: 900_000))
+        )
+        XCTAssertEqual(txBuilder.outputs.count, 1)
+    }
 }
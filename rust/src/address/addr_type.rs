@@ -1,4 +1,6 @@
+use crate::error::CError;
 use cardano_serialization_lib::address::*;
+use std::convert::TryFrom;
 
 pub enum AddrType {
   Base(BaseAddress),
@@ -8,18 +10,28 @@ pub enum AddrType {
   Byron(ByronAddress),
 }
 
-struct MAddress(AddrType);
+impl TryFrom<Address> for AddrType {
+  type Error = CError;
 
-impl From<Address> for AddrType {
-  fn from(address: Address) -> Self {
-    let maddr: MAddress = unsafe { std::mem::transmute(address) };
-    maddr.0
+  fn try_from(address: Address) -> Result<Self, CError> {
+    BaseAddress::from_address(&address)
+      .map(AddrType::Base)
+      .or_else(|| PointerAddress::from_address(&address).map(AddrType::Ptr))
+      .or_else(|| EnterpriseAddress::from_address(&address).map(AddrType::Enterprise))
+      .or_else(|| RewardAddress::from_address(&address).map(AddrType::Reward))
+      .or_else(|| ByronAddress::from_address(&address).map(AddrType::Byron))
+      .ok_or_else(|| "Address has unknown type".into())
   }
 }
 
 impl From<AddrType> for Address {
   fn from(t: AddrType) -> Self {
-    let maddr = MAddress(t);
-    unsafe { std::mem::transmute(maddr) }
+    match t {
+      AddrType::Base(base) => base.to_address(),
+      AddrType::Ptr(ptr) => ptr.to_address(),
+      AddrType::Enterprise(enterprise) => enterprise.to_address(),
+      AddrType::Reward(reward) => reward.to_address(),
+      AddrType::Byron(byron) => byron.to_address(),
+    }
   }
 }
\ No newline at end of file
@@ -35,7 +35,7 @@ impl TryFrom<RAddress> for Address {
   type Error = CError;
 
   fn try_from(address: RAddress) -> Result<Self> {
-    let t: AddrType = address.into();
+    let t: AddrType = address.try_into()?;
     match t {
       AddrType::Base(base) => base.try_into().map(Address::Base),
       AddrType::Byron(byron) => Ok(Address::Byron(byron.into())),
@@ -133,4 +133,40 @@ pub unsafe extern "C" fn cardano_address_clone(
 #[no_mangle]
 pub unsafe extern "C" fn cardano_address_free(address: &mut Address) {
   address.free();
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn address_round_trips_every_kind() {
+    let addresses = [
+      concat!(
+        "addr1qx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzer3n0d3vllmyqwsx5wktcd8cc3sq835lu7",
+        "drv2xwl2wywfgse35a3x",
+      ),
+      "addr1gx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzer5pnz75xxcrzqf96k",
+      "addr1vx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzers66hrl8",
+      "stake1uyehkck0lajq8gr28t9uxnuvgcqrc6070x3k9r8048z8y5gh6ffgw",
+    ];
+    for bech32 in addresses {
+      let address = RAddress::from_bech32(bech32).unwrap();
+      let c_address: Address = address.clone().try_into().unwrap();
+      let back: RAddress = c_address.try_into().unwrap();
+      assert_eq!(back.to_bytes(), address.to_bytes());
+    }
+    let byron = RAddress::from_bytes(
+      cardano_serialization_lib::address::ByronAddress::from_base58(
+        "Ae2tdPwUPEZ5uzkzh1o2DHECiUi3iugvnnKHRisPgRRP3CTF4KCMvy54Xd3",
+      )
+      .unwrap()
+      .to_address()
+      .to_bytes(),
+    )
+    .unwrap();
+    let c_address: Address = byron.clone().try_into().unwrap();
+    let back: RAddress = c_address.try_into().unwrap();
+    assert_eq!(back.to_bytes(), byron.to_bytes());
+  }
+}
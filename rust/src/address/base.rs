@@ -2,10 +2,7 @@ use crate::stake_credential::StakeCredential;
 use crate::error::CError;
 use crate::panic::*;
 use std::convert::{TryInto, TryFrom};
-use cardano_serialization_lib::address::{
-  BaseAddress as RBaseAddress,
-  StakeCredential as RStakeCredential
-};
+use cardano_serialization_lib::address::BaseAddress as RBaseAddress;
 
 #[repr(C)]
 #[derive(Copy, Clone)]
@@ -15,21 +12,18 @@ pub struct BaseAddress {
   stake: StakeCredential
 }
 
-// For transmute black magick
-struct MBAddress {
-  network: u8,
-  payment: RStakeCredential,
-  stake: RStakeCredential
-}
+const _: () = assert!(
+  std::mem::size_of::<BaseAddress>() == 76 && std::mem::align_of::<BaseAddress>() == 4
+);
 
 impl TryFrom<RBaseAddress> for BaseAddress {
   type Error = CError;
 
   fn try_from(address: RBaseAddress) -> Result<Self> {
-    let mbaddr: MBAddress = unsafe { std::mem::transmute(address) };
-    let payment = mbaddr.payment.try_into()?;
-    let stake = mbaddr.stake.try_into()?;
-    Ok(Self { network: mbaddr.network, payment: payment, stake: stake })
+    let network = address.to_address().network_id().into_result()?;
+    let payment = address.payment_cred().try_into()?;
+    let stake = address.stake_cred().try_into()?;
+    Ok(Self { network, payment, stake })
   }
 }
 
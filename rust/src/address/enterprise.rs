@@ -1,12 +1,9 @@
 use crate::stake_credential::StakeCredential;
 use crate::error::CError;
-use crate::panic::Result;
+use crate::panic::*;
 use std::convert::{TryInto, TryFrom};
 
-use cardano_serialization_lib::address::{
-  EnterpriseAddress as REnterpriseAddress,
-  StakeCredential as RStakeCredential
-};
+use cardano_serialization_lib::address::EnterpriseAddress as REnterpriseAddress;
 
 #[repr(C)]
 #[derive(Copy, Clone)]
@@ -15,18 +12,17 @@ pub struct EnterpriseAddress {
   payment: StakeCredential
 }
 
-struct MEAddress {
-  network: u8,
-  payment: RStakeCredential
-}
+const _: () = assert!(
+  std::mem::size_of::<EnterpriseAddress>() == 40 && std::mem::align_of::<EnterpriseAddress>() == 4
+);
 
 impl TryFrom<REnterpriseAddress> for EnterpriseAddress {
   type Error = CError;
 
   fn try_from(address: REnterpriseAddress) -> Result<Self> {
-    let maddress: MEAddress = unsafe { std::mem::transmute(address) };
-    let payment = maddress.payment.try_into()?;
-    Ok(Self { network: maddress.network, payment: payment })
+    let network = address.to_address().network_id().into_result()?;
+    let payment = address.payment_cred().try_into()?;
+    Ok(Self { network, payment })
   }
 }
 
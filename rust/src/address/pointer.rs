@@ -1,12 +1,11 @@
 use crate::error::CError;
-use crate::panic::Result;
+use crate::panic::*;
 use crate::transaction_input::TransactionIndex;
 use std::convert::{TryInto, TryFrom};
 use crate::stake_credential::StakeCredential;
 
 use cardano_serialization_lib::address::{
   Pointer as RPointer,
-  StakeCredential as RStakeCredential,
   PointerAddress as RPointerAddress
 };
 
@@ -44,19 +43,17 @@ pub struct PointerAddress {
   stake: Pointer,
 }
 
-struct MPAddress {
-  network: u8,
-  payment: RStakeCredential,
-  stake: RPointer,
-}
+const _: () = assert!(
+  std::mem::size_of::<PointerAddress>() == 52 && std::mem::align_of::<PointerAddress>() == 4
+);
 
 impl TryFrom<RPointerAddress> for PointerAddress {
   type Error = CError;
 
   fn try_from(address: RPointerAddress) -> Result<Self> {
-    let maddress: MPAddress = unsafe { std::mem::transmute(address) };
-    let payment = maddress.payment.try_into()?;
-    Ok(Self { network: maddress.network, payment: payment, stake: maddress.stake.into() })
+    let network = address.to_address().network_id().into_result()?;
+    let payment = address.payment_cred().try_into()?;
+    Ok(Self { network, payment, stake: address.stake_pointer().into() })
   }
 }
 
@@ -1,11 +1,10 @@
+use crate::cbor::*;
 use crate::error::CError;
-use crate::panic::Result;
+use crate::panic::*;
 use crate::stake_credential::StakeCredential;
 use std::convert::{TryFrom, TryInto};
 
-use cardano_serialization_lib::address::{
-  RewardAddress as RRewardAddress, StakeCredential as RStakeCredential,
-};
+use cardano_serialization_lib::address::{Address as RAddress, RewardAddress as RRewardAddress};
 
 #[repr(C)]
 #[derive(Copy, Clone, Hash, PartialEq, Eq)]
@@ -14,20 +13,19 @@ pub struct RewardAddress {
   payment: StakeCredential,
 }
 
-struct MRAddress {
-  network: u8,
-  payment: RStakeCredential,
-}
+const _: () = assert!(
+  std::mem::size_of::<RewardAddress>() == 40 && std::mem::align_of::<RewardAddress>() == 4
+);
 
 impl TryFrom<RRewardAddress> for RewardAddress {
   type Error = CError;
 
   fn try_from(address: RRewardAddress) -> Result<Self> {
-    let maddress: MRAddress = unsafe { std::mem::transmute(address) };
-    let payment = maddress.payment.try_into()?;
+    let network = address.to_address().network_id().into_result()?;
+    let payment = address.payment_cred().try_into()?;
     Ok(Self {
-      network: maddress.network,
-      payment: payment,
+      network,
+      payment,
     })
   }
 }
@@ -37,3 +35,22 @@ impl From<RewardAddress> for RRewardAddress {
     Self::new(address.network, &address.payment.into())
   }
 }
+
+impl CborSerialize for RewardAddress {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    let address: RRewardAddress = (*self).into();
+    se.write_bytes(address.to_address().to_bytes())?;
+    Ok(())
+  }
+}
+
+impl CborDeserialize for RewardAddress {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    RAddress::from_bytes(de.bytes()?)
+      .into_result()
+      .and_then(|address| {
+        RRewardAddress::from_address(&address).ok_or_else(|| "Not a reward address".into())
+      })
+      .and_then(|address| address.try_into())
+  }
+}
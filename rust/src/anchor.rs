@@ -0,0 +1,151 @@
+use crate::cbor::*;
+use crate::data::CData;
+use crate::error::CError;
+use crate::panic::*;
+use crate::ptr::*;
+use crate::string::*;
+use cbor_event::Len;
+use std::convert::TryInto;
+
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct AnchorDataHash([u8; 32]);
+
+impl CborSerialize for AnchorDataHash {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    se.write_bytes(self.0)?;
+    Ok(())
+  }
+}
+
+impl CborDeserialize for AnchorDataHash {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    de.bytes()?
+      .try_into()
+      .map(Self)
+      .map_err(|_| CError::DataLengthMismatch)
+  }
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_anchor_data_hash_to_bytes(
+  data_hash: AnchorDataHash, result: &mut CData, error: &mut CError,
+) -> bool {
+  handle_exception(|| data_hash.0.to_vec().into()).response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_anchor_data_hash_from_bytes(
+  data: CData, result: &mut AnchorDataHash, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    data.unowned().and_then(|bytes| {
+      bytes
+        .try_into()
+        .map(AnchorDataHash)
+        .map_err(|_| CError::DataLengthMismatch)
+    })
+  })
+  .response(result, error)
+}
+
+#[repr(C)]
+#[derive(Copy)]
+pub struct Anchor {
+  url: CharPtr,
+  data_hash: AnchorDataHash,
+}
+
+#[allow(clippy::non_canonical_clone_impl)]
+impl Clone for Anchor {
+  fn clone(&self) -> Self {
+    let url: String = unsafe { self.url.unowned().expect("Bad char pointer").into() };
+    Self { url: url.into_cstr(), data_hash: self.data_hash }
+  }
+}
+
+impl Free for Anchor {
+  unsafe fn free(&mut self) {
+    self.url.free();
+  }
+}
+
+impl CborSerialize for Anchor {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    let url = unsafe { self.url.unowned()? };
+    if url.len() > 128 {
+      return Err("Anchor url is longer than 128 bytes".into());
+    }
+    se.write_array(Len::Len(2))?;
+    se.write_text(url)?;
+    self.data_hash.serialize(se)
+  }
+}
+
+impl CborDeserialize for Anchor {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    let len = de.array()?;
+    if len != Len::Len(2) && len != Len::Indefinite {
+      return Err("Wrong Anchor array length".into());
+    }
+    let url = de.text()?;
+    let data_hash = AnchorDataHash::deserialize(de)?;
+    read_array_end(de, len)?;
+    Ok(Self { url: url.into_cstr(), data_hash })
+  }
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_anchor_to_bytes(
+  anchor: Anchor, result: &mut CData, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| anchor.to_cbor().map(|bytes| bytes.into())).response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_anchor_from_bytes(
+  data: CData, result: &mut Anchor, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| data.unowned().and_then(Anchor::from_cbor))
+    .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc, clippy::clone_on_copy)]
+pub unsafe extern "C" fn cardano_anchor_clone(
+  anchor: Anchor, result: &mut Anchor, error: &mut CError,
+) -> bool {
+  handle_exception(|| anchor.clone()).response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_anchor_free(anchor: &mut Anchor) {
+  anchor.free()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn anchor_round_trips() {
+    let mut bytes = vec![0x82, 0x73];
+    bytes.extend(b"https://example.com");
+    bytes.extend([0x58, 0x20]);
+    bytes.extend([9; 32]);
+    let anchor = Anchor::from_cbor(&bytes).unwrap();
+    assert!(anchor.data_hash == AnchorDataHash([9; 32]));
+    assert_eq!(anchor.to_cbor().unwrap(), bytes);
+  }
+
+  #[test]
+  fn long_anchor_url_is_rejected() {
+    let anchor = Anchor { url: "a".repeat(129).into_cstr(), data_hash: AnchorDataHash([0; 32]) };
+    assert!(anchor.to_cbor().is_err());
+  }
+}
@@ -1,17 +1,116 @@
 use std::convert::{TryInto, TryFrom};
+use crate::array::CArray;
 use crate::private_key::PrivateKey;
 use super::data::CData;
-use super::ptr::Ptr;
+use super::ptr::{Free, Ptr};
 use super::error::CError;
 use super::panic::*;
 use super::string::*;
 use super::bip32_public_key::Bip32PublicKey;
+use super::bip39::Bip39Wordlist;
+use super::secret_key::wipe;
 use cardano_serialization_lib::crypto::{Bip32PrivateKey as RBip32PrivateKey};
+use cryptoxide::hashing::sha256;
+use cryptoxide::hmac::Hmac;
+use cryptoxide::mac::Mac;
+use cryptoxide::pbkdf2::pbkdf2;
+use cryptoxide::sha2::{Sha256, Sha512};
+use unicode_normalization::UnicodeNormalization;
 
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub struct Bip32PrivateKey([u8; 96]);
 
+// Indices of the keys derived one after the other, hardened ones included
+pub type DerivationPath = CArray<u32>;
+pub type DerivationPaths = CArray<DerivationPath>;
+
+pub fn derive_path(key: Bip32PrivateKey, path: &[u32]) -> Result<RBip32PrivateKey> {
+  key
+    .try_into()
+    .map(|key: RBip32PrivateKey| path.iter().fold(key, |key, index| key.derive(*index)))
+}
+
+// CIP-3 Ledger scheme: the BIP-39 seed of the phrase is hashed with
+// HMAC-SHA512 until the third highest bit of the key is cleared
+pub fn from_ledger_mnemonic(mnemonic: &str, password: &[u8]) -> Result<RBip32PrivateKey> {
+  // The seed hashes the NFKD form, where the ideographic space is a plain one
+  let mnemonic: String = mnemonic.nfkd().collect();
+  let mut seed = [0u8; 64];
+  let salt = [b"mnemonic".as_ref(), password].concat();
+  pbkdf2(&mut Hmac::new(Sha512::new(), mnemonic.as_bytes()), &salt, 2048, &mut seed);
+  let mut key = [0u8; 96];
+  let mut message = seed.to_vec();
+  loop {
+    let mut mac = Hmac::new(Sha512::new(), b"ed25519 seed");
+    mac.input(&message);
+    mac.raw_result(&mut key[..64]);
+    if key[31] & 0b0010_0000 == 0 {
+      break;
+    }
+    message = key[..64].to_vec();
+  }
+  key[0] &= 0b1111_1000;
+  key[31] &= 0b0111_1111;
+  key[31] |= 0b0100_0000;
+  let mut mac = Hmac::new(Sha256::new(), b"ed25519 seed");
+  mac.input(&[1]);
+  mac.input(&seed);
+  mac.raw_result(&mut key[64..]);
+  RBip32PrivateKey::from_bytes(&key).into_result()
+}
+
+// CIP-3 Trezor scheme: Icarus, but 24 word phrases keep their checksum
+// byte in the entropy
+pub fn from_trezor_entropy(entropy: &[u8], password: &[u8]) -> RBip32PrivateKey {
+  if entropy.len() == 32 {
+    let entropy = [entropy, &sha256(entropy)[..1]].concat();
+    RBip32PrivateKey::from_bip39_entropy(&entropy, password)
+  } else {
+    RBip32PrivateKey::from_bip39_entropy(entropy, password)
+  }
+}
+
+pub const HARDENED: u32 = 0x80000000;
+
+// Parses paths written as m/1852'/1815'/0'/0/5, with ' or h marking hardened indices
+pub fn parse_derivation_path(path: &str) -> Result<Vec<u32>> {
+  let mut parts = path.trim().split('/');
+  if parts.next() != Some("m") {
+    return Err("Derivation path must start with m".into());
+  }
+  parts
+    .map(|part| {
+      let (index, hardened) = match part.strip_suffix(|c| c == '\'' || c == 'h' || c == 'H') {
+        Some(index) => (index, true),
+        None => (part, false),
+      };
+      index
+        .parse::<u32>()
+        .ok()
+        .filter(|index| *index < HARDENED)
+        .map(|index| if hardened { index | HARDENED } else { index })
+        .ok_or_else(|| format!("Bad derivation path index: {}", part).into())
+    })
+    .collect()
+}
+
+pub fn format_derivation_path(path: &[u32]) -> String {
+  path.iter().fold("m".to_string(), |path, index| {
+    if index & HARDENED != 0 {
+      format!("{}/{}'", path, index & !HARDENED)
+    } else {
+      format!("{}/{}", path, index)
+    }
+  })
+}
+
+impl Bip32PrivateKey {
+  pub fn wipe(&mut self) {
+    wipe(&mut self.0);
+  }
+}
+
 impl TryFrom<Bip32PrivateKey> for RBip32PrivateKey {
   type Error = CError;
 
@@ -56,7 +155,7 @@ pub unsafe extern "C" fn cardano_bip32_private_key_to_raw_key(
   handle_exception_result(|| {
     pk.try_into()
       .map(|pk: RBip32PrivateKey| pk.to_raw_key())
-      .map(|pk| pk.into())
+      .and_then(|pk| pk.try_into())
   })
   .response(result, error)
 }
@@ -124,6 +223,38 @@ pub unsafe extern "C" fn cardano_bip32_private_key_from_bip39_entropy(
   }).response(result, error)
 }
 
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_bip32_private_key_from_ledger_mnemonic(
+  wordlist: *const Bip39Wordlist, mnemonic: CharPtr, password: CData,
+  result: &mut Bip32PrivateKey, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    Bip39Wordlist::from_handle(wordlist)
+      .zip(mnemonic.unowned())
+      .and_then(|(wordlist, mnemonic)| wordlist.normalize(mnemonic))
+      .zip(password.unowned())
+      .and_then(|(mnemonic, pwd)| from_ledger_mnemonic(&mnemonic, pwd))
+      .map(|pk| pk.into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_bip32_private_key_from_trezor_entropy(
+  entropy: CData, password: CData, result: &mut Bip32PrivateKey, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    entropy
+      .unowned()
+      .zip(password.unowned())
+      .map(|(ent, pwd)| from_trezor_entropy(ent, pwd))
+      .map(|pk| pk.into())
+  })
+  .response(result, error)
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn cardano_bip32_private_key_from_128_xprv(
   data: CData, result: &mut Bip32PrivateKey, error: &mut CError,
@@ -160,3 +291,131 @@ pub unsafe extern "C" fn cardano_bip32_private_key_chaincode(
   })
   .response(result, error)
 }
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_bip32_private_key_derive_path(
+  pk: Bip32PrivateKey, path: DerivationPath, result: &mut Bip32PrivateKey, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    path
+      .unowned()
+      .and_then(|path| derive_path(pk, path))
+      .map(|pk| pk.into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_derivation_path_from_string(
+  path: CharPtr, result: &mut DerivationPath, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    path
+      .unowned()
+      .and_then(parse_derivation_path)
+      .map(|path| path.into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_derivation_path_to_string(
+  path: DerivationPath, result: &mut CharPtr, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    path
+      .unowned()
+      .map(|path| format_derivation_path(path).into_cstr())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_derivation_path_free(path: &mut DerivationPath) {
+  path.free();
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_bip32_private_key_wipe(pk: &mut Bip32PrivateKey) {
+  pk.wipe();
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::bip39::Bip39Language;
+  use crate::test_utils::hex;
+
+  const LEDGER_MNEMONIC: &str = concat!(
+    "recall grace sport punch exhibit mad harbor stand obey short width stem ",
+    "awkward used stairs wool ugly trap season stove worth toward congress jaguar",
+  );
+
+  // CIP-3 Ledger master keys
+  #[test]
+  fn ledger_keys_match_cip3() {
+    let key = from_ledger_mnemonic(LEDGER_MNEMONIC, b"").unwrap();
+    assert_eq!(
+      key.as_bytes(),
+      hex(concat!(
+        "a08cf85b564ecf3b947d8d4321fb96d70ee7bb760877e371899b14e2ccf88658",
+        "104b884682b57efd97decbb318a45c05a527b9cc5c2f64f7352935a049ceea60",
+        "680d52308194ccef2a18e6812b452a5815fbd7f5babc083856919aaf668fe7e4",
+      ))
+    );
+    let key = from_ledger_mnemonic(LEDGER_MNEMONIC, b"foo").unwrap();
+    assert_eq!(
+      key.as_bytes(),
+      hex(concat!(
+        "488b13cdf56ed4ced9b2bcd61924ca7a81d317810d4ddbbd8ab3e86431289a58",
+        "e0e3f635ab873a97f046c77bca4203419125293c9ab6d97a252a07353fdfa050",
+        "55209a1e929b4223692cbf6900d41e8b42d14449ede56dbab8c4c7fd981e139a",
+      ))
+    );
+    // The first HMAC result has the third highest bit set, so it is hashed again
+    let mnemonic = concat!(
+      "correct cherry mammal bubble want mandate polar hazard crater better craft exotic ",
+      "choice fun tourist census gap lottery neglect address glow carry old business",
+    );
+    let key = from_ledger_mnemonic(mnemonic, b"").unwrap();
+    assert_eq!(
+      key.as_bytes(),
+      hex(concat!(
+        "587c6774357ecbf840d4db6404ff7af016dace0400769751ad2abfc77b9a3844",
+        "cc71702520ef1a4d1b68b91187787a9b8faab0a9bb6b160de541b6ee62469901",
+        "fc0beda0975fe4763beabd83b7051a5fd5cbce5b88e82c4bbaca265014e524bd",
+      ))
+    );
+  }
+
+  // CIP-3 Trezor master key of a 24 word phrase, which keeps the checksum in the entropy
+  #[test]
+  fn trezor_key_matches_cip3() {
+    let entropy = hex("f585c11aec520db57dd353c69554b21a89b20fb0650966fa0a9d6f74fd989d8f");
+    let key = from_trezor_entropy(&entropy, b"");
+    assert_eq!(
+      key.as_bytes(),
+      hex(concat!(
+        "409bb7a2998ec48029c8d2956fabd043a368ccc9b5120e42dd8a5c7145d08f45",
+        "e8e8664d06f62b4fc3bab0134778af27ddf059a4ad1eb0efefeedd8189bbfe00",
+        "deb289c5cdc2cf8ccfa19aea63b28424a4b0045b4b762292d46b73aa1c5cc99a",
+      ))
+    );
+    assert_ne!(key.as_bytes(), RBip32PrivateKey::from_bip39_entropy(&entropy, b"").as_bytes());
+  }
+
+  #[test]
+  fn ledger_key_of_japanese_phrase_ignores_the_separator() {
+    let wordlist = Bip39Wordlist::standard(Bip39Language::Japanese).unwrap();
+    let mnemonic = wordlist.entropy_to_mnemonic(&[7; 32]).unwrap();
+    let normalized = wordlist.normalize(&mnemonic).unwrap();
+    let key = from_ledger_mnemonic(&normalized, b"").unwrap();
+    let spaced = from_ledger_mnemonic(&normalized.replace('\u{3000}', " "), b"").unwrap();
+    assert_eq!(key.as_bytes(), spaced.as_bytes());
+  }
+}
@@ -0,0 +1,528 @@
+use crate::array::CArray;
+use crate::bip32_private_key::Bip32PrivateKey;
+use crate::data::CData;
+use crate::error::CError;
+use crate::panic::*;
+use crate::ptr::*;
+use crate::string::*;
+use cardano_serialization_lib::crypto::Bip32PrivateKey as RBip32PrivateKey;
+use cryptoxide::hashing::sha256;
+use std::collections::HashMap;
+use unicode_normalization::UnicodeNormalization;
+
+const ENGLISH: &str = include_str!("wordlists/english.txt");
+const JAPANESE: &str = include_str!("wordlists/japanese.txt");
+const KOREAN: &str = include_str!("wordlists/korean.txt");
+const SPANISH: &str = include_str!("wordlists/spanish.txt");
+const CHINESE_SIMPLIFIED: &str = include_str!("wordlists/chinese_simplified.txt");
+const CHINESE_TRADITIONAL: &str = include_str!("wordlists/chinese_traditional.txt");
+const FRENCH: &str = include_str!("wordlists/french.txt");
+const ITALIAN: &str = include_str!("wordlists/italian.txt");
+const CZECH: &str = include_str!("wordlists/czech.txt");
+const PORTUGUESE: &str = include_str!("wordlists/portuguese.txt");
+// Japanese mnemonics are written with the ideographic space
+const JAPANESE_SEPARATOR: &str = "\u{3000}";
+const WORDLIST_SIZE: usize = 2048;
+const BITS_PER_WORD: usize = 11;
+const MAX_SUGGESTIONS: usize = 5;
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+// Standard BIP-39 wordlists built into the library
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Bip39Language {
+  English,
+  Japanese,
+  Korean,
+  Spanish,
+  ChineseSimplified,
+  ChineseTraditional,
+  French,
+  Italian,
+  Czech,
+  Portuguese,
+}
+
+impl Bip39Language {
+  fn words(&self) -> &'static str {
+    match self {
+      Self::English => ENGLISH,
+      Self::Japanese => JAPANESE,
+      Self::Korean => KOREAN,
+      Self::Spanish => SPANISH,
+      Self::ChineseSimplified => CHINESE_SIMPLIFIED,
+      Self::ChineseTraditional => CHINESE_TRADITIONAL,
+      Self::French => FRENCH,
+      Self::Italian => ITALIAN,
+      Self::Czech => CZECH,
+      Self::Portuguese => PORTUGUESE,
+    }
+  }
+
+  fn separator(&self) -> &'static str {
+    match self {
+      Self::Japanese => JAPANESE_SEPARATOR,
+      _ => " ",
+    }
+  }
+}
+
+// Opaque heap-allocated BIP-39 wordlist, one of the standard ones or a custom
+// list given by the caller. Words and mnemonics are compared in lowercase NFKD form.
+pub struct Bip39Wordlist {
+  words: Vec<String>,
+  indices: HashMap<String, usize>,
+  separator: String,
+}
+
+impl Bip39Wordlist {
+  pub fn new(words: Vec<String>, separator: String) -> Result<Self> {
+    if words.len() != WORDLIST_SIZE {
+      return Err(format!("Wordlist must have {} words", WORDLIST_SIZE).into());
+    }
+    let words: Vec<String> = words.iter().map(|word| normalize_word(word)).collect();
+    let indices: HashMap<String, usize> = words
+      .iter()
+      .enumerate()
+      .map(|(index, word)| (word.clone(), index))
+      .collect();
+    if indices.len() != WORDLIST_SIZE {
+      return Err("Wordlist has duplicate words".into());
+    }
+    if words.iter().any(|word| word.is_empty() || word.contains(char::is_whitespace)) {
+      return Err("Wordlist words must be non-empty and have no whitespace".into());
+    }
+    Ok(Self { words, indices, separator })
+  }
+
+  pub fn standard(language: Bip39Language) -> Result<Self> {
+    let words = language.words().lines().map(|word| word.to_string()).collect();
+    Self::new(words, language.separator().to_string())
+  }
+
+  pub fn english() -> Result<Self> {
+    Self::standard(Bip39Language::English)
+  }
+
+  fn into_handle(self) -> *mut Self {
+    Box::into_raw(Box::new(self))
+  }
+
+  #[allow(clippy::missing_safety_doc)]
+  pub unsafe fn from_handle<'a>(handle: *const Self) -> Result<&'a Self> {
+    handle.as_ref().ok_or(CError::NullPtr)
+  }
+
+  pub fn generate(&self, word_count: u32) -> Result<String> {
+    let mut entropy = vec![0u8; entropy_len(word_count as usize)?];
+    getrandom::getrandom(&mut entropy).map_err(|err| format!("{}", err))?;
+    self.entropy_to_mnemonic(&entropy)
+  }
+
+  pub fn entropy_to_mnemonic(&self, entropy: &[u8]) -> Result<String> {
+    if ![16, 20, 24, 28, 32].contains(&entropy.len()) {
+      return Err("Entropy must be 16, 20, 24, 28 or 32 bytes".into());
+    }
+    let mut bits = to_bits(entropy);
+    bits.extend(to_bits(&checksum(entropy)).into_iter().take(entropy.len() / 4));
+    let words: Vec<&str> = bits
+      .chunks(BITS_PER_WORD)
+      .map(|chunk| chunk.iter().fold(0, |index, bit| index << 1 | *bit as usize))
+      .map(|index| self.words[index].as_str())
+      .collect();
+    Ok(words.join(&self.separator))
+  }
+
+  pub fn mnemonic_to_entropy(&self, mnemonic: &str) -> Result<Vec<u8>> {
+    let words = split_words(mnemonic);
+    let entropy_len = entropy_len(words.len())?;
+    let bits = words
+      .iter()
+      .map(|word| {
+        self
+          .indices
+          .get(word)
+          .map(|index| (0..BITS_PER_WORD).rev().map(move |bit| index >> bit & 1 == 1))
+          .ok_or_else(|| CError::from(format!("Unknown word: {}", word)))
+      })
+      .collect::<Result<Vec<_>>>()?
+      .into_iter()
+      .flatten()
+      .collect::<Vec<bool>>();
+    let (entropy_bits, checksum_bits) = bits.split_at(entropy_len * 8);
+    let entropy = from_bits(entropy_bits);
+    if to_bits(&checksum(&entropy))[..checksum_bits.len()] != *checksum_bits {
+      return Err("Invalid mnemonic checksum".into());
+    }
+    Ok(entropy)
+  }
+
+  // Checked phrase in NFKD form with its words joined by the wordlist separator
+  pub fn normalize(&self, mnemonic: &str) -> Result<String> {
+    self
+      .mnemonic_to_entropy(mnemonic)
+      .map(|_| split_words(mnemonic).join(&self.separator))
+  }
+
+  pub fn validate(&self, mnemonic: &str) -> MnemonicValidation {
+    let words = split_words(mnemonic);
+    let unknown_words: Vec<MnemonicWordSuggestions> = words
+      .iter()
+      .enumerate()
+      .filter(|(_, word)| !self.indices.contains_key(word.as_str()))
+      .map(|(index, word)| MnemonicWordSuggestions {
+        index: index as u32,
+        word: word.into_cstr(),
+        suggestions: self
+          .suggestions(word)
+          .into_iter()
+          .map(|suggestion| suggestion.into_cstr())
+          .collect::<Vec<CharPtr>>()
+          .into(),
+      })
+      .collect();
+    let status = if !unknown_words.is_empty() {
+      MnemonicStatus::UnknownWords
+    } else if entropy_len(words.len()).is_err() {
+      MnemonicStatus::InvalidWordCount
+    } else if self.mnemonic_to_entropy(mnemonic).is_err() {
+      MnemonicStatus::InvalidChecksum
+    } else {
+      MnemonicStatus::Valid
+    };
+    MnemonicValidation { status, unknown_words: unknown_words.into() }
+  }
+
+  // Words starting with the typed ones, or the closest ones for typos
+  pub fn suggestions(&self, word: &str) -> Vec<&str> {
+    let completions: Vec<&str> = self
+      .words
+      .iter()
+      .filter(|candidate| candidate.starts_with(word))
+      .take(MAX_SUGGESTIONS)
+      .map(|candidate| candidate.as_str())
+      .collect();
+    if !completions.is_empty() {
+      return completions;
+    }
+    let mut closest: Vec<(usize, &str)> = self
+      .words
+      .iter()
+      .map(|candidate| (edit_distance(word, candidate), candidate.as_str()))
+      .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE)
+      .collect();
+    // Stable sort keeps the wordlist order for words at the same distance
+    closest.sort_by_key(|(distance, _)| *distance);
+    closest.into_iter().take(MAX_SUGGESTIONS).map(|(_, candidate)| candidate).collect()
+  }
+}
+
+fn entropy_len(word_count: usize) -> Result<usize> {
+  match word_count {
+    12 | 15 | 18 | 21 | 24 => Ok(word_count * BITS_PER_WORD * 32 / 33 / 8),
+    _ => Err("Mnemonic must have 12, 15, 18, 21 or 24 words".into()),
+  }
+}
+
+fn split_words(mnemonic: &str) -> Vec<String> {
+  mnemonic.split_whitespace().map(normalize_word).collect()
+}
+
+// Lowercase NFKD form, so precomposed and decomposed accents are the same word
+fn normalize_word(word: &str) -> String {
+  word.to_lowercase().nfkd().collect()
+}
+
+fn checksum(entropy: &[u8]) -> [u8; 1] {
+  [sha256(entropy)[0]]
+}
+
+fn to_bits(bytes: &[u8]) -> Vec<bool> {
+  bytes
+    .iter()
+    .flat_map(|byte| (0..8).rev().map(move |bit| byte >> bit & 1 == 1))
+    .collect()
+}
+
+fn from_bits(bits: &[bool]) -> Vec<u8> {
+  bits
+    .chunks(8)
+    .map(|chunk| chunk.iter().fold(0, |byte, bit| byte << 1 | *bit as u8))
+    .collect()
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+  let b: Vec<char> = b.chars().collect();
+  let mut row: Vec<usize> = (0..=b.len()).collect();
+  for (i, ca) in a.chars().enumerate() {
+    let mut diagonal = row[0];
+    row[0] = i + 1;
+    for (j, cb) in b.iter().enumerate() {
+      let substitution = diagonal + (ca != *cb) as usize;
+      diagonal = row[j + 1];
+      row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+    }
+  }
+  row[b.len()]
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MnemonicStatus {
+  Valid,
+  InvalidWordCount,
+  UnknownWords,
+  InvalidChecksum,
+}
+
+// Word of the mnemonic missing from the wordlist, at its position
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct MnemonicWordSuggestions {
+  index: u32,
+  word: CharPtr,
+  suggestions: CArray<CharPtr>,
+}
+
+impl Free for MnemonicWordSuggestions {
+  unsafe fn free(&mut self) {
+    self.word.free();
+    self.suggestions.free();
+  }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct MnemonicValidation {
+  status: MnemonicStatus,
+  unknown_words: CArray<MnemonicWordSuggestions>,
+}
+
+impl Free for MnemonicValidation {
+  unsafe fn free(&mut self) {
+    self.unknown_words.free();
+  }
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_bip39_wordlist_english(
+  result: &mut *mut Bip39Wordlist, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| Bip39Wordlist::english().map(Bip39Wordlist::into_handle))
+    .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_bip39_wordlist_standard(
+  language: Bip39Language, result: &mut *mut Bip39Wordlist, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    Bip39Wordlist::standard(language).map(Bip39Wordlist::into_handle)
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_bip39_wordlist_new(
+  words: CArray<CharPtr>, separator: CharPtr, result: &mut *mut Bip39Wordlist,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    let words = words
+      .unowned()?
+      .iter()
+      .map(|word| word.unowned().map(|word| word.to_string()))
+      .collect::<Result<Vec<String>>>()?;
+    separator
+      .unowned()
+      .and_then(|separator| Bip39Wordlist::new(words, separator.to_string()))
+      .map(Bip39Wordlist::into_handle)
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_bip39_generate(
+  wordlist: *const Bip39Wordlist, word_count: u32, result: &mut CharPtr, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    Bip39Wordlist::from_handle(wordlist)
+      .and_then(|wordlist| wordlist.generate(word_count))
+      .map(|mnemonic| mnemonic.into_cstr())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_bip39_entropy_to_mnemonic(
+  wordlist: *const Bip39Wordlist, entropy: CData, result: &mut CharPtr, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    Bip39Wordlist::from_handle(wordlist)
+      .zip(entropy.unowned())
+      .and_then(|(wordlist, entropy)| wordlist.entropy_to_mnemonic(entropy))
+      .map(|mnemonic| mnemonic.into_cstr())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_bip39_mnemonic_to_entropy(
+  wordlist: *const Bip39Wordlist, mnemonic: CharPtr, result: &mut CData, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    Bip39Wordlist::from_handle(wordlist)
+      .zip(mnemonic.unowned())
+      .and_then(|(wordlist, mnemonic)| wordlist.mnemonic_to_entropy(mnemonic))
+      .map(|entropy| entropy.into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_bip39_mnemonic_to_bip32_private_key(
+  wordlist: *const Bip39Wordlist, mnemonic: CharPtr, password: CData,
+  result: &mut Bip32PrivateKey, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    Bip39Wordlist::from_handle(wordlist)
+      .zip(mnemonic.unowned())
+      .and_then(|(wordlist, mnemonic)| wordlist.mnemonic_to_entropy(mnemonic))
+      .zip(password.unowned())
+      .map(|(entropy, password)| RBip32PrivateKey::from_bip39_entropy(&entropy, password))
+      .map(|pk| pk.into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_bip39_validate(
+  wordlist: *const Bip39Wordlist, mnemonic: CharPtr, result: &mut MnemonicValidation,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    Bip39Wordlist::from_handle(wordlist)
+      .zip(mnemonic.unowned())
+      .map(|(wordlist, mnemonic)| wordlist.validate(mnemonic))
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_bip39_suggestions(
+  wordlist: *const Bip39Wordlist, word: CharPtr, result: &mut CArray<CharPtr>,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    Bip39Wordlist::from_handle(wordlist)
+      .zip(word.unowned())
+      .map(|(wordlist, word)| {
+        let suggestions: Vec<CharPtr> = wordlist
+          .suggestions(&normalize_word(word))
+          .into_iter()
+          .map(|suggestion| suggestion.into_cstr())
+          .collect();
+        suggestions.into()
+      })
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_bip39_suggestions_free(suggestions: &mut CArray<CharPtr>) {
+  suggestions.free();
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_mnemonic_validation_free(validation: &mut MnemonicValidation) {
+  validation.free();
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_bip39_wordlist_free(wordlist: &mut *mut Bip39Wordlist) {
+  if !wordlist.is_null() {
+    let _ = Box::from_raw(*wordlist);
+    *wordlist = std::ptr::null_mut();
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const LANGUAGES: [Bip39Language; 10] = [
+    Bip39Language::English,
+    Bip39Language::Japanese,
+    Bip39Language::Korean,
+    Bip39Language::Spanish,
+    Bip39Language::ChineseSimplified,
+    Bip39Language::ChineseTraditional,
+    Bip39Language::French,
+    Bip39Language::Italian,
+    Bip39Language::Czech,
+    Bip39Language::Portuguese,
+  ];
+
+  #[test]
+  fn standard_wordlists_round_trip_entropy() {
+    let entropy: Vec<u8> = (0..32).collect();
+    for language in LANGUAGES.iter() {
+      let wordlist = Bip39Wordlist::standard(*language).unwrap();
+      let mnemonic = wordlist.entropy_to_mnemonic(&entropy).unwrap();
+      assert_eq!(wordlist.mnemonic_to_entropy(&mnemonic).unwrap(), entropy);
+    }
+    let english = Bip39Wordlist::english().unwrap();
+    let mnemonic = english.entropy_to_mnemonic(&[0; 16]).unwrap();
+    assert_eq!(mnemonic, format!("{}about", "abandon ".repeat(11)));
+  }
+
+  #[test]
+  fn precomposed_accents_are_accepted() {
+    let wordlist = Bip39Wordlist::standard(Bip39Language::Spanish).unwrap();
+    let mnemonic = wordlist.entropy_to_mnemonic(&[0; 16]).unwrap();
+    let composed: String = mnemonic.nfc().collect();
+    assert_ne!(composed, mnemonic);
+    assert_eq!(wordlist.mnemonic_to_entropy(&composed).unwrap(), vec![0; 16]);
+    assert_eq!(wordlist.validate(&composed.to_uppercase()).status, MnemonicStatus::Valid);
+  }
+
+  #[test]
+  fn japanese_words_are_separated_by_ideographic_space() {
+    let wordlist = Bip39Wordlist::standard(Bip39Language::Japanese).unwrap();
+    let mnemonic = wordlist.entropy_to_mnemonic(&[0; 16]).unwrap();
+    assert_eq!(mnemonic.matches('\u{3000}').count(), 11);
+    assert!(!mnemonic.contains(' '));
+    let spaced = mnemonic.replace('\u{3000}', "  ");
+    assert_eq!(wordlist.normalize(&spaced).unwrap(), mnemonic);
+  }
+
+  #[test]
+  fn custom_wordlist_is_lowercased() {
+    let mut words: Vec<String> = Bip39Language::English
+      .words()
+      .lines()
+      .enumerate()
+      .map(|(index, word)| if index % 2 == 0 { word.to_uppercase() } else { word.to_string() })
+      .collect();
+    let wordlist = Bip39Wordlist::new(words.clone(), " ".to_string()).unwrap();
+    let mnemonic = format!("{}about", "abandon ".repeat(11));
+    assert_eq!(wordlist.entropy_to_mnemonic(&[0; 16]).unwrap(), mnemonic);
+    assert_eq!(wordlist.mnemonic_to_entropy(&mnemonic.to_uppercase()).unwrap(), vec![0; 16]);
+    assert_eq!(wordlist.validate(&mnemonic).status, MnemonicStatus::Valid);
+    // ABANDON and abandon are the same word
+    words[1] = words[0].to_lowercase();
+    assert!(Bip39Wordlist::new(words, " ".to_string()).is_err());
+  }
+}
@@ -9,7 +9,10 @@ use crate::ptr::*;
 use crate::transaction_hash::TransactionHash;
 use crate::vkey::Vkey;
 use cardano_serialization_lib::{
-  crypto::{BootstrapWitness as RBootstrapWitness, BootstrapWitnesses as RBootstrapWitnesses},
+  crypto::{
+    BootstrapWitness as RBootstrapWitness, BootstrapWitnesses as RBootstrapWitnesses,
+    Ed25519KeyHash as REd25519KeyHash, PublicKey as RPublicKey,
+  },
   utils::make_icarus_bootstrap_witness,
 };
 use std::convert::{TryFrom, TryInto};
@@ -70,6 +73,16 @@ impl From<RBootstrapWitness> for BootstrapWitness {
   }
 }
 
+impl BootstrapWitness {
+  pub fn key_hash(&self) -> Result<REd25519KeyHash> {
+    self.vkey.0.try_into().map(|public_key: RPublicKey| public_key.hash())
+  }
+
+  pub fn attributes(&self) -> Result<Vec<u8>> {
+    unsafe { self.attributes.unowned() }.map(|attributes| attributes.to_vec())
+  }
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn cardano_bootstrap_witness_make_icarus_bootstrap_witness(
   tx_body_hash: TransactionHash, addr: ByronAddress, key: Bip32PrivateKey,
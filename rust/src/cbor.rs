@@ -0,0 +1,280 @@
+use crate::panic::*;
+use cbor_event::{de::Deserializer, se::Serializer, Len, Special, Type};
+use std::io::Cursor;
+
+pub type CborSerializer = Serializer<Vec<u8>>;
+pub type CborDeserializer<'a> = Deserializer<Cursor<&'a [u8]>>;
+
+// CBOR encoding for the structures cardano-serialization-lib doesn't know about
+pub trait CborSerialize {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()>;
+
+  fn to_cbor(&self) -> Result<Vec<u8>> {
+    let mut se = Serializer::new_vec();
+    self.serialize(&mut se)?;
+    Ok(se.finalize())
+  }
+}
+
+pub trait CborDeserialize: Sized {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self>;
+
+  fn from_cbor(bytes: &[u8]) -> Result<Self> {
+    let mut de = Deserializer::from(Cursor::new(bytes));
+    let value = Self::deserialize(&mut de)?;
+    if de.as_ref().position() as usize == bytes.len() {
+      Ok(value)
+    } else {
+      Err(cbor_event::Error::TrailingData.into())
+    }
+  }
+}
+
+impl CborSerialize for u64 {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    se.write_unsigned_integer(*self)?;
+    Ok(())
+  }
+}
+
+impl CborDeserialize for u64 {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    Ok(de.unsigned_integer()?)
+  }
+}
+
+impl CborSerialize for Vec<u8> {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    se.write_bytes(self)?;
+    Ok(())
+  }
+}
+
+impl CborDeserialize for Vec<u8> {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    Ok(de.bytes()?)
+  }
+}
+
+// Raw bytes of the next data item
+pub fn raw_item(de: &mut CborDeserializer) -> Result<Vec<u8>> {
+  let start = de.as_ref().position() as usize;
+  skip_item(de)?;
+  let end = de.as_ref().position() as usize;
+  Ok(de.as_ref().get_ref()[start..end].to_vec())
+}
+
+// cbor_event::Value can't hold maps with array keys, so items are walked by hand
+fn skip_item(de: &mut CborDeserializer) -> Result<()> {
+  match de.cbor_type()? {
+    Type::UnsignedInteger => de.unsigned_integer().map(|_| ())?,
+    Type::NegativeInteger => de.negative_integer().map(|_| ())?,
+    Type::Bytes => de.bytes().map(|_| ())?,
+    Type::Text => de.text().map(|_| ())?,
+    Type::Array => {
+      let len = de.array()?;
+      let mut read = 0;
+      while has_next(de, len, read)? {
+        skip_item(de)?;
+        read += 1;
+      }
+    }
+    Type::Map => {
+      let len = de.map()?;
+      let mut read = 0;
+      while has_next(de, len, read)? {
+        skip_item(de)?;
+        skip_item(de)?;
+        read += 1;
+      }
+    }
+    Type::Tag => {
+      de.tag()?;
+      skip_item(de)?;
+    }
+    Type::Special => de.special().map(|_| ())?,
+  }
+  Ok(())
+}
+
+// Skips the optional set tag Conway allows in front of sets
+pub fn skip_set_tag(de: &mut CborDeserializer) -> Result<()> {
+  if de.cbor_type()? == Type::Tag {
+    de.set_tag()?;
+  }
+  Ok(())
+}
+
+// Calls read for every element of the array or set at the current position
+pub fn read_array<T, F>(de: &mut CborDeserializer, mut read: F) -> Result<Vec<T>>
+where
+  F: FnMut(&mut CborDeserializer) -> Result<T>,
+{
+  skip_set_tag(de)?;
+  let len = de.array()?;
+  let mut items = Vec::new();
+  while has_next(de, len, items.len())? {
+    items.push(read(de)?);
+  }
+  Ok(items)
+}
+
+// Calls read for every key and value of the map at the current position
+pub fn read_map<K, V, F>(de: &mut CborDeserializer, mut read: F) -> Result<Vec<(K, V)>>
+where
+  F: FnMut(&mut CborDeserializer) -> Result<(K, V)>,
+{
+  let len = de.map()?;
+  let mut entries = Vec::new();
+  while has_next(de, len, entries.len())? {
+    entries.push(read(de)?);
+  }
+  Ok(entries)
+}
+
+fn has_next(de: &mut CborDeserializer, len: Len, read: usize) -> Result<bool> {
+  match len {
+    Len::Len(len) => Ok((read as u64) < len),
+    Len::Indefinite => {
+      if de.cbor_type()? == Type::Special {
+        match de.special()? {
+          Special::Break => Ok(false),
+          _ => Err("Unexpected special value in indefinite collection".into()),
+        }
+      } else {
+        Ok(true)
+      }
+    }
+  }
+}
+
+// Opens an array of the form [kind, fields...] and checks its kind and size
+pub fn read_kind_array(
+  de: &mut CborDeserializer, kind: u64, fields: u64, name: &str,
+) -> Result<Len> {
+  let len = de.array()?;
+  if let Len::Len(len) = len {
+    if len != fields + 1 {
+      return Err(format!("Wrong {} array length {}", name, len).into());
+    }
+  }
+  let actual = de.unsigned_integer()?;
+  if actual != kind {
+    return Err(format!("Wrong {} kind {}", name, actual).into());
+  }
+  Ok(len)
+}
+
+// Closes an array opened with a known number of fields
+pub fn read_array_end(de: &mut CborDeserializer, len: Len) -> Result<()> {
+  if len == Len::Indefinite && de.special()? != Special::Break {
+    return Err("Indefinite array has more items than expected".into());
+  }
+  Ok(())
+}
+
+// Reads the [kind, ...] tag of an array without consuming anything
+pub fn peek_kind(de: &CborDeserializer) -> Result<u64> {
+  let bytes = &de.as_ref().get_ref()[de.as_ref().position() as usize..];
+  let mut peek = Deserializer::from(Cursor::new(bytes));
+  peek.array()?;
+  Ok(peek.unsigned_integer()?)
+}
+
+pub fn read_nullable<T, F>(de: &mut CborDeserializer, read: F) -> Result<Option<T>>
+where
+  F: FnOnce(&mut CborDeserializer) -> Result<T>,
+{
+  if de.cbor_type()? == Type::Special {
+    match de.special()? {
+      Special::Null => Ok(None),
+      _ => Err("Expected null".into()),
+    }
+  } else {
+    read(de).map(Some)
+  }
+}
+
+pub fn write_nullable<T, F>(se: &mut CborSerializer, value: Option<T>, write: F) -> Result<()>
+where
+  F: FnOnce(&mut CborSerializer, T) -> Result<()>,
+{
+  match value {
+    Some(value) => write(se, value),
+    None => se.write_special(Special::Null).map(|_| ()).into_result(),
+  }
+}
+
+// Entries of a map with unsigned keys, as the transaction body and witness set are
+pub fn map_entries(bytes: &[u8]) -> Result<Vec<(u64, Vec<u8>)>> {
+  let mut de = Deserializer::from(Cursor::new(bytes));
+  read_map(&mut de, |de| Ok((de.unsigned_integer()?, raw_item(de)?)))
+}
+
+// Raw items of an array or set
+pub fn array_items(bytes: &[u8]) -> Result<Vec<Vec<u8>>> {
+  let mut de = Deserializer::from(Cursor::new(bytes));
+  read_array(&mut de, raw_item)
+}
+
+// Definite array of raw items
+pub fn encode_array(items: Vec<Vec<u8>>) -> Result<Vec<u8>> {
+  let mut se = Serializer::new_vec();
+  se.write_array(Len::Len(items.len() as u64))?;
+  for item in items {
+    se.write_raw_bytes(&item)?;
+  }
+  Ok(se.finalize())
+}
+
+// Definite map of raw entries sorted by key
+pub fn encode_map(mut entries: Vec<(u64, Vec<u8>)>) -> Result<Vec<u8>> {
+  entries.sort_by_key(|(key, _)| *key);
+  let mut se = Serializer::new_vec();
+  se.write_map(Len::Len(entries.len() as u64))?;
+  for (key, value) in entries {
+    se.write_unsigned_integer(key)?;
+    se.write_raw_bytes(&value)?;
+  }
+  Ok(se.finalize())
+}
+
+// Removes the entry with the key and returns its raw value
+pub fn take_entry(entries: &mut Vec<(u64, Vec<u8>)>, key: u64) -> Option<Vec<u8>> {
+  entries
+    .iter()
+    .position(|(k, _)| *k == key)
+    .map(|index| entries.remove(index).1)
+}
+
+// Raw value with the set tag removed, so cardano-serialization-lib accepts it
+pub fn strip_set_tag(bytes: Vec<u8>) -> Vec<u8> {
+  // 0xd9 0x0102 is tag 258 in its only valid encoding
+  if bytes.starts_with(&[0xd9, 0x01, 0x02]) {
+    bytes[3..].to_vec()
+  } else {
+    bytes
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_utils::hex;
+
+  #[test]
+  fn map_entries_are_sorted_by_key() {
+    let entries = vec![(2, vec![0x02]), (0, vec![0x80])];
+    assert_eq!(encode_map(entries).unwrap(), hex("a200800202"));
+    assert_eq!(map_entries(&hex("a200800202")).unwrap(), vec![(0, vec![0x80]), (2, vec![0x02])]);
+  }
+
+  #[test]
+  fn arrays_keep_raw_items() {
+    let items = vec![hex("d90102820102"), hex("f6")];
+    let bytes = encode_array(items.clone()).unwrap();
+    assert_eq!(bytes, hex("82d90102820102f6"));
+    assert_eq!(array_items(&bytes).unwrap(), items);
+    assert_eq!(strip_set_tag(items[0].clone()), hex("820102"));
+  }
+}
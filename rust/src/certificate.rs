@@ -1,17 +1,36 @@
 use crate::array::CArray;
+use crate::cbor::*;
+use crate::committee_cold_resign::CommitteeColdResign;
+use crate::committee_hot_auth::CommitteeHotAuth;
+use crate::data::CData;
+use crate::drep_deregistration::DRepDeregistration;
+use crate::drep_registration::DRepRegistration;
+use crate::drep_update::DRepUpdate;
 use crate::error::CError;
 use crate::genesis_key_delegation::GenesisKeyDelegation;
+use crate::linear_fee::Coin;
 use crate::move_instantaneous_rewards_cert::MoveInstantaneousRewardsCert;
 use crate::panic::*;
 use crate::pool_registration::PoolRegistration;
 use crate::pool_retirement::PoolRetirement;
 use crate::ptr::*;
+use crate::stake_and_vote_delegation::StakeAndVoteDelegation;
+use crate::stake_credential::StakeCredential;
 use crate::stake_delegation::StakeDelegation;
 use crate::stake_deregistration::StakeDeregistration;
+use crate::stake_deregistration_with_deposit::StakeDeregistrationWithDeposit;
 use crate::stake_registration::StakeRegistration;
+use crate::stake_registration_and_delegation::StakeRegistrationAndDelegation;
+use crate::stake_registration_with_deposit::StakeRegistrationWithDeposit;
+use crate::stake_vote_registration_and_delegation::StakeVoteRegistrationAndDelegation;
+use crate::vote_delegation::VoteDelegation;
+use crate::vote_registration_and_delegation::VoteRegistrationAndDelegation;
 use cardano_serialization_lib::{
-  Certificate as RCertificate, CertificateKind, Certificates as RCertificates,
+  crypto::Ed25519KeyHash as REd25519KeyHash, Certificate as RCertificate, CertificateKind,
+  Certificates as RCertificates,
 };
+use cbor_event::Len;
+use std::collections::BTreeSet;
 use std::convert::{TryFrom, TryInto};
 
 #[repr(C)]
@@ -24,6 +43,18 @@ pub enum Certificate {
   PoolRetirementKind(PoolRetirement),
   GenesisKeyDelegationKind(GenesisKeyDelegation),
   MoveInstantaneousRewardsCertKind(MoveInstantaneousRewardsCert),
+  StakeRegistrationWithDepositKind(StakeRegistrationWithDeposit),
+  StakeDeregistrationWithDepositKind(StakeDeregistrationWithDeposit),
+  VoteDelegationKind(VoteDelegation),
+  StakeAndVoteDelegationKind(StakeAndVoteDelegation),
+  StakeRegistrationAndDelegationKind(StakeRegistrationAndDelegation),
+  VoteRegistrationAndDelegationKind(VoteRegistrationAndDelegation),
+  StakeVoteRegistrationAndDelegationKind(StakeVoteRegistrationAndDelegation),
+  CommitteeHotAuthKind(CommitteeHotAuth),
+  CommitteeColdResignKind(CommitteeColdResign),
+  DRepRegistrationKind(DRepRegistration),
+  DRepDeregistrationKind(DRepDeregistration),
+  DRepUpdateKind(DRepUpdate),
 }
 
 impl Free for Certificate {
@@ -31,11 +62,56 @@ impl Free for Certificate {
     match self {
       Certificate::PoolRegistrationKind(pool_registration) => pool_registration.free(),
       Certificate::MoveInstantaneousRewardsCertKind(mirs_cert) => mirs_cert.free(),
+      Certificate::CommitteeColdResignKind(committee_cold_resign) => committee_cold_resign.free(),
+      Certificate::DRepRegistrationKind(drep_registration) => drep_registration.free(),
+      Certificate::DRepUpdateKind(drep_update) => drep_update.free(),
       _ => return,
     }
   }
 }
 
+impl Certificate {
+  // Credential witnessing a Conway certificate, None for the legacy ones
+  pub fn conway_credential(&self) -> Option<StakeCredential> {
+    match self {
+      Certificate::StakeRegistrationWithDepositKind(cert) => Some(cert.stake_credential()),
+      Certificate::StakeDeregistrationWithDepositKind(cert) => Some(cert.stake_credential()),
+      Certificate::VoteDelegationKind(cert) => Some(cert.stake_credential()),
+      Certificate::StakeAndVoteDelegationKind(cert) => Some(cert.stake_credential()),
+      Certificate::StakeRegistrationAndDelegationKind(cert) => Some(cert.stake_credential()),
+      Certificate::VoteRegistrationAndDelegationKind(cert) => Some(cert.stake_credential()),
+      Certificate::StakeVoteRegistrationAndDelegationKind(cert) => Some(cert.stake_credential()),
+      Certificate::CommitteeHotAuthKind(cert) => Some(cert.committee_cold_credential()),
+      Certificate::CommitteeColdResignKind(cert) => Some(cert.committee_cold_credential()),
+      Certificate::DRepRegistrationKind(cert) => Some(cert.voting_credential()),
+      Certificate::DRepDeregistrationKind(cert) => Some(cert.voting_credential()),
+      Certificate::DRepUpdateKind(cert) => Some(cert.voting_credential()),
+      _ => None,
+    }
+  }
+
+  // Deposit taken by a Conway certificate
+  pub fn conway_deposit(&self) -> Coin {
+    match self {
+      Certificate::StakeRegistrationWithDepositKind(cert) => cert.deposit(),
+      Certificate::StakeRegistrationAndDelegationKind(cert) => cert.deposit(),
+      Certificate::VoteRegistrationAndDelegationKind(cert) => cert.deposit(),
+      Certificate::StakeVoteRegistrationAndDelegationKind(cert) => cert.deposit(),
+      Certificate::DRepRegistrationKind(cert) => cert.deposit(),
+      _ => 0,
+    }
+  }
+
+  // Deposit returned by a Conway certificate
+  pub fn conway_refund(&self) -> Coin {
+    match self {
+      Certificate::StakeDeregistrationWithDepositKind(cert) => cert.deposit(),
+      Certificate::DRepDeregistrationKind(cert) => cert.deposit(),
+      _ => 0,
+    }
+  }
+}
+
 impl TryFrom<Certificate> for RCertificate {
   type Error = CError;
 
@@ -62,6 +138,7 @@ impl TryFrom<Certificate> for RCertificate {
       Certificate::MoveInstantaneousRewardsCertKind(mirs_cert) => mirs_cert
         .try_into()
         .map(|mirs_cert| Self::new_move_instantaneous_rewards_cert(&mirs_cert)),
+      _ => Err("Conway certificates can't be converted to cardano-serialization-lib".into()),
     }
   }
 }
@@ -110,6 +187,97 @@ impl TryFrom<RCertificate> for Certificate {
   }
 }
 
+impl CborSerialize for Certificate {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    match self {
+      Certificate::StakeRegistrationWithDepositKind(cert) => cert.serialize(se),
+      Certificate::StakeDeregistrationWithDepositKind(cert) => cert.serialize(se),
+      Certificate::VoteDelegationKind(cert) => cert.serialize(se),
+      Certificate::StakeAndVoteDelegationKind(cert) => cert.serialize(se),
+      Certificate::StakeRegistrationAndDelegationKind(cert) => cert.serialize(se),
+      Certificate::VoteRegistrationAndDelegationKind(cert) => cert.serialize(se),
+      Certificate::StakeVoteRegistrationAndDelegationKind(cert) => cert.serialize(se),
+      Certificate::CommitteeHotAuthKind(cert) => cert.serialize(se),
+      Certificate::CommitteeColdResignKind(cert) => cert.serialize(se),
+      Certificate::DRepRegistrationKind(cert) => cert.serialize(se),
+      Certificate::DRepDeregistrationKind(cert) => cert.serialize(se),
+      Certificate::DRepUpdateKind(cert) => cert.serialize(se),
+      _ => {
+        let certificate: RCertificate = (*self).try_into()?;
+        se.write_raw_bytes(&certificate.to_bytes())?;
+        Ok(())
+      }
+    }
+  }
+}
+
+impl CborDeserialize for Certificate {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    match peek_kind(de)? {
+      7 => StakeRegistrationWithDeposit::deserialize(de)
+        .map(Self::StakeRegistrationWithDepositKind),
+      8 => StakeDeregistrationWithDeposit::deserialize(de)
+        .map(Self::StakeDeregistrationWithDepositKind),
+      9 => VoteDelegation::deserialize(de).map(Self::VoteDelegationKind),
+      10 => StakeAndVoteDelegation::deserialize(de)
+        .map(Self::StakeAndVoteDelegationKind),
+      11 => StakeRegistrationAndDelegation::deserialize(de)
+        .map(Self::StakeRegistrationAndDelegationKind),
+      12 => VoteRegistrationAndDelegation::deserialize(de)
+        .map(Self::VoteRegistrationAndDelegationKind),
+      13 => StakeVoteRegistrationAndDelegation::deserialize(de)
+        .map(Self::StakeVoteRegistrationAndDelegationKind),
+      14 => CommitteeHotAuth::deserialize(de).map(Self::CommitteeHotAuthKind),
+      15 => CommitteeColdResign::deserialize(de).map(Self::CommitteeColdResignKind),
+      16 => DRepRegistration::deserialize(de).map(Self::DRepRegistrationKind),
+      17 => DRepDeregistration::deserialize(de).map(Self::DRepDeregistrationKind),
+      18 => DRepUpdate::deserialize(de).map(Self::DRepUpdateKind),
+      _ => raw_item(de)
+        .and_then(|bytes| RCertificate::from_bytes(bytes).into_result())
+        .and_then(|certificate| certificate.try_into()),
+    }
+  }
+}
+
+// Same keys RTransactionBuilder::set_certs adds to its input types
+pub fn legacy_cert_keys(certs: &RCertificates, keys: &mut BTreeSet<REd25519KeyHash>) {
+  for index in 0..certs.len() {
+    let cert = certs.get(index);
+    if let Some(cert) = cert.as_stake_deregistration() {
+      keys.extend(cert.stake_credential().to_keyhash());
+    } else if let Some(cert) = cert.as_stake_delegation() {
+      keys.extend(cert.stake_credential().to_keyhash());
+    } else if let Some(cert) = cert.as_pool_registration() {
+      let owners = cert.pool_params().pool_owners();
+      keys.extend((0..owners.len()).map(|owner| owners.get(owner)));
+      keys.insert(cert.pool_params().operator());
+    } else if let Some(cert) = cert.as_pool_retirement() {
+      keys.insert(cert.pool_keyhash());
+    } else if let Some(cert) = cert.as_genesis_key_delegation() {
+      let hash = cert.genesis_delegate_hash().to_bytes();
+      keys.extend(REd25519KeyHash::from_bytes(hash).ok());
+    }
+  }
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_certificate_to_bytes(
+  certificate: Certificate, result: &mut CData, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| certificate.to_cbor().map(|bytes| bytes.into()))
+    .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_certificate_from_bytes(
+  data: CData, result: &mut Certificate, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| data.unowned().and_then(Certificate::from_cbor))
+    .response(result, error)
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn cardano_certificate_clone(
   certificate: Certificate, result: &mut Certificate, error: &mut CError,
@@ -150,7 +318,63 @@ impl TryFrom<Certificates> for RCertificates {
   }
 }
 
+impl CborSerialize for Certificates {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    let certificates = unsafe { self.unowned()? };
+    se.write_array(Len::Len(certificates.len() as u64))?;
+    for certificate in certificates {
+      certificate.serialize(se)?;
+    }
+    Ok(())
+  }
+}
+
+impl CborDeserialize for Certificates {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    read_array(de, Certificate::deserialize).map(|certificates| certificates.into())
+  }
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn cardano_certificates_free(certificates: &mut Certificates) {
   certificates.free();
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_utils::hex;
+
+  fn credential() -> String {
+    format!("8200581c{}", "11".repeat(28))
+  }
+
+  #[test]
+  fn drep_registration_round_trips() {
+    let anchor = format!("827368747470733a2f2f6578616d706c652e636f6d5820{}", "09".repeat(32));
+    for anchor in ["f6".to_string(), anchor] {
+      let bytes = hex(&format!("8410{}1a001e8480{}", credential(), anchor));
+      let certificate = Certificate::from_cbor(&bytes).unwrap();
+      assert!(matches!(certificate, Certificate::DRepRegistrationKind(_)));
+      assert_eq!(certificate.conway_deposit(), 2_000_000);
+      assert_eq!(certificate.to_cbor().unwrap(), bytes);
+    }
+  }
+
+  #[test]
+  fn legacy_certificate_round_trips() {
+    let bytes = hex(&format!("8200{}", credential()));
+    let certificate = Certificate::from_cbor(&bytes).unwrap();
+    assert!(matches!(certificate, Certificate::StakeRegistrationKind(_)));
+    assert!(certificate.conway_credential().is_none());
+    assert_eq!(certificate.to_cbor().unwrap(), bytes);
+  }
+
+  #[test]
+  fn vote_delegation_refund_is_zero() {
+    let bytes = hex(&format!("8309{}8102", credential()));
+    let certificate = Certificate::from_cbor(&bytes).unwrap();
+    assert_eq!(certificate.conway_refund(), 0);
+    assert_eq!(certificate.to_cbor().unwrap(), bytes);
+  }
+}
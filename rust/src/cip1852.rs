@@ -0,0 +1,171 @@
+use crate::address::address::Address;
+use crate::bip32_private_key::{derive_path, Bip32PrivateKey, DerivationPath, HARDENED};
+use crate::error::CError;
+use crate::panic::*;
+use crate::ptr::*;
+use cardano_serialization_lib::{
+  address::{BaseAddress as RBaseAddress, StakeCredential as RStakeCredential},
+  crypto::Bip32PrivateKey as RBip32PrivateKey,
+};
+use std::convert::TryInto;
+
+const PURPOSE: u32 = 1852;
+const COIN_TYPE: u32 = 1815;
+
+// Role of the keys derived under a CIP-1852 account (CIP-1852, CIP-105)
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub enum KeyRole {
+  ExternalRole,
+  InternalRole,
+  StakeRole,
+  DRepRole,
+  CommitteeColdRole,
+  CommitteeHotRole,
+}
+
+impl From<KeyRole> for u32 {
+  fn from(role: KeyRole) -> Self {
+    match role {
+      KeyRole::ExternalRole => 0,
+      KeyRole::InternalRole => 1,
+      KeyRole::StakeRole => 2,
+      KeyRole::DRepRole => 3,
+      KeyRole::CommitteeColdRole => 4,
+      KeyRole::CommitteeHotRole => 5,
+    }
+  }
+}
+
+fn check_index(index: u32) -> Result<u32> {
+  if index < HARDENED {
+    Ok(index)
+  } else {
+    Err("Index must be below 2^31".into())
+  }
+}
+
+// m/1852'/1815'/account'
+pub fn account_path(account: u32) -> Result<Vec<u32>> {
+  check_index(account)
+    .map(|account| vec![PURPOSE | HARDENED, COIN_TYPE | HARDENED, account | HARDENED])
+}
+
+// m/1852'/1815'/account'/role/index
+pub fn key_path(account: u32, role: KeyRole, index: u32) -> Result<Vec<u32>> {
+  let mut path = account_path(account)?;
+  path.push(role.into());
+  path.push(check_index(index)?);
+  Ok(path)
+}
+
+// Payment and stake keys of the account with the base address they make
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct AddressKeys {
+  payment_key: Bip32PrivateKey,
+  stake_key: Bip32PrivateKey,
+  address: Address,
+}
+
+impl Free for AddressKeys {
+  unsafe fn free(&mut self) {
+    self.payment_key.wipe();
+    self.stake_key.wipe();
+    self.address.free();
+  }
+}
+
+impl AddressKeys {
+  pub fn derive(root_key: Bip32PrivateKey, network: u8, account: u32, index: u32) -> Result<Self> {
+    let payment_key = derive_path(root_key, &key_path(account, KeyRole::ExternalRole, index)?)?;
+    let stake_key = derive_path(root_key, &key_path(account, KeyRole::StakeRole, 0)?)?;
+    let credential = |key: &RBip32PrivateKey| {
+      RStakeCredential::from_keyhash(&key.to_public().to_raw_key().hash())
+    };
+    let address = RBaseAddress::new(network, &credential(&payment_key), &credential(&stake_key))
+      .to_address()
+      .try_into()?;
+    Ok(Self { payment_key: payment_key.into(), stake_key: stake_key.into(), address })
+  }
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_cip1852_account_path(
+  account: u32, result: &mut DerivationPath, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| account_path(account).map(|path| path.into())).response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_cip1852_key_path(
+  account: u32, role: KeyRole, index: u32, result: &mut DerivationPath, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| key_path(account, role, index).map(|path| path.into()))
+    .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_cip1852_account_key(
+  root_key: Bip32PrivateKey, account: u32, result: &mut Bip32PrivateKey, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    account_path(account)
+      .and_then(|path| derive_path(root_key, &path))
+      .map(|key| key.into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_cip1852_role_key(
+  account_key: Bip32PrivateKey, role: KeyRole, index: u32, result: &mut Bip32PrivateKey,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    check_index(index)
+      .and_then(|index| derive_path(account_key, &[role.into(), index]))
+      .map(|key| key.into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_cip1852_address_keys(
+  root_key: Bip32PrivateKey, network: u8, account: u32, index: u32, result: &mut AddressKeys,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| AddressKeys::derive(root_key, network, account, index))
+    .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_cip1852_address_keys_free(keys: &mut AddressKeys) {
+  keys.free();
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::bip32_private_key::{format_derivation_path, parse_derivation_path};
+
+  #[test]
+  fn key_path_uses_role_index() {
+    let path = key_path(0, KeyRole::DRepRole, 7).unwrap();
+    assert_eq!(path, parse_derivation_path("m/1852'/1815'/0'/3/7").unwrap());
+    let path = key_path(2, KeyRole::CommitteeHotRole, 0).unwrap();
+    assert_eq!(format_derivation_path(&path), "m/1852'/1815'/2'/5/0");
+  }
+
+  #[test]
+  fn hardened_indices_are_rejected() {
+    assert!(account_path(HARDENED).is_err());
+    assert!(key_path(0, KeyRole::ExternalRole, HARDENED).is_err());
+  }
+}
@@ -0,0 +1,48 @@
+use crate::anchor::Anchor;
+use crate::cbor::*;
+use crate::option::COption;
+use crate::panic::*;
+use crate::ptr::*;
+use crate::stake_credential::StakeCredential;
+use cbor_event::Len;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct CommitteeColdResign {
+  committee_cold_credential: StakeCredential,
+  anchor: COption<Anchor>,
+}
+
+impl CommitteeColdResign {
+  pub fn committee_cold_credential(&self) -> StakeCredential {
+    self.committee_cold_credential
+  }
+}
+
+impl Free for CommitteeColdResign {
+  unsafe fn free(&mut self) {
+    self.anchor.free()
+  }
+}
+
+impl CborSerialize for CommitteeColdResign {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    se.write_array(Len::Len(3))?;
+    se.write_unsigned_integer(15)?;
+    self.committee_cold_credential.serialize(se)?;
+    write_nullable(se, self.anchor.into(), |se, anchor: Anchor| anchor.serialize(se))
+  }
+}
+
+impl CborDeserialize for CommitteeColdResign {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    let len = read_kind_array(de, 15, 2, "CommitteeColdResign")?;
+    let committee_cold_credential = StakeCredential::deserialize(de)?;
+    let anchor = read_nullable(de, Anchor::deserialize)?.into();
+    read_array_end(de, len)?;
+    Ok(Self {
+      committee_cold_credential,
+      anchor,
+    })
+  }
+}
@@ -0,0 +1,39 @@
+use crate::cbor::*;
+use crate::panic::*;
+use crate::stake_credential::StakeCredential;
+use cbor_event::Len;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct CommitteeHotAuth {
+  committee_cold_credential: StakeCredential,
+  committee_hot_credential: StakeCredential,
+}
+
+impl CommitteeHotAuth {
+  pub fn committee_cold_credential(&self) -> StakeCredential {
+    self.committee_cold_credential
+  }
+}
+
+impl CborSerialize for CommitteeHotAuth {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    se.write_array(Len::Len(3))?;
+    se.write_unsigned_integer(14)?;
+    self.committee_cold_credential.serialize(se)?;
+    self.committee_hot_credential.serialize(se)
+  }
+}
+
+impl CborDeserialize for CommitteeHotAuth {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    let len = read_kind_array(de, 14, 2, "CommitteeHotAuth")?;
+    let committee_cold_credential = StakeCredential::deserialize(de)?;
+    let committee_hot_credential = StakeCredential::deserialize(de)?;
+    read_array_end(de, len)?;
+    Ok(Self {
+      committee_cold_credential,
+      committee_hot_credential,
+    })
+  }
+}
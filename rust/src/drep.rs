@@ -0,0 +1,101 @@
+use crate::cbor::*;
+use crate::data::CData;
+use crate::error::CError;
+use crate::panic::*;
+use crate::ptr::*;
+use crate::stake_credential::{Ed25519KeyHash, ScriptHash};
+use cbor_event::Len;
+
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum DRep {
+  KeyHashKind(Ed25519KeyHash),
+  ScriptHashKind(ScriptHash),
+  AlwaysAbstainKind,
+  AlwaysNoConfidenceKind,
+}
+
+impl CborSerialize for DRep {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    match self {
+      DRep::KeyHashKind(hash) => {
+        se.write_array(Len::Len(2))?.write_unsigned_integer(0)?;
+        hash.serialize(se)
+      }
+      DRep::ScriptHashKind(hash) => {
+        se.write_array(Len::Len(2))?.write_unsigned_integer(1)?;
+        hash.serialize(se)
+      }
+      DRep::AlwaysAbstainKind => {
+        se.write_array(Len::Len(1))?.write_unsigned_integer(2)?;
+        Ok(())
+      }
+      DRep::AlwaysNoConfidenceKind => {
+        se.write_array(Len::Len(1))?.write_unsigned_integer(3)?;
+        Ok(())
+      }
+    }
+  }
+}
+
+impl CborDeserialize for DRep {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    let (len, drep) = match peek_kind(de)? {
+      0 => read_kind_array(de, 0, 1, "DRep")
+        .zip(Ed25519KeyHash::deserialize(de))
+        .map(|(len, hash)| (len, Self::KeyHashKind(hash))),
+      1 => read_kind_array(de, 1, 1, "DRep")
+        .zip(ScriptHash::deserialize(de))
+        .map(|(len, hash)| (len, Self::ScriptHashKind(hash))),
+      2 => read_kind_array(de, 2, 0, "DRep").map(|len| (len, Self::AlwaysAbstainKind)),
+      3 => read_kind_array(de, 3, 0, "DRep").map(|len| (len, Self::AlwaysNoConfidenceKind)),
+      kind => Err(format!("Wrong DRep kind {}", kind).into()),
+    }?;
+    read_array_end(de, len)?;
+    Ok(drep)
+  }
+}
+
+impl Free for DRep {
+  unsafe fn free(&mut self) {}
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_drep_to_bytes(
+  drep: DRep, result: &mut CData, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| drep.to_cbor().map(|bytes| bytes.into())).response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_drep_from_bytes(
+  data: CData, result: &mut DRep, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| data.unowned().and_then(DRep::from_cbor))
+    .response(result, error)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_utils::hex;
+
+  #[test]
+  fn drep_kinds_round_trip() {
+    let key_hash = format!("8200581c{}", "11".repeat(28));
+    let script_hash = format!("8201581c{}", "22".repeat(28));
+    for bytes in [key_hash.as_str(), script_hash.as_str(), "8102", "8103"].map(hex) {
+      let drep = DRep::from_cbor(&bytes).unwrap();
+      assert_eq!(drep.to_cbor().unwrap(), bytes);
+    }
+    assert!(DRep::from_cbor(&hex("8102")).unwrap() == DRep::AlwaysAbstainKind);
+  }
+
+  #[test]
+  fn unknown_drep_kind_is_rejected() {
+    assert!(DRep::from_cbor(&hex("8104")).is_err());
+    assert!(DRep::from_cbor(&hex("820200")).is_err());
+  }
+}
@@ -0,0 +1,45 @@
+use crate::cbor::*;
+use crate::linear_fee::Coin;
+use crate::panic::*;
+use crate::stake_credential::StakeCredential;
+use cbor_event::Len;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct DRepDeregistration {
+  voting_credential: StakeCredential,
+  deposit: Coin,
+}
+
+impl DRepDeregistration {
+  pub fn voting_credential(&self) -> StakeCredential {
+    self.voting_credential
+  }
+
+  pub fn deposit(&self) -> Coin {
+    self.deposit
+  }
+}
+
+impl CborSerialize for DRepDeregistration {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    se.write_array(Len::Len(3))?;
+    se.write_unsigned_integer(17)?;
+    self.voting_credential.serialize(se)?;
+    se.write_unsigned_integer(self.deposit)?;
+    Ok(())
+  }
+}
+
+impl CborDeserialize for DRepDeregistration {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    let len = read_kind_array(de, 17, 2, "DRepDeregistration")?;
+    let voting_credential = StakeCredential::deserialize(de)?;
+    let deposit = de.unsigned_integer()?;
+    read_array_end(de, len)?;
+    Ok(Self {
+      voting_credential,
+      deposit,
+    })
+  }
+}
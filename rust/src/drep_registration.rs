@@ -0,0 +1,57 @@
+use crate::anchor::Anchor;
+use crate::cbor::*;
+use crate::linear_fee::Coin;
+use crate::option::COption;
+use crate::panic::*;
+use crate::ptr::*;
+use crate::stake_credential::StakeCredential;
+use cbor_event::Len;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct DRepRegistration {
+  voting_credential: StakeCredential,
+  deposit: Coin,
+  anchor: COption<Anchor>,
+}
+
+impl DRepRegistration {
+  pub fn voting_credential(&self) -> StakeCredential {
+    self.voting_credential
+  }
+
+  pub fn deposit(&self) -> Coin {
+    self.deposit
+  }
+}
+
+impl Free for DRepRegistration {
+  unsafe fn free(&mut self) {
+    self.anchor.free()
+  }
+}
+
+impl CborSerialize for DRepRegistration {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    se.write_array(Len::Len(4))?;
+    se.write_unsigned_integer(16)?;
+    self.voting_credential.serialize(se)?;
+    se.write_unsigned_integer(self.deposit)?;
+    write_nullable(se, self.anchor.into(), |se, anchor: Anchor| anchor.serialize(se))
+  }
+}
+
+impl CborDeserialize for DRepRegistration {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    let len = read_kind_array(de, 16, 3, "DRepRegistration")?;
+    let voting_credential = StakeCredential::deserialize(de)?;
+    let deposit = de.unsigned_integer()?;
+    let anchor = read_nullable(de, Anchor::deserialize)?.into();
+    read_array_end(de, len)?;
+    Ok(Self {
+      voting_credential,
+      deposit,
+      anchor,
+    })
+  }
+}
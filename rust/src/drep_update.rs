@@ -0,0 +1,48 @@
+use crate::anchor::Anchor;
+use crate::cbor::*;
+use crate::option::COption;
+use crate::panic::*;
+use crate::ptr::*;
+use crate::stake_credential::StakeCredential;
+use cbor_event::Len;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct DRepUpdate {
+  voting_credential: StakeCredential,
+  anchor: COption<Anchor>,
+}
+
+impl DRepUpdate {
+  pub fn voting_credential(&self) -> StakeCredential {
+    self.voting_credential
+  }
+}
+
+impl Free for DRepUpdate {
+  unsafe fn free(&mut self) {
+    self.anchor.free()
+  }
+}
+
+impl CborSerialize for DRepUpdate {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    se.write_array(Len::Len(3))?;
+    se.write_unsigned_integer(18)?;
+    self.voting_credential.serialize(se)?;
+    write_nullable(se, self.anchor.into(), |se, anchor: Anchor| anchor.serialize(se))
+  }
+}
+
+impl CborDeserialize for DRepUpdate {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    let len = read_kind_array(de, 18, 2, "DRepUpdate")?;
+    let voting_credential = StakeCredential::deserialize(de)?;
+    let anchor = read_nullable(de, Anchor::deserialize)?.into();
+    read_array_end(de, len)?;
+    Ok(Self {
+      voting_credential,
+      anchor,
+    })
+  }
+}
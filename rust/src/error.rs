@@ -55,6 +55,12 @@ impl From<&str> for CError {
     }
 }
 
+impl From<cbor_event::Error> for CError {
+    fn from(error: cbor_event::Error) -> Self {
+        Self::DeserializeError(format!("{}", error).into_cstr())
+    }
+}
+
 impl From<serde_json::Error> for CError {
     fn from(error: serde_json::Error) -> Self {
         Self::Error(format!("{}", error).into_cstr())
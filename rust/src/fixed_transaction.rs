@@ -0,0 +1,446 @@
+use crate::bip32_private_key::{Bip32PrivateKey, DerivationPath, DerivationPaths};
+use crate::bootstrap_witness::BootstrapWitness;
+use crate::cbor::*;
+use crate::data::CData;
+use crate::error::CError;
+use crate::option::COption;
+use crate::panic::*;
+use crate::private_key::{PrivateKey, PrivateKeys};
+use crate::ptr::*;
+use crate::secret_key::SecretKey;
+use crate::transaction::{
+  make_bip32_witnesses, make_vkey_witnesses, verify_vkey_witnesses, Transaction,
+  TransactionWitnessesVerification,
+};
+use crate::transaction_body::TransactionBody;
+use crate::transaction_hash::{hash_body_bytes, TransactionHash};
+use crate::transaction_unspent_output::{TransactionUnspentOutput, TransactionUnspentOutputs};
+use crate::transaction_witness_set::{merge_witness_items, TransactionWitnessSet};
+use crate::vkeywitness::Vkeywitness;
+use cbor_event::{Len, Special};
+
+// Witness set keys of the vkey and bootstrap witnesses
+const WITNESS_KEYS: [u64; 2] = [0, 2];
+
+// Encodes the items as a set the way the original value was encoded
+fn with_set_tag_of(original: &[u8], items: Vec<u8>) -> Vec<u8> {
+  match original.starts_with(&[0xd9, 0x01, 0x02]) {
+    true => [&original[..3], &items[..]].concat(),
+    false => items,
+  }
+}
+
+// Transaction keeping the bytes it was decoded from, so the body hash
+// stays the one the other signers signed whatever encoding they used.
+// Only the witness set is encoded again, when witnesses are added.
+#[repr(C)]
+#[derive(Copy)]
+pub struct FixedTransaction {
+  body: CData,
+  witness_set: CData,
+  is_valid: bool,
+  auxiliary_data: COption<CData>,
+  // Pre-Alonzo transactions are encoded without the validity flag
+  pre_alonzo: bool,
+}
+
+#[allow(clippy::non_canonical_clone_impl)]
+impl Clone for FixedTransaction {
+  fn clone(&self) -> Self {
+    let body = unsafe { self.body.unowned().expect("Bad bytes pointer").into() };
+    let witness_set = unsafe { self.witness_set.unowned().expect("Bad bytes pointer").into() };
+    let auxiliary_data: Option<CData> = self.auxiliary_data.into();
+    let auxiliary_data: Option<CData> = auxiliary_data.map(|auxiliary_data| unsafe {
+      auxiliary_data.unowned().expect("Bad bytes pointer").into()
+    });
+    Self {
+      body,
+      witness_set,
+      is_valid: self.is_valid,
+      auxiliary_data: auxiliary_data.into(),
+      pre_alonzo: self.pre_alonzo,
+    }
+  }
+}
+
+impl Free for FixedTransaction {
+  unsafe fn free(&mut self) {
+    self.body.free();
+    self.witness_set.free();
+    self.auxiliary_data.free();
+  }
+}
+
+impl FixedTransaction {
+  pub fn body(&self) -> Result<TransactionBody> {
+    unsafe { self.body.unowned() }.and_then(TransactionBody::from_cbor)
+  }
+
+  pub fn witness_set(&self) -> Result<TransactionWitnessSet> {
+    unsafe { self.witness_set.unowned() }
+      .and_then(TransactionWitnessSet::from_cbor)
+  }
+
+  pub fn hash(&self) -> Result<TransactionHash> {
+    unsafe { self.body.unowned() }.map(hash_body_bytes)
+  }
+
+  pub fn verify_witnesses(
+    &self, utxos: &[TransactionUnspentOutput],
+  ) -> Result<TransactionWitnessesVerification> {
+    let tx_body_hash = self.hash()?;
+    let mut body = self.body()?;
+    let mut witness_set = self.witness_set()?;
+    let verification = verify_vkey_witnesses(&body, &witness_set, &tx_body_hash, utxos);
+    unsafe {
+      body.free();
+      witness_set.free();
+    }
+    verification
+  }
+
+  // Copy of the transaction with the witnesses added to its witness set.
+  // Only the vkey and bootstrap entries are encoded again, the other ones
+  // (Plutus data, redeemers...) keep their bytes as the script data hash needs them.
+  fn with_witnesses(
+    &self, vkeywitnesses: Vec<Vkeywitness>, bootstraps: Vec<BootstrapWitness>,
+  ) -> Result<Self> {
+    let mut entries = map_entries(unsafe { self.witness_set.unowned()? })?;
+    let keys: Vec<(u64, Vec<u8>)> = entries
+      .iter()
+      .filter(|(key, _)| WITNESS_KEYS.contains(key))
+      .cloned()
+      .collect();
+    let mut witness_set = TransactionWitnessSet::from_cbor(&encode_map(keys.clone())?)?;
+    let witness_set_bytes = witness_set
+      .add_witnesses(vkeywitnesses, bootstraps)
+      .and_then(|_| witness_set.to_cbor());
+    unsafe { witness_set.free() };
+    for (key, value) in map_entries(&witness_set_bytes?)? {
+      let old = keys.iter().find(|(k, _)| *k == key).map(|(_, value)| value);
+      match old {
+        Some(old) => {
+          // The entry keeps its bytes when no witness was added to it
+          if array_items(&strip_set_tag(old.clone()))?.len() != array_items(&value)?.len() {
+            take_entry(&mut entries, key);
+            entries.push((key, with_set_tag_of(old, value)));
+          }
+        }
+        None => entries.push((key, value)),
+      }
+    }
+    Ok(self.with_witness_set_bytes(encode_map(entries)?))
+  }
+
+  // Copy of the transaction with the witness set encoded as the bytes
+  #[allow(clippy::clone_on_copy)]
+  fn with_witness_set_bytes(&self, bytes: Vec<u8>) -> Self {
+    let mut transaction = self.clone();
+    unsafe { transaction.witness_set.free() };
+    transaction.witness_set = bytes.into();
+    transaction
+  }
+
+  // Copy of the transaction with the other witness set merged into its own.
+  // Entries the other set adds nothing to keep their bytes, like in with_witnesses.
+  pub fn add_witness_set(&self, witness_set: &TransactionWitnessSet) -> Result<Self> {
+    let mut entries = map_entries(unsafe { self.witness_set.unowned()? })?;
+    for (key, value) in map_entries(&witness_set.to_cbor()?)? {
+      let other = array_items(&strip_set_tag(value.clone()))?;
+      let old = entries.iter().find(|(k, _)| *k == key).map(|(_, value)| value.clone());
+      match old {
+        Some(old) => {
+          let items = array_items(&strip_set_tag(old.clone()))?;
+          let count = items.len();
+          let merged = merge_witness_items(key, items, other)?;
+          if array_items(&merged)?.len() != count {
+            take_entry(&mut entries, key);
+            entries.push((key, with_set_tag_of(&old, merged)));
+          }
+        }
+        None => entries.push((key, value)),
+      }
+    }
+    Ok(self.with_witness_set_bytes(encode_map(entries)?))
+  }
+
+  pub fn sign(&self, keys: &[PrivateKey]) -> Result<Self> {
+    let tx_body_hash = self.hash()?;
+    self.with_witnesses(make_vkey_witnesses(&tx_body_hash, keys)?, Vec::new())
+  }
+
+  pub fn sign_with_secret_key(&self, key: &SecretKey) -> Result<Self> {
+    let tx_body_hash = self.hash()?;
+    self.with_witnesses(vec![key.make_vkey_witness(&tx_body_hash)?], Vec::new())
+  }
+
+  pub fn sign_bip32(
+    &self, root_key: Bip32PrivateKey, paths: &[DerivationPath],
+    utxos: &[TransactionUnspentOutput],
+  ) -> Result<Self> {
+    let tx_body_hash = self.hash()?;
+    let mut body = self.body()?;
+    let witnesses = make_bip32_witnesses(&body, &tx_body_hash, root_key, paths, utxos);
+    unsafe { body.free() };
+    let (vkeywitnesses, bootstraps) = witnesses?;
+    self.with_witnesses(vkeywitnesses, bootstraps)
+  }
+}
+
+impl CborSerialize for FixedTransaction {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    if self.pre_alonzo && !self.is_valid {
+      return Err("Pre-Alonzo transaction can't be invalid".into());
+    }
+    se.write_array(Len::Len(if self.pre_alonzo { 3 } else { 4 }))?;
+    se.write_raw_bytes(unsafe { self.body.unowned()? })?;
+    se.write_raw_bytes(unsafe { self.witness_set.unowned()? })?;
+    if !self.pre_alonzo {
+      se.write_special(Special::Bool(self.is_valid))?;
+    }
+    let auxiliary_data: Option<CData> = self.auxiliary_data.into();
+    write_nullable(se, auxiliary_data, |se, auxiliary_data| {
+      se.write_raw_bytes(unsafe { auxiliary_data.unowned()? })?;
+      Ok(())
+    })
+  }
+}
+
+impl CborDeserialize for FixedTransaction {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    let len = de.array()?;
+    let body = raw_item(de)?;
+    let witness_set = raw_item(de)?;
+    // Pre-Alonzo transactions have no validity flag
+    let pre_alonzo = len == Len::Len(3);
+    let is_valid = match pre_alonzo {
+      true => true,
+      false => de.bool()?,
+    };
+    let auxiliary_data = read_nullable(de, raw_item)?;
+    read_array_end(de, len)?;
+    // Parts are decoded once so that malformed ones are rejected here
+    unsafe {
+      TransactionBody::from_cbor(&body)?.free();
+      TransactionWitnessSet::from_cbor(&witness_set)?.free();
+    }
+    Ok(Self {
+      body: body.into(),
+      witness_set: witness_set.into(),
+      is_valid,
+      auxiliary_data: auxiliary_data.map(|auxiliary_data| auxiliary_data.into()).into(),
+      pre_alonzo,
+    })
+  }
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_fixed_transaction_from_bytes(
+  data: CData, result: &mut FixedTransaction, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    data
+      .unowned()
+      .and_then(FixedTransaction::from_cbor)
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_fixed_transaction_to_bytes(
+  transaction: FixedTransaction, result: &mut CData, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| transaction.to_cbor().map(|bytes| bytes.into()))
+    .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_fixed_transaction_from_transaction(
+  transaction: Transaction, result: &mut FixedTransaction, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    transaction
+      .to_cbor()
+      .and_then(|bytes| FixedTransaction::from_cbor(&bytes))
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_fixed_transaction_body(
+  transaction: FixedTransaction, result: &mut TransactionBody, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| transaction.body()).response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_fixed_transaction_witness_set(
+  transaction: FixedTransaction, result: &mut TransactionWitnessSet, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| transaction.witness_set()).response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_fixed_transaction_hash(
+  transaction: FixedTransaction, result: &mut TransactionHash, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| transaction.hash()).response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_fixed_transaction_add_witnesses(
+  transaction: FixedTransaction, witness_set: TransactionWitnessSet,
+  result: &mut FixedTransaction, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| transaction.add_witness_set(&witness_set)).response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_fixed_transaction_sign(
+  transaction: FixedTransaction, keys: PrivateKeys, result: &mut FixedTransaction,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| keys.unowned().and_then(|keys| transaction.sign(keys)))
+    .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_fixed_transaction_sign_with_secret_key(
+  transaction: FixedTransaction, key: *const SecretKey, result: &mut FixedTransaction,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    SecretKey::from_handle(key).and_then(|key| transaction.sign_with_secret_key(key))
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_fixed_transaction_sign_bip32(
+  transaction: FixedTransaction, root_key: Bip32PrivateKey, paths: DerivationPaths,
+  utxos: TransactionUnspentOutputs, result: &mut FixedTransaction, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    paths
+      .unowned()
+      .zip(utxos.unowned())
+      .and_then(|(paths, utxos)| transaction.sign_bip32(root_key, paths, utxos))
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_fixed_transaction_verify_witnesses(
+  transaction: FixedTransaction, utxos: TransactionUnspentOutputs,
+  result: &mut TransactionWitnessesVerification, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    utxos.unowned().and_then(|utxos| transaction.verify_witnesses(utxos))
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc, clippy::clone_on_copy)]
+pub unsafe extern "C" fn cardano_fixed_transaction_clone(
+  transaction: FixedTransaction, result: &mut FixedTransaction, error: &mut CError,
+) -> bool {
+  handle_exception(|| transaction.clone()).response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_fixed_transaction_free(transaction: &mut FixedTransaction) {
+  transaction.free();
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_utils::hex;
+  use cardano_serialization_lib::crypto::PrivateKey as RPrivateKey;
+  use std::convert::TryInto;
+
+  const BODY: &str = "a3008001800200";
+
+  fn key(byte: u8) -> PrivateKey {
+    RPrivateKey::from_normal_bytes(&[byte; 32]).unwrap().try_into().unwrap()
+  }
+
+  fn witness_set(transaction: &FixedTransaction) -> Vec<(u64, Vec<u8>)> {
+    map_entries(unsafe { transaction.witness_set.unowned().unwrap() }).unwrap()
+  }
+
+  #[test]
+  fn signing_keeps_the_other_witness_entries() {
+    // Plutus data as an indefinite list and vkey witnesses as a tagged set
+    let plutus_data = "819f0102ff";
+    let bytes = hex(&format!("84{}a204{}00d9010280f5f6", BODY, plutus_data));
+    let mut transaction = FixedTransaction::from_cbor(&bytes).unwrap();
+    let mut signed = transaction.sign(&[key(1)]).unwrap();
+    let entries = witness_set(&signed);
+    assert_eq!(entries[0].0, 0);
+    assert!(entries[0].1.starts_with(&hex("d9010281")));
+    assert_eq!(entries[1], (4, hex(plutus_data)));
+    // Signing again with the same key leaves the bytes as they are
+    let mut again = signed.sign(&[key(1)]).unwrap();
+    assert_eq!(again.to_cbor().unwrap(), signed.to_cbor().unwrap());
+    assert!(signed.verify_witnesses(&[]).is_ok());
+    unsafe {
+      transaction.free();
+      signed.free();
+      again.free();
+    }
+  }
+
+  #[test]
+  fn pre_alonzo_transaction_keeps_its_arity() {
+    let bytes = hex(&format!("83{}a0f6", BODY));
+    let mut transaction = FixedTransaction::from_cbor(&bytes).unwrap();
+    assert!(transaction.pre_alonzo && transaction.is_valid);
+    assert_eq!(transaction.to_cbor().unwrap(), bytes);
+    let mut signed = transaction.sign(&[key(1)]).unwrap();
+    assert_eq!(signed.to_cbor().unwrap()[0], 0x83);
+    unsafe {
+      transaction.free();
+      signed.free();
+    }
+  }
+
+  #[test]
+  fn added_witness_set_keeps_the_other_witness_entries() {
+    // Plutus data as an indefinite list and a redeemer, without vkey witnesses
+    let plutus_data = "819f0102ff";
+    let redeemers = "8184000001820101";
+    let bytes = hex(&format!("84{}a204{}05{}f5f6", BODY, plutus_data, redeemers));
+    let mut transaction = FixedTransaction::from_cbor(&bytes).unwrap();
+    let vkey_witness = [hex("825820"), vec![2; 32], hex("5840"), vec![0; 64]].concat();
+    let other_bytes = [hex("a20081"), vkey_witness.clone(), hex("05"), hex(redeemers)].concat();
+    let mut other = TransactionWitnessSet::from_cbor(&other_bytes).unwrap();
+    let mut merged = transaction.add_witness_set(&other).unwrap();
+    let entries = witness_set(&merged);
+    assert_eq!(entries[0], (0, [hex("81"), vkey_witness].concat()));
+    assert_eq!(entries[1], (4, hex(plutus_data)));
+    assert_eq!(entries[2], (5, hex(redeemers)));
+    // Adding the same witnesses again leaves the bytes as they are
+    let mut again = merged.add_witness_set(&other).unwrap();
+    assert_eq!(again.to_cbor().unwrap(), merged.to_cbor().unwrap());
+    unsafe {
+      transaction.free();
+      other.free();
+      merged.free();
+      again.free();
+    }
+  }
+}
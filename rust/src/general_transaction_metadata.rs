@@ -58,19 +58,29 @@ pub enum TransactionMetadatum {
   TextKind(CharPtr),
 }
 
-// for transmute
-pub struct TInt(i128);
+impl TryFrom<CInt128> for RInt {
+  type Error = CError;
 
-impl From<CInt128> for RInt {
-  fn from(int: CInt128) -> Self {
-    unsafe { std::mem::transmute(TInt(int.into())) }
+  fn try_from(int: CInt128) -> Result<Self> {
+    let int: i128 = int.into();
+    if int >= 0 {
+      u64::try_from(int).map(|int| Self::new(&to_bignum(int)))
+    } else {
+      u64::try_from(int.unsigned_abs()).map(|int| Self::new_negative(&to_bignum(int)))
+    }
+    .map_err(|_| "Int is out of the CBOR integer range".into())
   }
 }
 
-impl From<RInt> for CInt128 {
-  fn from(int: RInt) -> Self {
-    let int: TInt = unsafe { std::mem::transmute(int) };
-    int.0.into()
+impl TryFrom<RInt> for CInt128 {
+  type Error = CError;
+
+  fn try_from(int: RInt) -> Result<Self> {
+    int
+      .to_str()
+      .parse::<i128>()
+      .map(|int| int.into())
+      .map_err(|_| "Int is not a valid i128".into())
   }
 }
 
@@ -119,7 +129,7 @@ impl TryFrom<TransactionMetadatum> for RTransactionMetadatum {
       TransactionMetadatum::MetadataListKind(metadata_list) => metadata_list
         .try_into()
         .map(|metadata_list| Self::new_list(&metadata_list)),
-      TransactionMetadatum::IntKind(int) => Ok(Self::new_int(&int.into())),
+      TransactionMetadatum::IntKind(int) => int.try_into().map(|int| Self::new_int(&int)),
       TransactionMetadatum::BytesKind(bytes) => {
         let bytes = unsafe { bytes.unowned().expect("Bad bytes pointer") };
         Self::new_bytes(bytes.to_vec()).into_result()
@@ -150,7 +160,8 @@ impl TryFrom<RTransactionMetadatum> for TransactionMetadatum {
       TransactionMetadatumKind::Int => transaction_metadatum
         .as_int()
         .into_result()
-        .map(|int| Self::IntKind(int.into())),
+        .and_then(|int| int.try_into())
+        .map(Self::IntKind),
       TransactionMetadatumKind::Bytes => transaction_metadatum
         .as_bytes()
         .into_result()
@@ -305,3 +316,24 @@ pub unsafe extern "C" fn cardano_general_transaction_metadata_free(
 ) {
   general_transaction_metadata.free()
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn round_trip(int: i128) -> Result<i128> {
+    let int: RInt = CInt128::from(int).try_into()?;
+    CInt128::try_from(int).map(|int| int.into())
+  }
+
+  #[test]
+  fn int_round_trips_in_cbor_range() {
+    let max = u64::MAX as i128;
+    for int in [0, 1, -1, i64::MIN as i128, max, -max] {
+      assert_eq!(round_trip(int).unwrap(), int);
+    }
+    assert!(round_trip(max + 1).is_err());
+    assert!(round_trip(-max - 1).is_err());
+    assert!(round_trip(i128::MIN).is_err());
+  }
+}
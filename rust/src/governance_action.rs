@@ -0,0 +1,374 @@
+use crate::address::reward::RewardAddress;
+use crate::anchor::Anchor;
+use crate::array::*;
+use crate::cbor::*;
+use crate::data::CData;
+use crate::error::CError;
+use crate::option::COption;
+use crate::panic::*;
+use crate::pool_registration::UnitInterval;
+use crate::protocol_param_update::{ConwayProtocolParamUpdate, ProtocolVersion};
+use crate::ptr::*;
+use crate::stake_credential::{ScriptHash, StakeCredential, StakeCredentials};
+use crate::transaction_body::Epoch;
+use crate::voting_procedure::GovernanceActionId;
+use crate::withdrawals::{Withdrawals, WithdrawalsKeyValue};
+use cbor_event::Len;
+use std::convert::TryInto;
+
+fn write_action_id(
+  se: &mut CborSerializer, gov_action_id: COption<GovernanceActionId>,
+) -> Result<()> {
+  write_nullable(se, gov_action_id.into(), |se, id: GovernanceActionId| id.serialize(se))
+}
+
+fn read_action_id(de: &mut CborDeserializer) -> Result<COption<GovernanceActionId>> {
+  read_nullable(de, GovernanceActionId::deserialize).map(|id| id.into())
+}
+
+fn write_policy_hash(se: &mut CborSerializer, policy_hash: COption<ScriptHash>) -> Result<()> {
+  write_nullable(se, policy_hash.into(), |se, hash: ScriptHash| hash.serialize(se))
+}
+
+fn read_policy_hash(de: &mut CborDeserializer) -> Result<COption<ScriptHash>> {
+  read_nullable(de, ScriptHash::deserialize).map(|hash| hash.into())
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct ParameterChangeAction {
+  gov_action_id: COption<GovernanceActionId>,
+  protocol_param_update: ConwayProtocolParamUpdate,
+  policy_hash: COption<ScriptHash>,
+}
+
+impl Free for ParameterChangeAction {
+  unsafe fn free(&mut self) {
+    self.protocol_param_update.free()
+  }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct HardForkInitiationAction {
+  gov_action_id: COption<GovernanceActionId>,
+  protocol_version: ProtocolVersion,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct TreasuryWithdrawalsAction {
+  withdrawals: Withdrawals,
+  policy_hash: COption<ScriptHash>,
+}
+
+impl Free for TreasuryWithdrawalsAction {
+  unsafe fn free(&mut self) {
+    self.withdrawals.free()
+  }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct NoConfidenceAction {
+  gov_action_id: COption<GovernanceActionId>,
+}
+
+pub type CommitteeMembersKeyValue = CKeyValue<StakeCredential, Epoch>;
+pub type CommitteeMembers = CArray<CommitteeMembersKeyValue>;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct UpdateCommitteeAction {
+  gov_action_id: COption<GovernanceActionId>,
+  members_to_remove: StakeCredentials,
+  members_to_add: CommitteeMembers,
+  quorum_threshold: UnitInterval,
+}
+
+impl Free for UpdateCommitteeAction {
+  unsafe fn free(&mut self) {
+    self.members_to_remove.free();
+    self.members_to_add.free();
+  }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct Constitution {
+  anchor: Anchor,
+  script_hash: COption<ScriptHash>,
+}
+
+impl Free for Constitution {
+  unsafe fn free(&mut self) {
+    self.anchor.free()
+  }
+}
+
+impl CborSerialize for Constitution {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    se.write_array(Len::Len(2))?;
+    self.anchor.serialize(se)?;
+    write_policy_hash(se, self.script_hash)
+  }
+}
+
+impl CborDeserialize for Constitution {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    let len = de.array()?;
+    if len != Len::Len(2) && len != Len::Indefinite {
+      return Err("Wrong Constitution array length".into());
+    }
+    let anchor = Anchor::deserialize(de)?;
+    let script_hash = read_policy_hash(de)?;
+    read_array_end(de, len)?;
+    Ok(Self { anchor, script_hash })
+  }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct NewConstitutionAction {
+  gov_action_id: COption<GovernanceActionId>,
+  constitution: Constitution,
+}
+
+impl Free for NewConstitutionAction {
+  unsafe fn free(&mut self) {
+    self.constitution.free()
+  }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+#[allow(clippy::large_enum_variant)]
+pub enum GovernanceAction {
+  ParameterChangeActionKind(ParameterChangeAction),
+  HardForkInitiationActionKind(HardForkInitiationAction),
+  TreasuryWithdrawalsActionKind(TreasuryWithdrawalsAction),
+  NoConfidenceActionKind(NoConfidenceAction),
+  UpdateCommitteeActionKind(UpdateCommitteeAction),
+  NewConstitutionActionKind(NewConstitutionAction),
+  InfoActionKind,
+}
+
+impl Free for GovernanceAction {
+  unsafe fn free(&mut self) {
+    match self {
+      GovernanceAction::ParameterChangeActionKind(action) => action.free(),
+      GovernanceAction::TreasuryWithdrawalsActionKind(action) => action.free(),
+      GovernanceAction::UpdateCommitteeActionKind(action) => action.free(),
+      GovernanceAction::NewConstitutionActionKind(action) => action.free(),
+      _ => {}
+    }
+  }
+}
+
+impl CborSerialize for GovernanceAction {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    match self {
+      GovernanceAction::ParameterChangeActionKind(action) => {
+        se.write_array(Len::Len(4))?.write_unsigned_integer(0)?;
+        write_action_id(se, action.gov_action_id)?;
+        action.protocol_param_update.serialize(se)?;
+        write_policy_hash(se, action.policy_hash)
+      }
+      GovernanceAction::HardForkInitiationActionKind(action) => {
+        se.write_array(Len::Len(3))?.write_unsigned_integer(1)?;
+        write_action_id(se, action.gov_action_id)?;
+        action.protocol_version.serialize(se)
+      }
+      GovernanceAction::TreasuryWithdrawalsActionKind(action) => {
+        let withdrawals = unsafe { action.withdrawals.unowned()? };
+        se.write_array(Len::Len(3))?.write_unsigned_integer(2)?;
+        se.write_map(Len::Len(withdrawals.len() as u64))?;
+        for withdrawal in withdrawals {
+          withdrawal.key.serialize(se)?;
+          se.write_unsigned_integer(withdrawal.val)?;
+        }
+        write_policy_hash(se, action.policy_hash)
+      }
+      GovernanceAction::NoConfidenceActionKind(action) => {
+        se.write_array(Len::Len(2))?.write_unsigned_integer(3)?;
+        write_action_id(se, action.gov_action_id)
+      }
+      GovernanceAction::UpdateCommitteeActionKind(action) => {
+        let members_to_remove = unsafe { action.members_to_remove.unowned()? };
+        let members_to_add = unsafe { action.members_to_add.unowned()? };
+        se.write_array(Len::Len(5))?.write_unsigned_integer(4)?;
+        write_action_id(se, action.gov_action_id)?;
+        se.write_array(Len::Len(members_to_remove.len() as u64))?;
+        for member in members_to_remove {
+          member.serialize(se)?;
+        }
+        se.write_map(Len::Len(members_to_add.len() as u64))?;
+        for member in members_to_add {
+          member.key.serialize(se)?;
+          se.write_unsigned_integer(member.val.into())?;
+        }
+        action.quorum_threshold.serialize(se)
+      }
+      GovernanceAction::NewConstitutionActionKind(action) => {
+        se.write_array(Len::Len(3))?.write_unsigned_integer(5)?;
+        write_action_id(se, action.gov_action_id)?;
+        action.constitution.serialize(se)
+      }
+      GovernanceAction::InfoActionKind => {
+        se.write_array(Len::Len(1))?.write_unsigned_integer(6)?;
+        Ok(())
+      }
+    }
+  }
+}
+
+impl CborDeserialize for GovernanceAction {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    let (len, action) = match peek_kind(de)? {
+      0 => {
+        let len = read_kind_array(de, 0, 3, "ParameterChangeAction")?;
+        let gov_action_id = read_action_id(de)?;
+        let protocol_param_update = ConwayProtocolParamUpdate::deserialize(de)?;
+        let policy_hash = read_policy_hash(de)?;
+        let action = ParameterChangeAction {
+          gov_action_id,
+          protocol_param_update,
+          policy_hash,
+        };
+        (len, Self::ParameterChangeActionKind(action))
+      }
+      1 => {
+        let len = read_kind_array(de, 1, 2, "HardForkInitiationAction")?;
+        let gov_action_id = read_action_id(de)?;
+        let protocol_version = ProtocolVersion::deserialize(de)?;
+        let action = HardForkInitiationAction {
+          gov_action_id,
+          protocol_version,
+        };
+        (len, Self::HardForkInitiationActionKind(action))
+      }
+      2 => {
+        let len = read_kind_array(de, 2, 2, "TreasuryWithdrawalsAction")?;
+        let withdrawals = read_map(de, |de| {
+          RewardAddress::deserialize(de).zip(de.unsigned_integer().into_result())
+        })?;
+        let policy_hash = read_policy_hash(de)?;
+        let action = TreasuryWithdrawalsAction {
+          withdrawals: withdrawals
+            .into_iter()
+            .map(|withdrawal| withdrawal.into())
+            .collect::<Vec<WithdrawalsKeyValue>>()
+            .into(),
+          policy_hash,
+        };
+        (len, Self::TreasuryWithdrawalsActionKind(action))
+      }
+      3 => {
+        let len = read_kind_array(de, 3, 1, "NoConfidenceAction")?;
+        let gov_action_id = read_action_id(de)?;
+        (len, Self::NoConfidenceActionKind(NoConfidenceAction { gov_action_id }))
+      }
+      4 => {
+        let len = read_kind_array(de, 4, 4, "UpdateCommitteeAction")?;
+        let gov_action_id = read_action_id(de)?;
+        let members_to_remove = read_array(de, StakeCredential::deserialize)?;
+        let members_to_add = read_map(de, |de| {
+          let credential = StakeCredential::deserialize(de)?;
+          let epoch = de.unsigned_integer()?.try_into().map_err(|_| "Epoch is too big")?;
+          Ok((credential, epoch))
+        })?;
+        let quorum_threshold = UnitInterval::deserialize(de)?;
+        let action = UpdateCommitteeAction {
+          gov_action_id,
+          members_to_remove: members_to_remove.into(),
+          members_to_add: members_to_add
+            .into_iter()
+            .map(|member| member.into())
+            .collect::<Vec<CommitteeMembersKeyValue>>()
+            .into(),
+          quorum_threshold,
+        };
+        (len, Self::UpdateCommitteeActionKind(action))
+      }
+      5 => {
+        let len = read_kind_array(de, 5, 2, "NewConstitutionAction")?;
+        let gov_action_id = read_action_id(de)?;
+        let constitution = Constitution::deserialize(de)?;
+        let action = NewConstitutionAction {
+          gov_action_id,
+          constitution,
+        };
+        (len, Self::NewConstitutionActionKind(action))
+      }
+      6 => read_kind_array(de, 6, 0, "InfoAction").map(|len| (len, Self::InfoActionKind))?,
+      kind => return Err(format!("Wrong GovernanceAction kind {}", kind).into()),
+    };
+    read_array_end(de, len)?;
+    Ok(action)
+  }
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_governance_action_to_bytes(
+  governance_action: GovernanceAction, result: &mut CData, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| governance_action.to_cbor().map(|bytes| bytes.into()))
+    .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_governance_action_from_bytes(
+  data: CData, result: &mut GovernanceAction, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    data
+      .unowned()
+      .and_then(GovernanceAction::from_cbor)
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_governance_action_free(
+  governance_action: &mut GovernanceAction,
+) {
+  governance_action.free()
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_committee_members_free(committee_members: &mut CommitteeMembers) {
+  committee_members.free()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_utils::hex;
+
+  #[test]
+  fn parameter_change_keeps_conway_update() {
+    // [0, null, {0: 44, 30: 100000000000}, null]
+    let bytes = hex("8400f6a200182c181e1b000000174876e800f6");
+    let mut action = GovernanceAction::from_cbor(&bytes).unwrap();
+    match action {
+      GovernanceAction::ParameterChangeActionKind(change) => {
+        let update = change.protocol_param_update;
+        assert_eq!(Option::from(update.minfee_a), Some(44));
+        assert_eq!(Option::from(update.governance_action_deposit), Some(100_000_000_000));
+      }
+      _ => panic!("not a parameter change"),
+    }
+    assert_eq!(action.to_cbor().unwrap(), bytes);
+    unsafe { action.free() };
+  }
+
+  #[test]
+  fn parameter_change_rejects_alonzo_only_keys() {
+    // protocol_version (14) is no longer part of the update
+    assert!(GovernanceAction::from_cbor(&hex("8400f6a10e820900f6")).is_err());
+  }
+}
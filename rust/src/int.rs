@@ -11,6 +11,10 @@ pub struct CInt128 {
   w2: u64,
 }
 
+const _: () = assert!(
+  std::mem::size_of::<CInt128>() == 16 && std::mem::align_of::<CInt128>() == 8
+);
+
 impl From<i128> for CInt128 {
   fn from(int: i128) -> Self {
     Self {
@@ -98,6 +102,10 @@ pub struct CBigInt {
   data: CArray<u32>,
 }
 
+const _: () = assert!(
+  std::mem::size_of::<CBigInt>() == 24 && std::mem::align_of::<CBigInt>() == 8
+);
+
 impl Free for CBigInt {
   unsafe fn free(&mut self) {
     self.data.free()
@@ -0,0 +1,322 @@
+use crate::bip32_private_key::Bip32PrivateKey;
+use crate::data::CData;
+use crate::error::CError;
+use crate::panic::*;
+use crate::private_key::{PrivateKey, EXTENDED_PRIVATE_KEY_LENGTH, NORMAL_PRIVATE_KEY_LENGTH};
+use crate::ptr::*;
+use crate::secret_key::wipe;
+use cardano_serialization_lib::crypto::{
+  Bip32PrivateKey as RBip32PrivateKey, PrivateKey as RPrivateKey,
+};
+use cryptoxide::chacha20poly1305::ChaCha20Poly1305;
+use cryptoxide::hmac::Hmac;
+use cryptoxide::pbkdf2::pbkdf2;
+use cryptoxide::scrypt::{scrypt, ScryptParams};
+use cryptoxide::sha2::Sha512;
+use std::convert::{TryFrom, TryInto};
+
+const SALT_SIZE: usize = 32;
+const NONCE_SIZE: usize = 12;
+const TAG_SIZE: usize = 16;
+const KEY_SIZE: usize = 32;
+
+// Versioned format: magic, version, scrypt parameters, salt, nonce,
+// ciphertext and tag. The header is authenticated along with the data.
+const MAGIC: &[u8; 4] = b"CKEY";
+const VERSION: u8 = 1;
+const HEADER_SIZE: usize = 4 + 1 + 1 + 4 + 4;
+const SCRYPT_LOG_N: u8 = 15;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+// Bounds the work asked by a stored header
+const MAX_SCRYPT_LOG_N: u8 = 20;
+const MAX_SCRYPT_RP: u32 = 64;
+
+// EMIP-3 format of the light wallets: salt, nonce, tag and ciphertext,
+// with the key derived by PBKDF2-HMAC-SHA512
+const EMIP3_ITERATIONS: u32 = 19_162;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub enum KeyEncryptionFormat {
+  Versioned,
+  Emip3,
+}
+
+fn random_bytes(len: usize) -> Result<Vec<u8>> {
+  let mut bytes = vec![0u8; len];
+  getrandom::getrandom(&mut bytes).map_err(|err| format!("{}", err))?;
+  Ok(bytes)
+}
+
+fn scrypt_key(password: &[u8], salt: &[u8], log_n: u8, r: u32, p: u32) -> Result<[u8; KEY_SIZE]> {
+  let rp = r.saturating_mul(p);
+  if log_n == 0 || log_n > MAX_SCRYPT_LOG_N || r == 0 || p == 0 || rp > MAX_SCRYPT_RP {
+    return Err("Unsupported scrypt parameters".into());
+  }
+  // scrypt needs N < 2^(16r), cryptoxide panics otherwise
+  if u32::from(log_n) >= 16 * r {
+    return Err("Unsupported scrypt parameters".into());
+  }
+  let mut key = [0u8; KEY_SIZE];
+  scrypt(password, salt, &ScryptParams::new(log_n, r, p), &mut key);
+  Ok(key)
+}
+
+fn emip3_key(password: &[u8], salt: &[u8]) -> [u8; KEY_SIZE] {
+  let mut key = [0u8; KEY_SIZE];
+  pbkdf2(&mut Hmac::new(Sha512::new(), password), salt, EMIP3_ITERATIONS, &mut key);
+  key
+}
+
+fn seal(key: &[u8], nonce: &[u8], aad: &[u8], data: &[u8]) -> (Vec<u8>, [u8; TAG_SIZE]) {
+  let mut ciphertext = vec![0u8; data.len()];
+  let mut tag = [0u8; TAG_SIZE];
+  ChaCha20Poly1305::new(key, nonce, aad).encrypt(data, &mut ciphertext, &mut tag);
+  (ciphertext, tag)
+}
+
+fn open(key: &[u8], nonce: &[u8], aad: &[u8], ciphertext: &[u8], tag: &[u8]) -> Result<Vec<u8>> {
+  let mut data = vec![0u8; ciphertext.len()];
+  if ChaCha20Poly1305::new(key, nonce, aad).decrypt(ciphertext, &mut data, tag) {
+    Ok(data)
+  } else {
+    wipe(&mut data);
+    Err("Wrong password or corrupted data".into())
+  }
+}
+
+pub fn encrypt_with_password(
+  password: &[u8], data: &[u8], format: KeyEncryptionFormat,
+) -> Result<Vec<u8>> {
+  let salt = random_bytes(SALT_SIZE)?;
+  let nonce = random_bytes(NONCE_SIZE)?;
+  match format {
+    KeyEncryptionFormat::Versioned => {
+      let header = [
+        MAGIC.as_ref(),
+        &[VERSION, SCRYPT_LOG_N],
+        &SCRYPT_R.to_be_bytes(),
+        &SCRYPT_P.to_be_bytes(),
+      ]
+      .concat();
+      let mut key = scrypt_key(password, &salt, SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P)?;
+      let (ciphertext, tag) = seal(&key, &nonce, &header, data);
+      wipe(&mut key);
+      Ok([header.as_slice(), &salt, &nonce, &ciphertext, &tag].concat())
+    }
+    KeyEncryptionFormat::Emip3 => {
+      let mut key = emip3_key(password, &salt);
+      let (ciphertext, tag) = seal(&key, &nonce, &[], data);
+      wipe(&mut key);
+      Ok([salt.as_slice(), &nonce, &tag, &ciphertext].concat())
+    }
+  }
+}
+
+// The format is given by the caller, as EMIP-3 salts are random and
+// may start with the magic of the versioned format
+pub fn decrypt_with_password(
+  password: &[u8], data: &[u8], format: KeyEncryptionFormat,
+) -> Result<Vec<u8>> {
+  match format {
+    KeyEncryptionFormat::Versioned => {
+      if data.len() < HEADER_SIZE + SALT_SIZE + NONCE_SIZE + TAG_SIZE {
+        return Err("Encrypted data is too short".into());
+      }
+      let (header, rest) = data.split_at(HEADER_SIZE);
+      if !header.starts_with(MAGIC) {
+        return Err("Encrypted data has no versioned format header".into());
+      }
+      if header[4] != VERSION {
+        return Err(format!("Unsupported encrypted key version: {}", header[4]).into());
+      }
+      let r = u32::from_be_bytes(header[6..10].try_into().unwrap());
+      let p = u32::from_be_bytes(header[10..14].try_into().unwrap());
+      let (salt, rest) = rest.split_at(SALT_SIZE);
+      let (nonce, rest) = rest.split_at(NONCE_SIZE);
+      let (ciphertext, tag) = rest.split_at(rest.len() - TAG_SIZE);
+      let mut key = scrypt_key(password, salt, header[5], r, p)?;
+      let data = open(&key, nonce, header, ciphertext, tag);
+      wipe(&mut key);
+      data
+    }
+    KeyEncryptionFormat::Emip3 => {
+      if data.len() < SALT_SIZE + NONCE_SIZE + TAG_SIZE {
+        return Err("Encrypted data is too short".into());
+      }
+      let (salt, rest) = data.split_at(SALT_SIZE);
+      let (nonce, rest) = rest.split_at(NONCE_SIZE);
+      let (tag, ciphertext) = rest.split_at(TAG_SIZE);
+      let mut key = emip3_key(password, salt);
+      let data = open(&key, nonce, &[], ciphertext, tag);
+      wipe(&mut key);
+      data
+    }
+  }
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_key_encryption_encrypt(
+  password: CData, data: CData, format: KeyEncryptionFormat, result: &mut CData,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    password
+      .unowned()
+      .zip(data.unowned())
+      .and_then(|(password, data)| encrypt_with_password(password, data, format))
+      .map(|bytes| bytes.into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_key_encryption_decrypt(
+  password: CData, data: CData, format: KeyEncryptionFormat, result: &mut CData,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    password
+      .unowned()
+      .zip(data.unowned())
+      .and_then(|(password, data)| decrypt_with_password(password, data, format))
+      .map(|bytes| bytes.into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_bip32_private_key_encrypt(
+  pk: Bip32PrivateKey, password: CData, format: KeyEncryptionFormat, result: &mut CData,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    let mut bytes = RBip32PrivateKey::try_from(pk)?.as_bytes();
+    let encrypted = password
+      .unowned()
+      .and_then(|password| encrypt_with_password(password, &bytes, format));
+    wipe(&mut bytes);
+    encrypted.map(|bytes| bytes.into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_bip32_private_key_decrypt(
+  data: CData, password: CData, format: KeyEncryptionFormat, result: &mut Bip32PrivateKey,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    password
+      .unowned()
+      .zip(data.unowned())
+      .and_then(|(password, data)| decrypt_with_password(password, data, format))
+      .and_then(|mut bytes| {
+        let pk = RBip32PrivateKey::from_bytes(&bytes).into_result();
+        wipe(&mut bytes);
+        pk
+      })
+      .map(|pk| pk.into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_private_key_encrypt(
+  private_key: PrivateKey, password: CData, format: KeyEncryptionFormat, result: &mut CData,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    let mut bytes = RPrivateKey::try_from(private_key)?.as_bytes();
+    let encrypted = password
+      .unowned()
+      .and_then(|password| encrypt_with_password(password, &bytes, format));
+    wipe(&mut bytes);
+    encrypted.map(|bytes| bytes.into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_private_key_decrypt(
+  data: CData, password: CData, format: KeyEncryptionFormat, result: &mut PrivateKey,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    let mut bytes = password
+      .unowned()
+      .zip(data.unowned())
+      .and_then(|(password, data)| decrypt_with_password(password, data, format))?;
+    let private_key = match bytes.len() {
+      EXTENDED_PRIVATE_KEY_LENGTH => RPrivateKey::from_extended_bytes(&bytes).into_result(),
+      NORMAL_PRIVATE_KEY_LENGTH => RPrivateKey::from_normal_bytes(&bytes).into_result(),
+      _ => Err("Decrypted data is not a private key".into()),
+    };
+    wipe(&mut bytes);
+    private_key.and_then(|private_key| private_key.try_into())
+  })
+  .response(result, error)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_utils::hex;
+
+  // EMIP-3 reference vector: password, salt, nonce and data of the spec
+  #[test]
+  fn emip3_matches_the_reference_vector() {
+    let salt = hex("50515253c0c1c2c3c4c5c6c750515253c0c1c2c3c4c5c6c750515253c0c1c2c3");
+    let nonce = hex("50515253c0c1c2c3c4c5c6c7");
+    let encrypted = hex(concat!(
+      "50515253c0c1c2c3c4c5c6c750515253c0c1c2c3c4c5c6c750515253c0c1c2c3",
+      "50515253c0c1c2c3c4c5c6c7",
+      "c266630887d216bf88cc4990f73bad7f",
+      "35bc7c0225b38fe24a7c28b5f9bda6283e3c5768",
+    ));
+    let key = emip3_key(b"password", &salt);
+    let (ciphertext, tag) = seal(&key, &nonce, &[], b"some data to encrypt");
+    assert_eq!([salt.as_slice(), &nonce, &tag, &ciphertext].concat(), encrypted);
+    let format = KeyEncryptionFormat::Emip3;
+    let data = decrypt_with_password(b"password", &encrypted, format).unwrap();
+    assert_eq!(data, b"some data to encrypt");
+  }
+
+  #[test]
+  fn both_formats_round_trip() {
+    for format in [KeyEncryptionFormat::Versioned, KeyEncryptionFormat::Emip3].iter() {
+      let data = encrypt_with_password(b"password", b"secret", *format).unwrap();
+      assert_eq!(decrypt_with_password(b"password", &data, *format).unwrap(), b"secret");
+      assert!(decrypt_with_password(b"wrong", &data, *format).is_err());
+    }
+  }
+
+  #[test]
+  fn emip3_salt_may_start_with_the_magic() {
+    let salt = [MAGIC.as_ref(), &[0u8; SALT_SIZE - 4]].concat();
+    let nonce = [1u8; NONCE_SIZE];
+    let (ciphertext, tag) = seal(&emip3_key(b"password", &salt), &nonce, &[], b"secret");
+    let data = [salt.as_slice(), &nonce, &tag, &ciphertext].concat();
+    let format = KeyEncryptionFormat::Emip3;
+    assert_eq!(decrypt_with_password(b"password", &data, format).unwrap(), b"secret");
+  }
+
+  #[test]
+  fn header_with_too_large_n_for_r_is_rejected() {
+    let data = encrypt_with_password(b"password", b"secret", KeyEncryptionFormat::Versioned);
+    let mut data = data.unwrap();
+    // log_n = 20 with r = 1 breaks N < 2^(16r)
+    data[5] = 20;
+    data[6..10].copy_from_slice(&1u32.to_be_bytes());
+    let format = KeyEncryptionFormat::Versioned;
+    assert!(decrypt_with_password(b"password", &data, format).is_err());
+    assert!(scrypt_key(b"password", &[0; SALT_SIZE], 16, 1, 1).is_err());
+  }
+}
@@ -6,8 +6,14 @@ pub mod error;
 pub mod string;
 pub mod data;
 pub mod stake_credential;
+pub mod anchor;
+pub mod drep;
 pub mod bip32_private_key;
 pub mod bip32_public_key;
+pub mod bip39;
+pub mod cip1852;
+pub mod key_encryption;
+pub mod secret_key;
 pub mod ed25519_signature;
 pub mod linear_fee;
 pub mod private_key;
@@ -27,7 +33,22 @@ pub mod pool_retirement;
 pub mod pool_registration;
 pub mod genesis_key_delegation;
 pub mod move_instantaneous_rewards_cert;
+pub mod stake_registration_with_deposit;
+pub mod stake_deregistration_with_deposit;
+pub mod vote_delegation;
+pub mod stake_and_vote_delegation;
+pub mod stake_registration_and_delegation;
+pub mod vote_registration_and_delegation;
+pub mod stake_vote_registration_and_delegation;
+pub mod committee_hot_auth;
+pub mod committee_cold_resign;
+pub mod drep_registration;
+pub mod drep_deregistration;
+pub mod drep_update;
 pub mod certificate;
+pub mod voting_procedure;
+pub mod governance_action;
+pub mod proposal_procedure;
 pub mod value;
 pub mod protocol_param_update;
 pub mod transaction_output;
@@ -42,13 +63,21 @@ pub mod plutus_map;
 pub mod plutus_list;
 pub mod transaction_metadatum_labels;
 pub mod transaction;
+pub mod fixed_transaction;
+pub mod protocol_parameters;
+pub mod message_signing;
 pub mod transaction_builder;
+pub mod transaction_builder_handle;
+pub mod slot_config;
 pub mod int;
 pub mod json_value;
 pub mod transaction_unspent_output;
 mod ptr;
+mod cbor;
 mod panic;
 mod array;
+#[cfg(test)]
+mod test_utils;
 
 #[no_mangle]
 pub unsafe extern "C" fn cardano_initialize() {
@@ -0,0 +1,596 @@
+use crate::address::address::Address;
+use crate::cbor::*;
+use crate::data::CData;
+use crate::error::CError;
+use crate::option::COption;
+use crate::panic::*;
+use crate::private_key::PrivateKey;
+use crate::ptr::*;
+use crate::public_key::PublicKey;
+use crate::secret_key::SecretKey;
+use cardano_serialization_lib::{
+  address::{
+    Address as RAddress, BaseAddress as RBaseAddress, EnterpriseAddress as REnterpriseAddress,
+    PointerAddress as RPointerAddress, RewardAddress as RRewardAddress,
+  },
+  crypto::{
+    Ed25519Signature as REd25519Signature, PrivateKey as RPrivateKey, PublicKey as RPublicKey,
+  },
+};
+use cbor_event::{de::Deserializer, se::Serializer, Len, Type};
+use cryptoxide::blake2b::Blake2b;
+use std::convert::{TryFrom, TryInto};
+use std::io::Cursor;
+
+// COSE labels and values used by CIP-8 and CIP-30
+const ALGORITHM_LABEL: i64 = 1;
+const EDDSA: i64 = -8;
+const KEY_TYPE_LABEL: i64 = 1;
+const OKP: i64 = 1;
+const KEY_ALGORITHM_LABEL: i64 = 3;
+const CURVE_LABEL: i64 = -1;
+const ED25519: i64 = 6;
+const X_LABEL: i64 = -2;
+const ADDRESS_LABEL: &str = "address";
+const HASHED_LABEL: &str = "hashed";
+const COSE_SIGN1_TAG: u64 = 18;
+const SIGNATURE1_CONTEXT: &str = "Signature1";
+
+// COSE header label, an integer or a text string
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum Label {
+  Int(i64),
+  Text(String),
+}
+
+fn read_label(de: &mut CborDeserializer) -> Result<Label> {
+  match de.cbor_type()? {
+    Type::UnsignedInteger => Ok(Label::Int(de.unsigned_integer()? as i64)),
+    Type::NegativeInteger => Ok(Label::Int(de.negative_integer()?)),
+    Type::Text => Ok(Label::Text(de.text()?)),
+    _ => Err("COSE label must be an integer or a text".into()),
+  }
+}
+
+fn write_int(se: &mut CborSerializer, value: i64) -> Result<()> {
+  if value < 0 {
+    se.write_negative_integer(value)?;
+  } else {
+    se.write_unsigned_integer(value as u64)?;
+  }
+  Ok(())
+}
+
+fn write_label(se: &mut CborSerializer, label: &Label) -> Result<()> {
+  match label {
+    Label::Int(value) => write_int(se, *value),
+    Label::Text(text) => se.write_text(text).map(|_| ()).into_result(),
+  }
+}
+
+// Entries of a COSE header map with their raw values
+fn header_entries(bytes: &[u8]) -> Result<Vec<(Label, Vec<u8>)>> {
+  let mut de = Deserializer::from(Cursor::new(bytes));
+  read_map(&mut de, |de| Ok((read_label(de)?, raw_item(de)?)))
+}
+
+fn encode_header(entries: Vec<(Label, Vec<u8>)>) -> Result<Vec<u8>> {
+  let mut se = Serializer::new_vec();
+  se.write_map(Len::Len(entries.len() as u64))?;
+  for (label, value) in entries {
+    write_label(&mut se, &label)?;
+    se.write_raw_bytes(&value)?;
+  }
+  Ok(se.finalize())
+}
+
+fn header_value(entries: &[(Label, Vec<u8>)], label: Label) -> Option<&[u8]> {
+  entries
+    .iter()
+    .find(|(key, _)| *key == label)
+    .map(|(_, value)| value.as_slice())
+}
+
+fn read_int(bytes: &[u8]) -> Result<i64> {
+  let mut de = Deserializer::from(Cursor::new(bytes));
+  match read_label(&mut de)? {
+    Label::Int(value) => Ok(value),
+    Label::Text(_) => Err("Expected an integer".into()),
+  }
+}
+
+fn read_bytes(bytes: &[u8]) -> Result<Vec<u8>> {
+  Deserializer::from(Cursor::new(bytes)).bytes().into_result()
+}
+
+fn encoded<F>(write: F) -> Result<Vec<u8>>
+where
+  F: FnOnce(&mut CborSerializer) -> Result<()>,
+{
+  let mut se = Serializer::new_vec();
+  write(&mut se)?;
+  Ok(se.finalize())
+}
+
+fn hash_payload(payload: &[u8]) -> Vec<u8> {
+  let mut hash = [0; 28];
+  Blake2b::blake2b(&mut hash, payload, &[]);
+  hash.to_vec()
+}
+
+// COSE_Sign1 of CIP-8. The protected header keeps the bytes it was signed
+// with, only the "hashed" flag of the unprotected header is kept.
+#[repr(C)]
+#[derive(Copy)]
+pub struct CoseSign1 {
+  protected: CData,
+  hashed: bool,
+  payload: COption<CData>,
+  signature: CData,
+}
+
+#[allow(clippy::non_canonical_clone_impl)]
+impl Clone for CoseSign1 {
+  fn clone(&self) -> Self {
+    let protected = unsafe { self.protected.unowned().expect("Bad bytes pointer").into() };
+    let payload: Option<CData> = self.payload.into();
+    let payload: Option<CData> =
+      payload.map(|payload| unsafe { payload.unowned().expect("Bad bytes pointer").into() });
+    let signature = unsafe { self.signature.unowned().expect("Bad bytes pointer").into() };
+    Self { protected, hashed: self.hashed, payload: payload.into(), signature }
+  }
+}
+
+impl Free for CoseSign1 {
+  unsafe fn free(&mut self) {
+    self.protected.free();
+    self.payload.free();
+    self.signature.free();
+  }
+}
+
+impl CoseSign1 {
+  // Signs the payload, or its Blake2b-224 hash, with the address in the
+  // protected header as CIP-30 signData does
+  pub fn sign(key: &RPrivateKey, address: &RAddress, payload: &[u8], hashed: bool) -> Result<Self> {
+    let protected = encode_header(vec![
+      (Label::Int(ALGORITHM_LABEL), encoded(|se| write_int(se, EDDSA))?),
+      (
+        Label::Text(ADDRESS_LABEL.to_string()),
+        encoded(|se| se.write_bytes(address.to_bytes()).map(|_| ()).into_result())?,
+      ),
+    ])?;
+    let payload = if hashed { hash_payload(payload) } else { payload.to_vec() };
+    let signature = key.sign(&sig_structure(&protected, &payload)?).to_bytes();
+    Ok(Self {
+      protected: protected.into(),
+      hashed,
+      payload: Some(CData::from(payload)).into(),
+      signature: signature.into(),
+    })
+  }
+
+  fn protected_entries(&self) -> Result<Vec<(Label, Vec<u8>)>> {
+    unsafe { self.protected.unowned() }.and_then(header_entries)
+  }
+
+  pub fn address(&self) -> Result<Option<RAddress>> {
+    let entries = self.protected_entries()?;
+    header_value(&entries, Label::Text(ADDRESS_LABEL.to_string()))
+      .map(|value| read_bytes(value).and_then(|bytes| RAddress::from_bytes(bytes).into_result()))
+      .transpose()
+  }
+
+  // Payload is the signed one, or the detached one when it isn't included.
+  // A payload given for a signature including one must match it.
+  pub fn verify(&self, key: &RPublicKey, payload: Option<&[u8]>) -> Result<bool> {
+    let entries = self.protected_entries()?;
+    match header_value(&entries, Label::Int(ALGORITHM_LABEL)).map(read_int) {
+      Some(Ok(EDDSA)) => {}
+      _ => return Err("Signature algorithm must be EdDSA".into()),
+    }
+    let expected = payload.map(|payload| {
+      if self.hashed {
+        hash_payload(payload)
+      } else {
+        payload.to_vec()
+      }
+    });
+    let included: Option<CData> = self.payload.into();
+    let signed = match (included, expected) {
+      (Some(included), Some(expected)) => {
+        if unsafe { included.unowned()? } != expected.as_slice() {
+          return Ok(false);
+        }
+        expected
+      }
+      (Some(included), None) => unsafe { included.unowned()? }.to_vec(),
+      (None, Some(expected)) => expected,
+      (None, None) => return Err("Detached payload must be given".into()),
+    };
+    let signature = unsafe { self.signature.unowned() }
+      .and_then(|bytes| REd25519Signature::from_bytes(bytes.to_vec()).into_result())?;
+    let protected = unsafe { self.protected.unowned()? };
+    Ok(key.verify(&sig_structure(protected, &signed)?, &signature))
+  }
+}
+
+// Sig_structure of RFC 8152 for a COSE_Sign1 without external data
+fn sig_structure(protected: &[u8], payload: &[u8]) -> Result<Vec<u8>> {
+  encoded(|se| {
+    se.write_array(Len::Len(4))?;
+    se.write_text(SIGNATURE1_CONTEXT)?;
+    se.write_bytes(protected)?;
+    se.write_bytes([])?;
+    se.write_bytes(payload)?;
+    Ok(())
+  })
+}
+
+impl CborSerialize for CoseSign1 {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    se.write_array(Len::Len(4))?;
+    se.write_bytes(unsafe { self.protected.unowned()? })?;
+    se.write_map(Len::Len(1))?;
+    se.write_text(HASHED_LABEL)?;
+    se.write_special(cbor_event::Special::Bool(self.hashed))?;
+    let payload: Option<CData> = self.payload.into();
+    write_nullable(se, payload, |se, payload| {
+      se.write_bytes(unsafe { payload.unowned()? })?;
+      Ok(())
+    })?;
+    se.write_bytes(unsafe { self.signature.unowned()? })?;
+    Ok(())
+  }
+}
+
+impl CborDeserialize for CoseSign1 {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    if de.cbor_type()? == Type::Tag && de.tag()? != COSE_SIGN1_TAG {
+      return Err("Wrong COSE_Sign1 tag".into());
+    }
+    let len = de.array()?;
+    if len != Len::Len(4) && len != Len::Indefinite {
+      return Err("COSE_Sign1 must have 4 items".into());
+    }
+    let protected = de.bytes()?;
+    header_entries(&protected)?;
+    let unprotected = read_map(de, |de| Ok((read_label(de)?, raw_item(de)?)))?;
+    let hashed = match header_value(&unprotected, Label::Text(HASHED_LABEL.to_string())) {
+      Some(value) => Deserializer::from(Cursor::new(value)).bool()?,
+      None => false,
+    };
+    let payload = read_nullable(de, |de| de.bytes().into_result())?;
+    let signature = de.bytes()?;
+    read_array_end(de, len)?;
+    Ok(Self {
+      protected: protected.into(),
+      hashed,
+      payload: payload.map(CData::from).into(),
+      signature: signature.into(),
+    })
+  }
+}
+
+// COSE_Key of an Ed25519 public key
+pub fn encode_cose_key(key: &RPublicKey) -> Result<Vec<u8>> {
+  encoded(|se| {
+    se.write_map(Len::Len(4))?;
+    let params = [(KEY_TYPE_LABEL, OKP), (KEY_ALGORITHM_LABEL, EDDSA), (CURVE_LABEL, ED25519)];
+    for (label, value) in params {
+      write_int(se, label)?;
+      write_int(se, value)?;
+    }
+    write_int(se, X_LABEL)?;
+    se.write_bytes(key.as_bytes())?;
+    Ok(())
+  })
+}
+
+pub fn decode_cose_key(bytes: &[u8]) -> Result<RPublicKey> {
+  let entries = header_entries(bytes)?;
+  let int = |label| header_value(&entries, Label::Int(label)).map(read_int).transpose();
+  if int(KEY_TYPE_LABEL)? != Some(OKP) || int(CURVE_LABEL)? != Some(ED25519) {
+    return Err("COSE_Key must be an Ed25519 OKP key".into());
+  }
+  if !matches!(int(KEY_ALGORITHM_LABEL)?, None | Some(EDDSA)) {
+    return Err("COSE_Key algorithm must be EdDSA".into());
+  }
+  header_value(&entries, Label::Int(X_LABEL))
+    .ok_or_else(|| CError::from("COSE_Key has no public key"))
+    .and_then(read_bytes)
+    .and_then(|bytes| RPublicKey::from_bytes(&bytes).into_result())
+}
+
+// Key hash of the payment credential, or the stake one of reward addresses
+fn address_key_hash(address: &RAddress) -> Option<Vec<u8>> {
+  RBaseAddress::from_address(address)
+    .map(|address| address.payment_cred())
+    .or_else(|| REnterpriseAddress::from_address(address).map(|address| address.payment_cred()))
+    .or_else(|| RPointerAddress::from_address(address).map(|address| address.payment_cred()))
+    .or_else(|| RRewardAddress::from_address(address).map(|address| address.payment_cred()))
+    .and_then(|credential| credential.to_keyhash())
+    .map(|hash| hash.to_bytes())
+}
+
+// CIP-30 DataSignature: the COSE_Sign1 and COSE_Key encodings
+#[repr(C)]
+#[derive(Copy)]
+pub struct DataSignature {
+  signature: CData,
+  key: CData,
+}
+
+#[allow(clippy::non_canonical_clone_impl)]
+impl Clone for DataSignature {
+  fn clone(&self) -> Self {
+    let signature = unsafe { self.signature.unowned().expect("Bad bytes pointer").into() };
+    let key = unsafe { self.key.unowned().expect("Bad bytes pointer").into() };
+    Self { signature, key }
+  }
+}
+
+impl Free for DataSignature {
+  unsafe fn free(&mut self) {
+    self.signature.free();
+    self.key.free();
+  }
+}
+
+impl DataSignature {
+  pub fn sign(key: &RPrivateKey, address: &RAddress, payload: &[u8], hashed: bool) -> Result<Self> {
+    let mut sign1 = CoseSign1::sign(key, address, payload, hashed)?;
+    let signature = sign1.to_cbor();
+    unsafe { sign1.free() };
+    let cose_key = encode_cose_key(&key.to_public())?;
+    Ok(Self { signature: signature?.into(), key: cose_key.into() })
+  }
+
+  // Valid when the address is the signed one, the key is the one of its
+  // credential and the signature is right
+  pub fn verify(&self, address: &RAddress, payload: Option<&[u8]>) -> Result<bool> {
+    let key = decode_cose_key(unsafe { self.key.unowned()? })?;
+    let mut sign1 = CoseSign1::from_cbor(unsafe { self.signature.unowned()? })?;
+    let verified = sign1.address().and_then(|signed| {
+      let key_hash = key.hash().to_bytes();
+      match signed {
+        Some(signed) if signed.to_bytes() == address.to_bytes() => {
+          if address_key_hash(address) == Some(key_hash) {
+            sign1.verify(&key, payload)
+          } else {
+            Ok(false)
+          }
+        }
+        _ => Ok(false),
+      }
+    });
+    unsafe { sign1.free() };
+    verified
+  }
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_data_signature_sign(
+  private_key: PrivateKey, address: Address, payload: CData, hashed: bool,
+  result: &mut DataSignature, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    let private_key: RPrivateKey = private_key.try_into()?;
+    let address = RAddress::try_from(address)?;
+    payload
+      .unowned()
+      .and_then(|payload| DataSignature::sign(&private_key, &address, payload, hashed))
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_data_signature_sign_with_secret_key(
+  key: *const SecretKey, address: Address, payload: CData, hashed: bool,
+  result: &mut DataSignature, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    let private_key = SecretKey::from_handle(key).and_then(|key| key.private_key())?;
+    let address = RAddress::try_from(address)?;
+    payload
+      .unowned()
+      .and_then(|payload| DataSignature::sign(&private_key, &address, payload, hashed))
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_data_signature_verify(
+  signature: DataSignature, address: Address, payload: COption<CData>, result: &mut bool,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    let address = RAddress::try_from(address)?;
+    let payload: Option<CData> = payload.into();
+    let payload = payload.as_ref().map(|payload| payload.unowned()).transpose()?;
+    signature.verify(&address, payload)
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc, clippy::clone_on_copy)]
+pub unsafe extern "C" fn cardano_data_signature_clone(
+  signature: DataSignature, result: &mut DataSignature, error: &mut CError,
+) -> bool {
+  handle_exception(|| signature.clone()).response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_data_signature_free(signature: &mut DataSignature) {
+  signature.free();
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_cose_sign1_from_bytes(
+  data: CData, result: &mut CoseSign1, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| data.unowned().and_then(CoseSign1::from_cbor))
+    .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_cose_sign1_to_bytes(
+  sign1: CoseSign1, result: &mut CData, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| sign1.to_cbor().map(|bytes| bytes.into())).response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_cose_sign1_address(
+  sign1: CoseSign1, result: &mut Address, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    sign1
+      .address()
+      .and_then(|address| address.ok_or_else(|| "COSE_Sign1 has no address".into()))
+      .and_then(|address| address.try_into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_cose_sign1_verify(
+  sign1: CoseSign1, public_key: PublicKey, payload: COption<CData>, result: &mut bool,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    let public_key: RPublicKey = public_key.try_into()?;
+    let payload: Option<CData> = payload.into();
+    let payload = payload.as_ref().map(|payload| payload.unowned()).transpose()?;
+    sign1.verify(&public_key, payload)
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc, clippy::clone_on_copy)]
+pub unsafe extern "C" fn cardano_cose_sign1_clone(
+  sign1: CoseSign1, result: &mut CoseSign1, error: &mut CError,
+) -> bool {
+  handle_exception(|| sign1.clone()).response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_cose_sign1_free(sign1: &mut CoseSign1) {
+  sign1.free();
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_cose_key_from_bytes(
+  data: CData, result: &mut PublicKey, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    data
+      .unowned()
+      .and_then(decode_cose_key)
+      .map(|public_key| public_key.into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_cose_key_to_bytes(
+  public_key: PublicKey, result: &mut CData, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    public_key
+      .try_into()
+      .and_then(|public_key: RPublicKey| encode_cose_key(&public_key))
+      .map(|bytes| bytes.into())
+  })
+  .response(result, error)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_utils::hex;
+  use cardano_serialization_lib::address::StakeCredential as RStakeCredential;
+
+  fn signer(byte: u8) -> (RPrivateKey, RAddress) {
+    let key = RPrivateKey::from_normal_bytes(&[byte; 32]).unwrap();
+    let credential = RStakeCredential::from_keyhash(&key.to_public().hash());
+    (key, REnterpriseAddress::new(0, &credential).to_address())
+  }
+
+  #[test]
+  fn cose_key_encodes_an_ed25519_okp_key() {
+    let key = signer(1).0.to_public();
+    let bytes = encode_cose_key(&key).unwrap();
+    let mut expected = hex("a4010103272006215820");
+    expected.extend(key.as_bytes());
+    assert_eq!(bytes, expected);
+    assert_eq!(decode_cose_key(&bytes).unwrap().as_bytes(), key.as_bytes());
+    // X25519 curve
+    let mut x25519 = hex("a4010103272004215820");
+    x25519.extend(key.as_bytes());
+    assert!(decode_cose_key(&x25519).is_err());
+  }
+
+  #[test]
+  fn signed_data_verifies_against_its_address() {
+    let (key, address) = signer(1);
+    for hashed in [false, true] {
+      let mut signature = DataSignature::sign(&key, &address, b"hello", hashed).unwrap();
+      assert!(signature.verify(&address, None).unwrap());
+      assert!(signature.verify(&address, Some(b"hello")).unwrap());
+      assert!(!signature.verify(&address, Some(b"hallo")).unwrap());
+      assert!(!signature.verify(&signer(2).1, None).unwrap());
+      unsafe { signature.free() };
+    }
+  }
+
+  #[test]
+  fn sign1_keeps_its_protected_header_through_cbor() {
+    let (key, address) = signer(1);
+    let mut sign1 = CoseSign1::sign(&key, &address, b"hello", false).unwrap();
+    let bytes = sign1.to_cbor().unwrap();
+    let mut protected = hex("a201276761646472657373581d");
+    protected.extend(address.to_bytes());
+    assert_eq!(unsafe { sign1.protected.unowned() }.unwrap(), protected.as_slice());
+    assert_eq!(bytes[0], 0x84);
+    // Tagged COSE_Sign1 and an unknown tag
+    let mut tagged = hex("d2");
+    tagged.extend(&bytes);
+    let mut decoded = CoseSign1::from_cbor(&tagged).unwrap();
+    assert_eq!(decoded.to_cbor().unwrap(), bytes);
+    assert_eq!(decoded.address().unwrap().unwrap().to_bytes(), address.to_bytes());
+    assert!(decoded.verify(&key.to_public(), None).unwrap());
+    assert!(!decoded.verify(&signer(2).0.to_public(), None).unwrap());
+    tagged[0] = 0xd1;
+    assert!(CoseSign1::from_cbor(&tagged).is_err());
+    unsafe {
+      sign1.free();
+      decoded.free();
+    }
+  }
+
+  #[test]
+  fn detached_payload_must_be_given() {
+    let (key, address) = signer(1);
+    let mut sign1 = CoseSign1::sign(&key, &address, b"hello", true).unwrap();
+    unsafe { sign1.payload.free() };
+    sign1.payload = COption::None;
+    assert!(sign1.verify(&key.to_public(), None).is_err());
+    assert!(sign1.verify(&key.to_public(), Some(b"hello")).unwrap());
+    assert!(!sign1.verify(&key.to_public(), Some(b"hallo")).unwrap());
+    unsafe { sign1.free() };
+  }
+}
@@ -63,7 +63,7 @@ impl TryFrom<MIRToStakeCredentials> for RMIRToStakeCredentials {
     let map = unsafe { mir_to_stake_credentials.rewards.as_hash_map()? };
     let mut mir_to_stake_credentials = Self::new();
     for (stake_credential, coin) in map {
-      mir_to_stake_credentials.insert(&stake_credential.into(), &coin.into());
+      mir_to_stake_credentials.insert(&stake_credential.into(), &coin.try_into()?);
     }
     Ok(mir_to_stake_credentials)
   }
@@ -80,8 +80,9 @@ impl TryFrom<RMIRToStakeCredentials> for MIRToStakeCredentials {
           mir_to_stake_credentials
             .get(&stake_credential)
             .ok_or("Cannot get DeltaCoin by StakeCredential".into())
+            .and_then(|delta_coin| delta_coin.try_into())
             .zip(stake_credential.try_into())
-            .map(|(delta_coin, stake_credential)| (stake_credential, delta_coin.into()).into())
+            .map(|(delta_coin, stake_credential)| (stake_credential, delta_coin).into())
         })
         .collect::<Result<Vec<CKeyValue<StakeCredential, CInt128>>>>()
         .map(|mir_to_stake_credentials| Self {
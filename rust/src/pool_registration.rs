@@ -1,5 +1,6 @@
 use crate::address::reward::RewardAddress;
 use crate::array::CArray;
+use crate::cbor::*;
 use crate::data::CData;
 use crate::error::CError;
 use crate::genesis_key_delegation::VRFKeyHash;
@@ -16,12 +17,18 @@ use cardano_serialization_lib::{DNSRecordAorAAAA as RDNSRecordAorAAAA, DNSRecord
 use std::convert::{TryFrom, TryInto};
 
 #[repr(C)]
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, PartialEq, Eq)]
 pub struct UnitInterval {
   numerator: BigNum,
   denominator: BigNum,
 }
 
+impl UnitInterval {
+  pub fn new(numerator: BigNum, denominator: BigNum) -> Self {
+    Self { numerator, denominator }
+  }
+}
+
 impl From<UnitInterval> for RUnitInterval {
   fn from(unit_interval: UnitInterval) -> Self {
     Self::new(
@@ -40,6 +47,22 @@ impl From<RUnitInterval> for UnitInterval {
   }
 }
 
+impl CborSerialize for UnitInterval {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    let unit_interval: RUnitInterval = (*self).into();
+    se.write_raw_bytes(&unit_interval.to_bytes())?;
+    Ok(())
+  }
+}
+
+impl CborDeserialize for UnitInterval {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    raw_item(de)
+      .and_then(|bytes| RUnitInterval::from_bytes(bytes).into_result())
+      .map(|unit_interval| unit_interval.into())
+  }
+}
+
 pub type Port = u16;
 
 #[repr(C)]
@@ -1,12 +1,12 @@
+use super::array::CArray;
 use super::data::CData;
 use super::ed25519_signature::Ed25519Signature;
 use super::error::CError;
 use super::panic::*;
 use super::ptr::*;
 use super::public_key::PublicKey;
-use cardano_serialization_lib::{
-  crypto::PrivateKey as RPrivateKey, impl_mockchain::key::EitherEd25519SecretKey,
-};
+use super::secret_key::wipe;
+use cardano_serialization_lib::crypto::PrivateKey as RPrivateKey;
 use std::convert::{TryFrom, TryInto};
 
 pub const EXTENDED_PRIVATE_KEY_LENGTH: usize = 64;
@@ -19,6 +19,26 @@ pub enum PrivateKey {
   Normal([u8; NORMAL_PRIVATE_KEY_LENGTH]),
 }
 
+// Layout the C header and the Swift wrapper are built for, on 64-bit targets
+const _: () = assert!(
+  std::mem::size_of::<PrivateKey>() == 68 && std::mem::align_of::<PrivateKey>() == 4
+);
+
+impl Free for PrivateKey {
+  unsafe fn free(&mut self) {}
+}
+
+pub type PrivateKeys = CArray<PrivateKey>;
+
+impl PrivateKey {
+  pub fn wipe(&mut self) {
+    match self {
+      PrivateKey::Extended(bytes) => wipe(bytes),
+      PrivateKey::Normal(bytes) => wipe(bytes),
+    }
+  }
+}
+
 impl TryFrom<PrivateKey> for RPrivateKey {
   type Error = CError;
 
@@ -30,17 +50,19 @@ impl TryFrom<PrivateKey> for RPrivateKey {
   }
 }
 
-// For transmutation
-struct TPrivateKey(EitherEd25519SecretKey);
+impl TryFrom<RPrivateKey> for PrivateKey {
+  type Error = CError;
 
-impl From<RPrivateKey> for PrivateKey {
-  fn from(private_key: RPrivateKey) -> Self {
-    let bytes = private_key.as_bytes();
-    let tpkey: TPrivateKey = unsafe { std::mem::transmute(private_key) };
-    match tpkey.0 {
-      EitherEd25519SecretKey::Extended(_) => PrivateKey::Extended(bytes.try_into().unwrap()),
-      EitherEd25519SecretKey::Normal(_) => PrivateKey::Normal(bytes.try_into().unwrap()),
-    }
+  fn try_from(private_key: RPrivateKey) -> Result<Self> {
+    // as_bytes returns the whole secret, so its length tells the key kind apart
+    let mut bytes = private_key.as_bytes();
+    let private_key = match bytes.len() {
+      EXTENDED_PRIVATE_KEY_LENGTH => bytes.as_slice().try_into().map(PrivateKey::Extended).ok(),
+      NORMAL_PRIVATE_KEY_LENGTH => bytes.as_slice().try_into().map(PrivateKey::Normal).ok(),
+      _ => None,
+    };
+    wipe(&mut bytes);
+    private_key.ok_or_else(|| "Private key has an unknown length".into())
   }
 }
 
@@ -76,7 +98,7 @@ pub unsafe extern "C" fn cardano_private_key_from_extended_bytes(
     data
       .unowned()
       .and_then(|bytes| RPrivateKey::from_extended_bytes(bytes).into_result())
-      .map(|private_key| private_key.into())
+      .and_then(|private_key| private_key.try_into())
   })
   .response(result, error)
 }
@@ -89,7 +111,7 @@ pub unsafe extern "C" fn cardano_private_key_from_normal_bytes(
     data
       .unowned()
       .and_then(|bytes| RPrivateKey::from_normal_bytes(bytes).into_result())
-      .map(|private_key| private_key.into())
+      .and_then(|private_key| private_key.try_into())
   })
   .response(result, error)
 }
@@ -107,3 +129,33 @@ pub unsafe extern "C" fn cardano_private_key_sign(
   })
   .response(result, error)
 }
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_private_key_wipe(private_key: &mut PrivateKey) {
+  private_key.wipe();
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn private_key_round_trips() {
+    let normal = RPrivateKey::from_normal_bytes(&[3; NORMAL_PRIVATE_KEY_LENGTH]).unwrap();
+    let private_key: PrivateKey = normal.try_into().unwrap();
+    assert!(matches!(private_key, PrivateKey::Normal(_)));
+    let back: RPrivateKey = private_key.try_into().unwrap();
+    assert_eq!(back.as_bytes(), vec![3; NORMAL_PRIVATE_KEY_LENGTH]);
+
+    let mut bytes = [3; EXTENDED_PRIVATE_KEY_LENGTH];
+    // clamped scalar of an extended key
+    bytes[0] &= 0xf8;
+    bytes[31] = (bytes[31] & 0x1f) | 0x40;
+    let extended = RPrivateKey::from_extended_bytes(&bytes).unwrap();
+    let private_key: PrivateKey = extended.try_into().unwrap();
+    assert!(matches!(private_key, PrivateKey::Extended(_)));
+    let back: RPrivateKey = private_key.try_into().unwrap();
+    assert_eq!(back.as_bytes(), bytes.to_vec());
+  }
+}
@@ -0,0 +1,120 @@
+use crate::address::reward::RewardAddress;
+use crate::anchor::Anchor;
+use crate::array::CArray;
+use crate::cbor::*;
+use crate::data::CData;
+use crate::error::CError;
+use crate::governance_action::GovernanceAction;
+use crate::linear_fee::Coin;
+use crate::panic::*;
+use crate::ptr::*;
+use cbor_event::Len;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct ProposalProcedure {
+  deposit: Coin,
+  reward_account: RewardAddress,
+  governance_action: GovernanceAction,
+  anchor: Anchor,
+}
+
+impl ProposalProcedure {
+  pub fn deposit(&self) -> Coin {
+    self.deposit
+  }
+}
+
+impl Free for ProposalProcedure {
+  unsafe fn free(&mut self) {
+    self.governance_action.free();
+    self.anchor.free();
+  }
+}
+
+impl CborSerialize for ProposalProcedure {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    se.write_array(Len::Len(4))?;
+    se.write_unsigned_integer(self.deposit)?;
+    self.reward_account.serialize(se)?;
+    self.governance_action.serialize(se)?;
+    self.anchor.serialize(se)
+  }
+}
+
+impl CborDeserialize for ProposalProcedure {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    let len = de.array()?;
+    if len != Len::Len(4) && len != Len::Indefinite {
+      return Err("Wrong ProposalProcedure array length".into());
+    }
+    let deposit = de.unsigned_integer()?;
+    let reward_account = RewardAddress::deserialize(de)?;
+    let governance_action = GovernanceAction::deserialize(de)?;
+    let anchor = Anchor::deserialize(de)?;
+    read_array_end(de, len)?;
+    Ok(Self {
+      deposit,
+      reward_account,
+      governance_action,
+      anchor,
+    })
+  }
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_proposal_procedure_to_bytes(
+  proposal_procedure: ProposalProcedure, result: &mut CData, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| proposal_procedure.to_cbor().map(|bytes| bytes.into()))
+    .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_proposal_procedure_from_bytes(
+  data: CData, result: &mut ProposalProcedure, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    data
+      .unowned()
+      .and_then(ProposalProcedure::from_cbor)
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_proposal_procedure_free(
+  proposal_procedure: &mut ProposalProcedure,
+) {
+  proposal_procedure.free()
+}
+
+pub type ProposalProcedures = CArray<ProposalProcedure>;
+
+impl CborSerialize for ProposalProcedures {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    let proposals = unsafe { self.unowned()? };
+    se.write_array(Len::Len(proposals.len() as u64))?;
+    for proposal in proposals {
+      proposal.serialize(se)?;
+    }
+    Ok(())
+  }
+}
+
+impl CborDeserialize for ProposalProcedures {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    read_array(de, ProposalProcedure::deserialize).map(|proposals| proposals.into())
+  }
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_proposal_procedures_free(
+  proposal_procedures: &mut ProposalProcedures,
+) {
+  proposal_procedures.free()
+}
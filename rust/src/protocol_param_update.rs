@@ -1,4 +1,5 @@
 use crate::array::*;
+use crate::cbor::*;
 use crate::data::CData;
 use crate::error::CError;
 use crate::int::CInt128;
@@ -19,6 +20,8 @@ use cardano_serialization_lib::{
   ProtocolParamUpdate as RProtocolParamUpdate, ProtocolVersion as RProtocolVersion,
   ProtocolVersions as RProtocolVersions,
 };
+use cbor_event::{Len, Special, Type};
+use std::collections::{BTreeMap, BTreeSet};
 use std::convert::{TryFrom, TryInto};
 
 pub type Rational = UnitInterval;
@@ -78,12 +81,18 @@ pub unsafe extern "C" fn cardano_nonce_to_bytes(
 }
 
 #[repr(C)]
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, PartialEq, Eq)]
 pub struct ProtocolVersion {
   major: u32,
   minor: u32,
 }
 
+impl ProtocolVersion {
+  pub fn new(major: u32, minor: u32) -> Self {
+    Self { major, minor }
+  }
+}
+
 impl Free for ProtocolVersion {
   unsafe fn free(&mut self) {}
 }
@@ -103,6 +112,28 @@ impl From<RProtocolVersion> for ProtocolVersion {
   }
 }
 
+impl CborSerialize for ProtocolVersion {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    se.write_array(Len::Len(2))?;
+    se.write_unsigned_integer(self.major.into())?;
+    se.write_unsigned_integer(self.minor.into())?;
+    Ok(())
+  }
+}
+
+impl CborDeserialize for ProtocolVersion {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    let len = de.array()?;
+    if len != Len::Len(2) && len != Len::Indefinite {
+      return Err("Wrong ProtocolVersion array length".into());
+    }
+    let major = de.unsigned_integer()?.try_into().map_err(|_| "Major version is too big")?;
+    let minor = de.unsigned_integer()?.try_into().map_err(|_| "Minor version is too big")?;
+    read_array_end(de, len)?;
+    Ok(Self { major, minor })
+  }
+}
+
 pub type ProtocolVersions = CArray<ProtocolVersion>;
 
 impl TryFrom<ProtocolVersions> for RProtocolVersions {
@@ -137,16 +168,21 @@ pub unsafe extern "C" fn cardano_protocol_versions_free(protocol_versions: &mut
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Language {
   PlutusV1,
+  PlutusV2,
+  PlutusV3,
 }
 
 impl Free for Language {
   unsafe fn free(&mut self) {}
 }
 
-impl From<Language> for RLanguage {
-  fn from(language: Language) -> Self {
+impl TryFrom<Language> for RLanguage {
+  type Error = CError;
+
+  fn try_from(language: Language) -> Result<Self> {
     match language {
-      Language::PlutusV1 => Self::new_plutus_v1(),
+      Language::PlutusV1 => Ok(Self::new_plutus_v1()),
+      _ => Err("Only Plutus V1 is supported here".into()),
     }
   }
 }
@@ -172,7 +208,11 @@ impl TryFrom<CostModel> for RCostModel {
       vec
         .iter()
         .enumerate()
-        .map(|(operation, &cost)| cost_model.set(operation, &cost.into()).into_result())
+        .map(|(operation, &cost)| {
+          cost
+            .try_into()
+            .and_then(|cost| cost_model.set(operation, &cost).into_result())
+        })
         .collect::<Result<Vec<_>>>()
         .map(|_| cost_model)
     })
@@ -187,8 +227,8 @@ impl TryFrom<RCostModel> for CostModel {
       .map(|operation| {
         cost_model
           .get(operation)
-          .map(|cost| cost.into())
           .into_result()
+          .and_then(|cost| cost.try_into())
       })
       .collect::<Result<Vec<CInt128>>>()
       .map(|cost_model| cost_model.into())
@@ -211,9 +251,10 @@ impl TryFrom<Costmdls> for RCostmdls {
       map
         .into_iter()
         .map(|(language, cost_model)| {
-          cost_model
+          language
             .try_into()
-            .map(|cost_model| costmdls.insert(&language.into(), &cost_model))
+            .zip(cost_model.try_into())
+            .map(|(language, cost_model)| costmdls.insert(&language, &cost_model))
         })
         .collect::<Result<Vec<_>>>()
         .map(|_| costmdls)
@@ -241,20 +282,93 @@ impl TryFrom<RCostmdls> for Costmdls {
   }
 }
 
+// Cost models of all languages, including the ones cardano-serialization-lib doesn't know
+impl TryFrom<Costmdls> for BTreeMap<Language, Vec<i128>> {
+  type Error = CError;
+
+  fn try_from(costmdls: Costmdls) -> Result<Self> {
+    let map = unsafe { costmdls.as_btree_map()? };
+    map
+      .into_iter()
+      .map(|(language, cost_model)| {
+        unsafe { cost_model.unowned() }
+          .map(|costs| (language, costs.iter().map(|&cost| cost.into()).collect()))
+      })
+      .collect()
+  }
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn cardano_costmdls_free(costmdls: &mut Costmdls) {
   costmdls.free();
 }
 
+fn write_costs(se: &mut CborSerializer, costs: &[i128]) -> Result<()> {
+  for &cost in costs {
+    match cost >= 0 {
+      true => se.write_unsigned_integer(cost.try_into().map_err(|_| "Cost is too big")?)?,
+      false => se.write_negative_integer(cost.try_into().map_err(|_| "Cost is too big")?)?,
+    };
+  }
+  Ok(())
+}
+
+// Language views part of the script data hash. Plutus V1 view keeps the ledger
+// quirks: its key and costs are wrapped in bytes and the costs list is indefinite.
+pub fn language_views(
+  cost_models: &BTreeMap<Language, Vec<i128>>, languages: &BTreeSet<Language>,
+) -> Result<Vec<u8>> {
+  let mut views = Vec::new();
+  for language in languages {
+    let costs = cost_models
+      .get(language)
+      .ok_or("Cost model of a script language is missing")?;
+    let mut key = CborSerializer::new_vec();
+    let mut value = CborSerializer::new_vec();
+    match language {
+      Language::PlutusV1 => {
+        let mut list = CborSerializer::new_vec();
+        list.write_array(Len::Indefinite)?;
+        write_costs(&mut list, costs)?;
+        list.write_special(Special::Break)?;
+        key.write_bytes([0])?;
+        value.write_bytes(list.finalize())?;
+      }
+      Language::PlutusV2 | Language::PlutusV3 => {
+        let id = if *language == Language::PlutusV2 { 1 } else { 2 };
+        key.write_unsigned_integer(id)?;
+        value.write_array(Len::Len(costs.len() as u64))?;
+        write_costs(&mut value, costs)?;
+      }
+    }
+    views.push((key.finalize(), value.finalize()));
+  }
+  // canonical order of the keys: shorter first, then bytewise
+  views.sort_by(|(a, _), (b, _)| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+  let mut se = CborSerializer::new_vec();
+  se.write_map(Len::Len(views.len() as u64))?;
+  for (key, value) in views {
+    se.write_raw_bytes(&key)?;
+    se.write_raw_bytes(&value)?;
+  }
+  Ok(se.finalize())
+}
+
 pub type SubCoin = UnitInterval;
 
 #[repr(C)]
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, PartialEq, Eq)]
 pub struct ExUnitPrices {
   mem_price: SubCoin,
   step_price: SubCoin,
 }
 
+impl ExUnitPrices {
+  pub fn new(mem_price: SubCoin, step_price: SubCoin) -> Self {
+    Self { mem_price, step_price }
+  }
+}
+
 impl From<ExUnitPrices> for RExUnitPrices {
   fn from(ex_unit_prices: ExUnitPrices) -> Self {
     Self::new(
@@ -274,12 +388,18 @@ impl From<RExUnitPrices> for ExUnitPrices {
 }
 
 #[repr(C)]
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, PartialEq, Eq)]
 pub struct ExUnits {
   mem: BigNum,
   steps: BigNum,
 }
 
+impl ExUnits {
+  pub fn new(mem: BigNum, steps: BigNum) -> Self {
+    Self { mem, steps }
+  }
+}
+
 impl From<ExUnits> for RExUnits {
   fn from(ex_units: ExUnits) -> Self {
     Self::new(&to_bignum(ex_units.mem), &to_bignum(ex_units.steps))
@@ -298,28 +418,28 @@ impl From<RExUnits> for ExUnits {
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub struct ProtocolParamUpdate {
-  minfee_a: COption<Coin>,
-  minfee_b: COption<Coin>,
-  max_block_body_size: COption<u32>,
-  max_tx_size: COption<u32>,
-  max_block_header_size: COption<u32>,
-  key_deposit: COption<Coin>,
-  pool_deposit: COption<Coin>,
-  max_epoch: COption<Epoch>,
-  n_opt: COption<u32>,
-  pool_pledge_influence: COption<Rational>,
-  expansion_rate: COption<UnitInterval>,
-  treasury_growth_rate: COption<UnitInterval>,
-  d: COption<UnitInterval>,
-  extra_entropy: COption<Nonce>,
-  protocol_version: COption<ProtocolVersions>,
-  min_pool_cost: COption<Coin>,
-  ada_per_utxo_byte: COption<Coin>,
-  cost_models: COption<Costmdls>,
-  execution_costs: COption<ExUnitPrices>,
-  max_tx_ex_units: COption<ExUnits>,
-  max_block_ex_units: COption<ExUnits>,
-  max_value_size: COption<u32>,
+  pub minfee_a: COption<Coin>,
+  pub minfee_b: COption<Coin>,
+  pub max_block_body_size: COption<u32>,
+  pub max_tx_size: COption<u32>,
+  pub max_block_header_size: COption<u32>,
+  pub key_deposit: COption<Coin>,
+  pub pool_deposit: COption<Coin>,
+  pub max_epoch: COption<Epoch>,
+  pub n_opt: COption<u32>,
+  pub pool_pledge_influence: COption<Rational>,
+  pub expansion_rate: COption<UnitInterval>,
+  pub treasury_growth_rate: COption<UnitInterval>,
+  pub d: COption<UnitInterval>,
+  pub extra_entropy: COption<Nonce>,
+  pub protocol_version: COption<ProtocolVersions>,
+  pub min_pool_cost: COption<Coin>,
+  pub ada_per_utxo_byte: COption<Coin>,
+  pub cost_models: COption<Costmdls>,
+  pub execution_costs: COption<ExUnitPrices>,
+  pub max_tx_ex_units: COption<ExUnits>,
+  pub max_block_ex_units: COption<ExUnits>,
+  pub max_value_size: COption<u32>,
 }
 
 impl Free for ProtocolParamUpdate {
@@ -469,3 +589,449 @@ pub unsafe extern "C" fn cardano_protocol_param_update_free(
 ) {
   protocol_param_update.free()
 }
+
+impl CborSerialize for ExUnitPrices {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    se.write_array(Len::Len(2))?;
+    self.mem_price.serialize(se)?;
+    self.step_price.serialize(se)
+  }
+}
+
+impl CborDeserialize for ExUnitPrices {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    let len = de.array()?;
+    if len != Len::Len(2) && len != Len::Indefinite {
+      return Err("Wrong ExUnitPrices array length".into());
+    }
+    let mem_price = SubCoin::deserialize(de)?;
+    let step_price = SubCoin::deserialize(de)?;
+    read_array_end(de, len)?;
+    Ok(Self { mem_price, step_price })
+  }
+}
+
+impl CborSerialize for ExUnits {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    se.write_array(Len::Len(2))?;
+    se.write_unsigned_integer(self.mem)?;
+    se.write_unsigned_integer(self.steps)?;
+    Ok(())
+  }
+}
+
+impl CborDeserialize for ExUnits {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    let len = de.array()?;
+    if len != Len::Len(2) && len != Len::Indefinite {
+      return Err("Wrong ExUnits array length".into());
+    }
+    let mem = de.unsigned_integer()?;
+    let steps = de.unsigned_integer()?;
+    read_array_end(de, len)?;
+    Ok(Self { mem, steps })
+  }
+}
+
+// Conway cost models: language ids 0, 1 and 2 mapped to definite lists of costs
+fn costmdls_to_cbor(costmdls: Costmdls) -> Result<Vec<u8>> {
+  let cost_models: BTreeMap<Language, Vec<i128>> = costmdls.try_into()?;
+  let mut se = CborSerializer::new_vec();
+  se.write_map(Len::Len(cost_models.len() as u64))?;
+  for (language, costs) in cost_models {
+    se.write_unsigned_integer(language as u64)?;
+    se.write_array(Len::Len(costs.len() as u64))?;
+    write_costs(&mut se, &costs)?;
+  }
+  Ok(se.finalize())
+}
+
+fn costmdls_from_cbor(bytes: &[u8]) -> Result<Costmdls> {
+  let entries = map_entries(bytes)?
+    .into_iter()
+    .map(|(language, costs)| {
+      let language = match language {
+        0 => Language::PlutusV1,
+        1 => Language::PlutusV2,
+        2 => Language::PlutusV3,
+        language => return Err(CError::from(format!("Unknown cost model language {}", language))),
+      };
+      let mut de = CborDeserializer::from(std::io::Cursor::new(costs.as_slice()));
+      let costs = read_array(&mut de, |de| match de.cbor_type()? {
+        Type::UnsignedInteger => Ok(CInt128::from(de.unsigned_integer()? as i128)),
+        _ => Ok(CInt128::from(de.negative_integer()? as i128)),
+      })?;
+      Ok((language, costs.into()).into())
+    })
+    .collect::<Result<Vec<CKeyValue<Language, CostModel>>>>()?;
+  Ok(entries.into())
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct PoolVotingThresholds {
+  motion_no_confidence: UnitInterval,
+  committee_normal: UnitInterval,
+  committee_no_confidence: UnitInterval,
+  hard_fork_initiation: UnitInterval,
+  security_relevant_parameter: UnitInterval,
+}
+
+impl CborSerialize for PoolVotingThresholds {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    se.write_array(Len::Len(5))?;
+    self.motion_no_confidence.serialize(se)?;
+    self.committee_normal.serialize(se)?;
+    self.committee_no_confidence.serialize(se)?;
+    self.hard_fork_initiation.serialize(se)?;
+    self.security_relevant_parameter.serialize(se)
+  }
+}
+
+impl CborDeserialize for PoolVotingThresholds {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    let len = de.array()?;
+    if len != Len::Len(5) && len != Len::Indefinite {
+      return Err("Wrong PoolVotingThresholds array length".into());
+    }
+    let thresholds = Self {
+      motion_no_confidence: UnitInterval::deserialize(de)?,
+      committee_normal: UnitInterval::deserialize(de)?,
+      committee_no_confidence: UnitInterval::deserialize(de)?,
+      hard_fork_initiation: UnitInterval::deserialize(de)?,
+      security_relevant_parameter: UnitInterval::deserialize(de)?,
+    };
+    read_array_end(de, len)?;
+    Ok(thresholds)
+  }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct DRepVotingThresholds {
+  motion_no_confidence: UnitInterval,
+  committee_normal: UnitInterval,
+  committee_no_confidence: UnitInterval,
+  update_constitution: UnitInterval,
+  hard_fork_initiation: UnitInterval,
+  pp_network_group: UnitInterval,
+  pp_economic_group: UnitInterval,
+  pp_technical_group: UnitInterval,
+  pp_governance_group: UnitInterval,
+  treasury_withdrawal: UnitInterval,
+}
+
+impl CborSerialize for DRepVotingThresholds {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    se.write_array(Len::Len(10))?;
+    self.motion_no_confidence.serialize(se)?;
+    self.committee_normal.serialize(se)?;
+    self.committee_no_confidence.serialize(se)?;
+    self.update_constitution.serialize(se)?;
+    self.hard_fork_initiation.serialize(se)?;
+    self.pp_network_group.serialize(se)?;
+    self.pp_economic_group.serialize(se)?;
+    self.pp_technical_group.serialize(se)?;
+    self.pp_governance_group.serialize(se)?;
+    self.treasury_withdrawal.serialize(se)
+  }
+}
+
+impl CborDeserialize for DRepVotingThresholds {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    let len = de.array()?;
+    if len != Len::Len(10) && len != Len::Indefinite {
+      return Err("Wrong DRepVotingThresholds array length".into());
+    }
+    let thresholds = Self {
+      motion_no_confidence: UnitInterval::deserialize(de)?,
+      committee_normal: UnitInterval::deserialize(de)?,
+      committee_no_confidence: UnitInterval::deserialize(de)?,
+      update_constitution: UnitInterval::deserialize(de)?,
+      hard_fork_initiation: UnitInterval::deserialize(de)?,
+      pp_network_group: UnitInterval::deserialize(de)?,
+      pp_economic_group: UnitInterval::deserialize(de)?,
+      pp_technical_group: UnitInterval::deserialize(de)?,
+      pp_governance_group: UnitInterval::deserialize(de)?,
+      treasury_withdrawal: UnitInterval::deserialize(de)?,
+    };
+    read_array_end(de, len)?;
+    Ok(thresholds)
+  }
+}
+
+// Parameter update of a Conway ParameterChangeAction. The decentralisation
+// parameter, extra entropy and protocol version (keys 12, 13 and 14) are gone,
+// the version is changed by HardForkInitiationAction instead.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct ConwayProtocolParamUpdate {
+  pub minfee_a: COption<Coin>,
+  pub minfee_b: COption<Coin>,
+  pub max_block_body_size: COption<u32>,
+  pub max_tx_size: COption<u32>,
+  pub max_block_header_size: COption<u32>,
+  pub key_deposit: COption<Coin>,
+  pub pool_deposit: COption<Coin>,
+  pub max_epoch: COption<Epoch>,
+  pub n_opt: COption<u32>,
+  pub pool_pledge_influence: COption<Rational>,
+  pub expansion_rate: COption<UnitInterval>,
+  pub treasury_growth_rate: COption<UnitInterval>,
+  pub min_pool_cost: COption<Coin>,
+  pub ada_per_utxo_byte: COption<Coin>,
+  pub cost_models: COption<Costmdls>,
+  pub execution_costs: COption<ExUnitPrices>,
+  pub max_tx_ex_units: COption<ExUnits>,
+  pub max_block_ex_units: COption<ExUnits>,
+  pub max_value_size: COption<u32>,
+  pub collateral_percentage: COption<u32>,
+  pub max_collateral_inputs: COption<u32>,
+  pub pool_voting_thresholds: COption<PoolVotingThresholds>,
+  pub drep_voting_thresholds: COption<DRepVotingThresholds>,
+  pub min_committee_size: COption<u32>,
+  pub committee_term_limit: COption<Epoch>,
+  pub governance_action_validity_period: COption<Epoch>,
+  pub governance_action_deposit: COption<Coin>,
+  pub drep_deposit: COption<Coin>,
+  pub drep_inactivity_period: COption<Epoch>,
+  pub min_fee_ref_script_cost_per_byte: COption<SubCoin>,
+}
+
+impl Free for ConwayProtocolParamUpdate {
+  unsafe fn free(&mut self) {
+    self.cost_models.free()
+  }
+}
+
+fn push_entry<T>(
+  entries: &mut Vec<(u64, Vec<u8>)>, key: u64, value: COption<T>,
+  encode: impl FnOnce(T) -> Result<Vec<u8>>,
+) -> Result<()> {
+  if let COption::Some(value) = value {
+    entries.push((key, encode(value)?));
+  }
+  Ok(())
+}
+
+fn uint_to_cbor<T: Into<u64>>(value: T) -> Result<Vec<u8>> {
+  value.into().to_cbor()
+}
+
+fn uint_from_cbor<T: TryFrom<u64>>(bytes: &[u8]) -> Result<COption<T>> {
+  u64::from_cbor(bytes)?
+    .try_into()
+    .map(COption::Some)
+    .map_err(|_| "Protocol parameter is too big".into())
+}
+
+impl CborSerialize for ConwayProtocolParamUpdate {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    let mut entries = Vec::new();
+    push_entry(&mut entries, 0, self.minfee_a, uint_to_cbor)?;
+    push_entry(&mut entries, 1, self.minfee_b, uint_to_cbor)?;
+    push_entry(&mut entries, 2, self.max_block_body_size, uint_to_cbor)?;
+    push_entry(&mut entries, 3, self.max_tx_size, uint_to_cbor)?;
+    push_entry(&mut entries, 4, self.max_block_header_size, uint_to_cbor)?;
+    push_entry(&mut entries, 5, self.key_deposit, uint_to_cbor)?;
+    push_entry(&mut entries, 6, self.pool_deposit, uint_to_cbor)?;
+    push_entry(&mut entries, 7, self.max_epoch, uint_to_cbor)?;
+    push_entry(&mut entries, 8, self.n_opt, uint_to_cbor)?;
+    push_entry(&mut entries, 9, self.pool_pledge_influence, |ppi| ppi.to_cbor())?;
+    push_entry(&mut entries, 10, self.expansion_rate, |er| er.to_cbor())?;
+    push_entry(&mut entries, 11, self.treasury_growth_rate, |tgr| tgr.to_cbor())?;
+    push_entry(&mut entries, 16, self.min_pool_cost, uint_to_cbor)?;
+    push_entry(&mut entries, 17, self.ada_per_utxo_byte, uint_to_cbor)?;
+    push_entry(&mut entries, 18, self.cost_models, costmdls_to_cbor)?;
+    push_entry(&mut entries, 19, self.execution_costs, |ec| ec.to_cbor())?;
+    push_entry(&mut entries, 20, self.max_tx_ex_units, |mteu| mteu.to_cbor())?;
+    push_entry(&mut entries, 21, self.max_block_ex_units, |mbeu| mbeu.to_cbor())?;
+    push_entry(&mut entries, 22, self.max_value_size, uint_to_cbor)?;
+    push_entry(&mut entries, 23, self.collateral_percentage, uint_to_cbor)?;
+    push_entry(&mut entries, 24, self.max_collateral_inputs, uint_to_cbor)?;
+    push_entry(&mut entries, 25, self.pool_voting_thresholds, |pvt| pvt.to_cbor())?;
+    push_entry(&mut entries, 26, self.drep_voting_thresholds, |dvt| dvt.to_cbor())?;
+    push_entry(&mut entries, 27, self.min_committee_size, uint_to_cbor)?;
+    push_entry(&mut entries, 28, self.committee_term_limit, uint_to_cbor)?;
+    push_entry(&mut entries, 29, self.governance_action_validity_period, uint_to_cbor)?;
+    push_entry(&mut entries, 30, self.governance_action_deposit, uint_to_cbor)?;
+    push_entry(&mut entries, 31, self.drep_deposit, uint_to_cbor)?;
+    push_entry(&mut entries, 32, self.drep_inactivity_period, uint_to_cbor)?;
+    push_entry(&mut entries, 33, self.min_fee_ref_script_cost_per_byte, |mfr| mfr.to_cbor())?;
+    if entries.is_empty() {
+      return Err("ProtocolParamUpdate has to change at least one parameter".into());
+    }
+    se.write_raw_bytes(&encode_map(entries)?)?;
+    Ok(())
+  }
+}
+
+impl CborDeserialize for ConwayProtocolParamUpdate {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    let mut update = Self {
+      minfee_a: COption::None,
+      minfee_b: COption::None,
+      max_block_body_size: COption::None,
+      max_tx_size: COption::None,
+      max_block_header_size: COption::None,
+      key_deposit: COption::None,
+      pool_deposit: COption::None,
+      max_epoch: COption::None,
+      n_opt: COption::None,
+      pool_pledge_influence: COption::None,
+      expansion_rate: COption::None,
+      treasury_growth_rate: COption::None,
+      min_pool_cost: COption::None,
+      ada_per_utxo_byte: COption::None,
+      cost_models: COption::None,
+      execution_costs: COption::None,
+      max_tx_ex_units: COption::None,
+      max_block_ex_units: COption::None,
+      max_value_size: COption::None,
+      collateral_percentage: COption::None,
+      max_collateral_inputs: COption::None,
+      pool_voting_thresholds: COption::None,
+      drep_voting_thresholds: COption::None,
+      min_committee_size: COption::None,
+      committee_term_limit: COption::None,
+      governance_action_validity_period: COption::None,
+      governance_action_deposit: COption::None,
+      drep_deposit: COption::None,
+      drep_inactivity_period: COption::None,
+      min_fee_ref_script_cost_per_byte: COption::None,
+    };
+    let entries = map_entries(&raw_item(de)?)?;
+    if entries.is_empty() {
+      return Err("ProtocolParamUpdate has to change at least one parameter".into());
+    }
+    let mut keys = BTreeSet::new();
+    for (key, value) in entries {
+      let set = match keys.insert(key) {
+        true => update.set(key, &value),
+        false => Err(format!("Duplicate protocol parameter {}", key).into()),
+      };
+      if let Err(error) = set {
+        unsafe { update.free() };
+        return Err(error);
+      }
+    }
+    Ok(update)
+  }
+}
+
+impl ConwayProtocolParamUpdate {
+  // Sets the parameter of the map key to its encoded value
+  fn set(&mut self, key: u64, value: &[u8]) -> Result<()> {
+    match key {
+      0 => self.minfee_a = uint_from_cbor(value)?,
+      1 => self.minfee_b = uint_from_cbor(value)?,
+      2 => self.max_block_body_size = uint_from_cbor(value)?,
+      3 => self.max_tx_size = uint_from_cbor(value)?,
+      4 => self.max_block_header_size = uint_from_cbor(value)?,
+      5 => self.key_deposit = uint_from_cbor(value)?,
+      6 => self.pool_deposit = uint_from_cbor(value)?,
+      7 => self.max_epoch = uint_from_cbor(value)?,
+      8 => self.n_opt = uint_from_cbor(value)?,
+      9 => self.pool_pledge_influence = COption::Some(Rational::from_cbor(value)?),
+      10 => self.expansion_rate = COption::Some(UnitInterval::from_cbor(value)?),
+      11 => self.treasury_growth_rate = COption::Some(UnitInterval::from_cbor(value)?),
+      16 => self.min_pool_cost = uint_from_cbor(value)?,
+      17 => self.ada_per_utxo_byte = uint_from_cbor(value)?,
+      18 => self.cost_models = COption::Some(costmdls_from_cbor(value)?),
+      19 => self.execution_costs = COption::Some(ExUnitPrices::from_cbor(value)?),
+      20 => self.max_tx_ex_units = COption::Some(ExUnits::from_cbor(value)?),
+      21 => self.max_block_ex_units = COption::Some(ExUnits::from_cbor(value)?),
+      22 => self.max_value_size = uint_from_cbor(value)?,
+      23 => self.collateral_percentage = uint_from_cbor(value)?,
+      24 => self.max_collateral_inputs = uint_from_cbor(value)?,
+      25 => self.pool_voting_thresholds = COption::Some(PoolVotingThresholds::from_cbor(value)?),
+      26 => self.drep_voting_thresholds = COption::Some(DRepVotingThresholds::from_cbor(value)?),
+      27 => self.min_committee_size = uint_from_cbor(value)?,
+      28 => self.committee_term_limit = uint_from_cbor(value)?,
+      29 => self.governance_action_validity_period = uint_from_cbor(value)?,
+      30 => self.governance_action_deposit = uint_from_cbor(value)?,
+      31 => self.drep_deposit = uint_from_cbor(value)?,
+      32 => self.drep_inactivity_period = uint_from_cbor(value)?,
+      33 => self.min_fee_ref_script_cost_per_byte = COption::Some(SubCoin::from_cbor(value)?),
+      key => return Err(format!("Unknown Conway protocol parameter {}", key).into()),
+    }
+    Ok(())
+  }
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_conway_protocol_param_update_from_bytes(
+  data: CData, result: &mut ConwayProtocolParamUpdate, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    data
+      .unowned()
+      .and_then(ConwayProtocolParamUpdate::from_cbor)
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_conway_protocol_param_update_to_bytes(
+  protocol_param_update: ConwayProtocolParamUpdate, result: &mut CData, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| protocol_param_update.to_cbor().map(|bytes| bytes.into()))
+    .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_conway_protocol_param_update_clone(
+  protocol_param_update: ConwayProtocolParamUpdate, result: &mut ConwayProtocolParamUpdate,
+  error: &mut CError,
+) -> bool {
+  // Cost models are copied through the encoding, a plain copy would share them
+  handle_exception_result(|| {
+    protocol_param_update
+      .to_cbor()
+      .and_then(|bytes| ConwayProtocolParamUpdate::from_cbor(&bytes))
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_conway_protocol_param_update_free(
+  protocol_param_update: &mut ConwayProtocolParamUpdate,
+) {
+  protocol_param_update.free()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_utils::hex;
+
+  #[test]
+  fn conway_update_round_trips() {
+    // min fee a, governance action deposit and reference script price
+    let bytes = hex("a300182c181e1b000000174876e8001821d81e820f01");
+    let update = ConwayProtocolParamUpdate::from_cbor(&bytes).unwrap();
+    assert!(matches!(update.minfee_a, COption::Some(44)));
+    assert!(matches!(update.governance_action_deposit, COption::Some(100000000000)));
+    assert_eq!(update.to_cbor().unwrap(), bytes);
+    let mut update = ConwayProtocolParamUpdate::from_cbor(&hex("a112a100820121")).unwrap();
+    let cost_models: Option<Costmdls> = update.cost_models.into();
+    let cost_models: BTreeMap<Language, Vec<i128>> = cost_models.unwrap().try_into().unwrap();
+    assert_eq!(cost_models[&Language::PlutusV1], vec![1, -2]);
+    assert_eq!(update.to_cbor().unwrap(), hex("a112a100820121"));
+    unsafe { update.free() };
+  }
+
+  #[test]
+  fn removed_parameters_are_rejected() {
+    // decentralisation, extra entropy and protocol version
+    for bytes in ["a10cd81e820001", "a10d8100", "a10e820900"] {
+      assert!(ConwayProtocolParamUpdate::from_cbor(&hex(bytes)).is_err());
+    }
+    assert!(ConwayProtocolParamUpdate::from_cbor(&hex("a0")).is_err());
+    assert!(ConwayProtocolParamUpdate::from_cbor(&hex("a200010002")).is_err());
+  }
+}
@@ -0,0 +1,991 @@
+use crate::array::CArray;
+use crate::error::CError;
+use crate::linear_fee::Coin;
+use crate::option::COption;
+use crate::panic::*;
+use crate::pool_registration::UnitInterval;
+use crate::protocol_param_update::{
+  ConwayProtocolParamUpdate, Costmdls, ExUnitPrices, ExUnits, Language, ProtocolParamUpdate,
+  ProtocolVersion, ProtocolVersions, Rational, SubCoin,
+};
+use crate::ptr::*;
+use crate::string::CharPtr;
+use crate::transaction_body::Epoch;
+use crate::transaction_builder::TransactionBuilderConfig;
+use cardano_serialization_lib::{
+  plutus::{ExUnitPrices as RExUnitPrices, ExUnits as RExUnits},
+  utils::from_bignum,
+  ProtocolVersion as RProtocolVersion, UnitInterval as RUnitInterval,
+};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::convert::{TryFrom, TryInto};
+
+// Protocol parameters of the current epoch
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct ProtocolParameters {
+  min_fee_a: Coin,
+  min_fee_b: Coin,
+  max_block_body_size: u32,
+  max_tx_size: u32,
+  max_block_header_size: u32,
+  key_deposit: Coin,
+  pool_deposit: Coin,
+  max_epoch: Epoch,
+  n_opt: u32,
+  pool_pledge_influence: Rational,
+  expansion_rate: UnitInterval,
+  treasury_growth_rate: UnitInterval,
+  protocol_version: ProtocolVersion,
+  min_pool_cost: Coin,
+  coins_per_utxo_byte: Coin,
+  cost_models: Costmdls,
+  execution_costs: ExUnitPrices,
+  max_tx_ex_units: ExUnits,
+  max_block_ex_units: ExUnits,
+  max_value_size: u32,
+  collateral_percentage: u32,
+  max_collateral_inputs: u32,
+  min_fee_ref_script_cost_per_byte: SubCoin,
+}
+
+impl Free for ProtocolParameters {
+  unsafe fn free(&mut self) {
+    self.cost_models.free()
+  }
+}
+
+impl ProtocolParameters {
+  pub fn min_fee_a(&self) -> Coin {
+    self.min_fee_a
+  }
+
+  pub fn min_fee_b(&self) -> Coin {
+    self.min_fee_b
+  }
+
+  pub fn max_block_body_size(&self) -> u32 {
+    self.max_block_body_size
+  }
+
+  pub fn max_tx_size(&self) -> u32 {
+    self.max_tx_size
+  }
+
+  pub fn max_block_header_size(&self) -> u32 {
+    self.max_block_header_size
+  }
+
+  pub fn key_deposit(&self) -> Coin {
+    self.key_deposit
+  }
+
+  pub fn pool_deposit(&self) -> Coin {
+    self.pool_deposit
+  }
+
+  pub fn max_epoch(&self) -> Epoch {
+    self.max_epoch
+  }
+
+  pub fn n_opt(&self) -> u32 {
+    self.n_opt
+  }
+
+  pub fn pool_pledge_influence(&self) -> Rational {
+    self.pool_pledge_influence
+  }
+
+  pub fn expansion_rate(&self) -> UnitInterval {
+    self.expansion_rate
+  }
+
+  pub fn treasury_growth_rate(&self) -> UnitInterval {
+    self.treasury_growth_rate
+  }
+
+  pub fn protocol_version(&self) -> ProtocolVersion {
+    self.protocol_version
+  }
+
+  pub fn min_pool_cost(&self) -> Coin {
+    self.min_pool_cost
+  }
+
+  pub fn coins_per_utxo_byte(&self) -> Coin {
+    self.coins_per_utxo_byte
+  }
+
+  // Copy of the cost models by language
+  pub fn cost_models(&self) -> Result<BTreeMap<Language, Vec<i128>>> {
+    self.cost_models.try_into()
+  }
+
+  pub fn execution_costs(&self) -> ExUnitPrices {
+    self.execution_costs
+  }
+
+  pub fn max_tx_ex_units(&self) -> ExUnits {
+    self.max_tx_ex_units
+  }
+
+  pub fn max_block_ex_units(&self) -> ExUnits {
+    self.max_block_ex_units
+  }
+
+  pub fn max_value_size(&self) -> u32 {
+    self.max_value_size
+  }
+
+  pub fn collateral_percentage(&self) -> u32 {
+    self.collateral_percentage
+  }
+
+  pub fn max_collateral_inputs(&self) -> u32 {
+    self.max_collateral_inputs
+  }
+
+  pub fn min_fee_ref_script_cost_per_byte(&self) -> SubCoin {
+    self.min_fee_ref_script_cost_per_byte
+  }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub enum ProtocolParametersJsonFormat {
+  CardanoCli,
+  Blockfrost,
+  Koios,
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+  if b == 0 {
+    a
+  } else {
+    gcd(b, a % b)
+  }
+}
+
+// Exact fraction of a decimal number like "0.0577" or "7.21e-5"
+fn decimal_to_rational(text: &str) -> Option<UnitInterval> {
+  let (mantissa, exponent) = match text.find(&['e', 'E'][..]) {
+    Some(index) => (&text[..index], text[index + 1..].parse::<i32>().ok()?),
+    None => (text, 0),
+  };
+  let (integer, fraction) = match mantissa.find('.') {
+    Some(index) => (&mantissa[..index], &mantissa[index + 1..]),
+    None => (mantissa, ""),
+  };
+  let digits = format!("{}{}", integer, fraction);
+  if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+    return None;
+  }
+  let mut numerator: u64 = digits.parse().ok()?;
+  let mut denominator: u64 = 1;
+  let scale = fraction.len() as i32 - exponent;
+  for _ in 0..scale.abs() {
+    if scale > 0 {
+      denominator = denominator.checked_mul(10)?;
+    } else {
+      numerator = numerator.checked_mul(10)?;
+    }
+  }
+  let divisor = gcd(numerator, denominator);
+  Some(UnitInterval::new(numerator / divisor, denominator / divisor))
+}
+
+fn json_u64(value: &Value) -> Option<u64> {
+  match value {
+    Value::Number(number) => number.as_u64(),
+    Value::String(text) => text.parse().ok(),
+    _ => None,
+  }
+}
+
+fn json_i128(value: &Value) -> Option<i128> {
+  match value {
+    Value::Number(number) => number.as_i64().map(|cost| cost.into()),
+    Value::String(text) => text.parse().ok(),
+    _ => None,
+  }
+}
+
+// Decimal number or string, or an object with numerator and denominator
+fn json_rational(value: &Value) -> Option<UnitInterval> {
+  match value {
+    Value::Number(number) => decimal_to_rational(&number.to_string()),
+    Value::String(text) => decimal_to_rational(text),
+    Value::Object(object) => {
+      let numerator = object.get("numerator").and_then(json_u64)?;
+      let denominator = object.get("denominator").and_then(json_u64)?;
+      Some(UnitInterval::new(numerator, denominator))
+    }
+    _ => None,
+  }
+}
+
+fn json_language(name: &str) -> Option<Language> {
+  match name {
+    "PlutusV1" | "PlutusScriptV1" => Some(Language::PlutusV1),
+    "PlutusV2" | "PlutusScriptV2" => Some(Language::PlutusV2),
+    "PlutusV3" | "PlutusScriptV3" => Some(Language::PlutusV3),
+    _ => None,
+  }
+}
+
+// Cost models keyed by language, each one a list of costs in the ledger order.
+// Maps of named costs are rejected: the ledger order of the names isn't the
+// alphabetical one. Languages this library doesn't know are skipped.
+fn json_cost_models(value: &Value) -> Result<BTreeMap<Language, Vec<i128>>> {
+  // Some indexers give the cost models as a JSON encoded string
+  let value = match value {
+    Value::String(text) => serde_json::from_str(text)?,
+    value => value.clone(),
+  };
+  let models = value.as_object().ok_or("Cost models must be a JSON object")?;
+  models
+    .iter()
+    .filter_map(|(name, costs)| json_language(name).map(|language| (name, language, costs)))
+    .map(|(name, language, costs)| {
+      let costs = match costs {
+        Value::Array(costs) => costs.iter().map(json_i128).collect::<Option<Vec<i128>>>(),
+        Value::Object(_) => {
+          return Err(format!("Cost model of {} has named costs, a list is needed", name).into())
+        }
+        _ => None,
+      };
+      costs
+        .map(|costs| (language, costs))
+        .ok_or_else(|| format!("Bad cost model of {}", name).into())
+    })
+    .collect()
+}
+
+// Fields of a protocol parameters JSON object
+struct JsonParams<'a>(&'a Map<String, Value>);
+
+impl<'a> JsonParams<'a> {
+  fn value(&self, key: &str) -> Option<&'a Value> {
+    self.0.get(key).filter(|value| !value.is_null())
+  }
+
+  fn field<T>(&self, key: &str, read: fn(&Value) -> Option<T>) -> Result<T> {
+    let value = self
+      .value(key)
+      .ok_or_else(|| CError::from(format!("Protocol parameter {} is missing", key)))?;
+    read(value).ok_or_else(|| format!("Protocol parameter {} is malformed", key).into())
+  }
+
+  fn u64(&self, key: &str) -> Result<u64> {
+    self.field(key, json_u64)
+  }
+
+  fn u32(&self, key: &str) -> Result<u32> {
+    self.field(key, |value| json_u64(value).and_then(|value| value.try_into().ok()))
+  }
+
+  fn rational(&self, key: &str) -> Result<UnitInterval> {
+    self.field(key, json_rational)
+  }
+
+  fn object(&self, key: &str) -> Result<JsonParams<'a>> {
+    self
+      .value(key)
+      .and_then(|value| value.as_object())
+      .map(JsonParams)
+      .ok_or_else(|| format!("Protocol parameter {} is missing", key).into())
+  }
+
+  fn ex_units(&self, mem: &str, steps: &str) -> Result<ExUnits> {
+    Ok(ExUnits::new(self.u64(mem)?, self.u64(steps)?))
+  }
+
+  fn cost_models(&self, key: &str) -> Result<BTreeMap<Language, Vec<i128>>> {
+    self
+      .value(key)
+      .ok_or_else(|| format!("Protocol parameter {} is missing", key).into())
+      .and_then(json_cost_models)
+  }
+
+  // Reference script fee exists since Conway only
+  fn ref_script_fee(&self, key: &str) -> Result<SubCoin> {
+    match self.value(key) {
+      Some(_) => self.rational(key),
+      None => Ok(UnitInterval::new(0, 1)),
+    }
+  }
+
+  // Cost per byte, or per word of eight bytes before Babbage
+  fn coins_per_utxo_byte(&self, byte_key: &str, word_key: &str) -> Result<Coin> {
+    match self.value(byte_key) {
+      Some(_) => self.u64(byte_key),
+      None => self.u64(word_key).map(|coins_per_word| coins_per_word / 8),
+    }
+  }
+}
+
+// Keys of the snake case formats of the indexers, where they differ
+struct IndexerKeys {
+  max_block_header_size: &'static str,
+  max_epoch: &'static str,
+  n_opt: &'static str,
+  pool_pledge_influence: &'static str,
+  expansion_rate: &'static str,
+  treasury_growth_rate: &'static str,
+  protocol_major: &'static str,
+  protocol_minor: &'static str,
+  cost_models: &'static [&'static str],
+}
+
+const BLOCKFROST_KEYS: IndexerKeys = IndexerKeys {
+  max_block_header_size: "max_block_header_size",
+  max_epoch: "e_max",
+  n_opt: "n_opt",
+  pool_pledge_influence: "a0",
+  expansion_rate: "rho",
+  treasury_growth_rate: "tau",
+  protocol_major: "protocol_major_ver",
+  protocol_minor: "protocol_minor_ver",
+  // Raw cost models are lists. The other ones are maps of named costs,
+  // which are looked up only to tell they can't be read.
+  cost_models: &["cost_models_raw", "cost_models"],
+};
+
+const KOIOS_KEYS: IndexerKeys = IndexerKeys {
+  max_block_header_size: "max_bh_size",
+  max_epoch: "max_epoch",
+  n_opt: "optimal_pool_count",
+  pool_pledge_influence: "influence",
+  expansion_rate: "monetary_expand_rate",
+  treasury_growth_rate: "treasury_growth_rate",
+  protocol_major: "protocol_major",
+  protocol_minor: "protocol_minor",
+  cost_models: &["cost_models"],
+};
+
+impl ProtocolParameters {
+  pub fn from_json(json: &str, format: ProtocolParametersJsonFormat) -> Result<Self> {
+    let value: Value = serde_json::from_str(json)?;
+    // Koios gives a list of epochs, the first one is used
+    let value = match (format, value) {
+      (ProtocolParametersJsonFormat::Koios, Value::Array(epochs)) => {
+        epochs.into_iter().next().ok_or("No epoch in protocol parameters")?
+      }
+      (_, value) => value,
+    };
+    let params = JsonParams(value.as_object().ok_or("Protocol parameters must be a JSON object")?);
+    match format {
+      ProtocolParametersJsonFormat::CardanoCli => Self::from_cli_json(&params),
+      ProtocolParametersJsonFormat::Blockfrost => {
+        Self::from_indexer_json(&params, &BLOCKFROST_KEYS)
+      }
+      ProtocolParametersJsonFormat::Koios => Self::from_indexer_json(&params, &KOIOS_KEYS),
+    }
+  }
+
+  // Output of cardano-cli query protocol-parameters
+  fn from_cli_json(params: &JsonParams) -> Result<Self> {
+    let protocol_version = params.object("protocolVersion")?;
+    let prices = params.object("executionUnitPrices")?;
+    let max_tx_ex_units = params.object("maxTxExecutionUnits")?;
+    let max_block_ex_units = params.object("maxBlockExecutionUnits")?;
+    let cost_models = params.cost_models("costModels")?;
+    Ok(Self {
+      min_fee_a: params.u64("txFeePerByte")?,
+      min_fee_b: params.u64("txFeeFixed")?,
+      max_block_body_size: params.u32("maxBlockBodySize")?,
+      max_tx_size: params.u32("maxTxSize")?,
+      max_block_header_size: params.u32("maxBlockHeaderSize")?,
+      key_deposit: params.u64("stakeAddressDeposit")?,
+      pool_deposit: params.u64("stakePoolDeposit")?,
+      max_epoch: params.u32("poolRetireMaxEpoch")?,
+      n_opt: params.u32("stakePoolTargetNum")?,
+      pool_pledge_influence: params.rational("poolPledgeInfluence")?,
+      expansion_rate: params.rational("monetaryExpansion")?,
+      treasury_growth_rate: params.rational("treasuryCut")?,
+      protocol_version: ProtocolVersion::new(
+        protocol_version.u32("major")?,
+        protocol_version.u32("minor")?,
+      ),
+      min_pool_cost: params.u64("minPoolCost")?,
+      coins_per_utxo_byte: params.coins_per_utxo_byte("utxoCostPerByte", "utxoCostPerWord")?,
+      execution_costs: ExUnitPrices::new(
+        prices.rational("priceMemory")?,
+        prices.rational("priceSteps")?,
+      ),
+      max_tx_ex_units: max_tx_ex_units.ex_units("memory", "steps")?,
+      max_block_ex_units: max_block_ex_units.ex_units("memory", "steps")?,
+      max_value_size: params.u32("maxValueSize")?,
+      collateral_percentage: params.u32("collateralPercentage")?,
+      max_collateral_inputs: params.u32("maxCollateralInputs")?,
+      min_fee_ref_script_cost_per_byte: params.ref_script_fee("minFeeRefScriptCostPerByte")?,
+      cost_models: cost_models.into(),
+    })
+  }
+
+  // Epoch parameters of Blockfrost and Koios
+  fn from_indexer_json(params: &JsonParams, keys: &IndexerKeys) -> Result<Self> {
+    let cost_models = keys
+      .cost_models
+      .iter()
+      .find(|key| params.value(key).is_some())
+      .map(|key| params.cost_models(key))
+      .unwrap_or_else(|| Err("Protocol parameter cost_models is missing".into()))?;
+    Ok(Self {
+      min_fee_a: params.u64("min_fee_a")?,
+      min_fee_b: params.u64("min_fee_b")?,
+      max_block_body_size: params.u32("max_block_size")?,
+      max_tx_size: params.u32("max_tx_size")?,
+      max_block_header_size: params.u32(keys.max_block_header_size)?,
+      key_deposit: params.u64("key_deposit")?,
+      pool_deposit: params.u64("pool_deposit")?,
+      max_epoch: params.u32(keys.max_epoch)?,
+      n_opt: params.u32(keys.n_opt)?,
+      pool_pledge_influence: params.rational(keys.pool_pledge_influence)?,
+      expansion_rate: params.rational(keys.expansion_rate)?,
+      treasury_growth_rate: params.rational(keys.treasury_growth_rate)?,
+      protocol_version: ProtocolVersion::new(
+        params.u32(keys.protocol_major)?,
+        params.u32(keys.protocol_minor)?,
+      ),
+      min_pool_cost: params.u64("min_pool_cost")?,
+      coins_per_utxo_byte: params
+        .coins_per_utxo_byte("coins_per_utxo_size", "coins_per_utxo_word")?,
+      execution_costs: ExUnitPrices::new(
+        params.rational("price_mem")?,
+        params.rational("price_step")?,
+      ),
+      max_tx_ex_units: params.ex_units("max_tx_ex_mem", "max_tx_ex_steps")?,
+      max_block_ex_units: params.ex_units("max_block_ex_mem", "max_block_ex_steps")?,
+      max_value_size: params.u32("max_val_size")?,
+      collateral_percentage: params.u32("collateral_percent")?,
+      max_collateral_inputs: params.u32("max_collateral_inputs")?,
+      min_fee_ref_script_cost_per_byte: params.ref_script_fee("min_fee_ref_script_cost_per_byte")?,
+      cost_models: cost_models.into(),
+    })
+  }
+
+  // Copy owning its own cost models
+  pub fn try_clone(&self) -> Result<Self> {
+    BTreeMap::<Language, Vec<i128>>::try_from(self.cost_models).map(|cost_models| Self {
+      cost_models: cost_models.into(),
+      ..*self
+    })
+  }
+}
+
+// Parameters an update proposal can change
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum ProtocolParameterField {
+  MinFeeAField,
+  MinFeeBField,
+  MaxBlockBodySizeField,
+  MaxTxSizeField,
+  MaxBlockHeaderSizeField,
+  KeyDepositField,
+  PoolDepositField,
+  MaxEpochField,
+  NOptField,
+  PoolPledgeInfluenceField,
+  ExpansionRateField,
+  TreasuryGrowthRateField,
+  ProtocolVersionField,
+  MinPoolCostField,
+  CoinsPerUtxoByteField,
+  CostModelsField,
+  ExecutionCostsField,
+  MaxTxExUnitsField,
+  MaxBlockExUnitsField,
+  MaxValueSizeField,
+  CollateralPercentageField,
+  MaxCollateralInputsField,
+  MinFeeRefScriptCostPerByteField,
+}
+
+impl Free for ProtocolParameterField {
+  unsafe fn free(&mut self) {}
+}
+
+pub type ProtocolParameterFields = CArray<ProtocolParameterField>;
+
+// Parameters with an update applied and the fields whose value changed
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct ProtocolParametersUpdate {
+  parameters: ProtocolParameters,
+  changed: ProtocolParameterFields,
+}
+
+impl Free for ProtocolParametersUpdate {
+  unsafe fn free(&mut self) {
+    self.parameters.free();
+    self.changed.free();
+  }
+}
+
+fn denominator(value: UnitInterval) -> u64 {
+  from_bignum(&RUnitInterval::from(value).denominator())
+}
+
+fn is_unit_interval(value: UnitInterval) -> bool {
+  let value: RUnitInterval = value.into();
+  let denominator = from_bignum(&value.denominator());
+  denominator != 0 && from_bignum(&value.numerator()) <= denominator
+}
+
+fn check_update<T: Copy>(name: &str, value: COption<T>, is_valid: fn(T) -> bool) -> Result<()> {
+  let value: Option<T> = value.into();
+  match value {
+    Some(value) if !is_valid(value) => {
+      Err(format!("Protocol parameter {} is out of range", name).into())
+    }
+    _ => Ok(()),
+  }
+}
+
+// Major version goes up with the minor one reset, or the minor one goes up
+fn can_follow(current: ProtocolVersion, next: ProtocolVersion) -> bool {
+  let current: RProtocolVersion = current.into();
+  let next: RProtocolVersion = next.into();
+  (next.major() == current.major() && Some(next.minor()) == current.minor().checked_add(1))
+    || (Some(next.major()) == current.major().checked_add(1) && next.minor() == 0)
+}
+
+fn update_field<T: PartialEq>(
+  changed: &mut Vec<ProtocolParameterField>, field: ProtocolParameterField, current: &mut T,
+  value: COption<T>,
+) {
+  let value: Option<T> = value.into();
+  if let Some(value) = value {
+    if *current != value {
+      *current = value;
+      changed.push(field);
+    }
+  }
+}
+
+// Fields a Conway update shares with the legacy one. The protocol version
+// changes with a hard fork action instead.
+fn legacy_update(update: &ConwayProtocolParamUpdate) -> ProtocolParamUpdate {
+  ProtocolParamUpdate {
+    minfee_a: update.minfee_a,
+    minfee_b: update.minfee_b,
+    max_block_body_size: update.max_block_body_size,
+    max_tx_size: update.max_tx_size,
+    max_block_header_size: update.max_block_header_size,
+    key_deposit: update.key_deposit,
+    pool_deposit: update.pool_deposit,
+    max_epoch: update.max_epoch,
+    n_opt: update.n_opt,
+    pool_pledge_influence: update.pool_pledge_influence,
+    expansion_rate: update.expansion_rate,
+    treasury_growth_rate: update.treasury_growth_rate,
+    d: COption::None,
+    extra_entropy: COption::None,
+    protocol_version: COption::None,
+    min_pool_cost: update.min_pool_cost,
+    ada_per_utxo_byte: update.ada_per_utxo_byte,
+    cost_models: update.cost_models,
+    execution_costs: update.execution_costs,
+    max_tx_ex_units: update.max_tx_ex_units,
+    max_block_ex_units: update.max_block_ex_units,
+    max_value_size: update.max_value_size,
+  }
+}
+
+impl ProtocolParameters {
+  // Decentralisation and extra entropy are gone since Babbage,
+  // so they are only validated
+  pub fn apply_update(&self, update: &ProtocolParamUpdate) -> Result<ProtocolParametersUpdate> {
+    self.apply(update, None)
+  }
+
+  // Update of a parameter change action, with the collateral and
+  // reference script parameters the legacy update doesn't have
+  pub fn apply_conway_update(
+    &self, update: &ConwayProtocolParamUpdate,
+  ) -> Result<ProtocolParametersUpdate> {
+    self.apply(&legacy_update(update), Some(update))
+  }
+
+  fn apply(
+    &self, update: &ProtocolParamUpdate, conway: Option<&ConwayProtocolParamUpdate>,
+  ) -> Result<ProtocolParametersUpdate> {
+    if let Some(conway) = conway {
+      check_update("collateral_percentage", conway.collateral_percentage, |percentage| {
+        percentage != 0
+      })?;
+      check_update(
+        "min_fee_ref_script_cost_per_byte",
+        conway.min_fee_ref_script_cost_per_byte,
+        |price| denominator(price) != 0,
+      )?;
+    }
+    check_update("max_block_body_size", update.max_block_body_size, |size| size != 0)?;
+    check_update("max_tx_size", update.max_tx_size, |size| size != 0)?;
+    check_update("max_block_header_size", update.max_block_header_size, |size| size != 0)?;
+    check_update("max_value_size", update.max_value_size, |size| size != 0)?;
+    check_update("pool_deposit", update.pool_deposit, |deposit| deposit != 0)?;
+    check_update("n_opt", update.n_opt, |n_opt| n_opt != 0)?;
+    check_update("ada_per_utxo_byte", update.ada_per_utxo_byte, |coins| coins != 0)?;
+    check_update("pool_pledge_influence", update.pool_pledge_influence, |influence| {
+      denominator(influence) != 0
+    })?;
+    check_update("expansion_rate", update.expansion_rate, is_unit_interval)?;
+    check_update("treasury_growth_rate", update.treasury_growth_rate, is_unit_interval)?;
+    check_update("d", update.d, is_unit_interval)?;
+    check_update("execution_costs", update.execution_costs, |prices| {
+      let prices: RExUnitPrices = prices.into();
+      denominator(prices.mem_price().into()) != 0 && denominator(prices.step_price().into()) != 0
+    })?;
+
+    let protocol_versions: Option<ProtocolVersions> = update.protocol_version.into();
+    let protocol_version = match protocol_versions {
+      Some(protocol_versions) => unsafe { protocol_versions.unowned()? }.first().copied(),
+      None => None,
+    };
+    if let Some(protocol_version) = protocol_version {
+      if protocol_version != self.protocol_version
+        && !can_follow(self.protocol_version, protocol_version)
+      {
+        return Err("Protocol version can't follow the current one".into());
+      }
+    }
+
+    // Models of the languages in the update replace the current ones
+    let current_cost_models = BTreeMap::<Language, Vec<i128>>::try_from(self.cost_models)?;
+    let mut cost_models = current_cost_models.clone();
+    let update_cost_models: Option<Costmdls> = update.cost_models.into();
+    if let Some(update_cost_models) = update_cost_models {
+      cost_models.extend(BTreeMap::<Language, Vec<i128>>::try_from(update_cost_models)?);
+    }
+
+    let mut params = *self;
+    let mut changed = Vec::new();
+    use ProtocolParameterField::*;
+    update_field(&mut changed, MinFeeAField, &mut params.min_fee_a, update.minfee_a);
+    update_field(&mut changed, MinFeeBField, &mut params.min_fee_b, update.minfee_b);
+    update_field(
+      &mut changed,
+      MaxBlockBodySizeField,
+      &mut params.max_block_body_size,
+      update.max_block_body_size,
+    );
+    update_field(&mut changed, MaxTxSizeField, &mut params.max_tx_size, update.max_tx_size);
+    update_field(
+      &mut changed,
+      MaxBlockHeaderSizeField,
+      &mut params.max_block_header_size,
+      update.max_block_header_size,
+    );
+    update_field(&mut changed, KeyDepositField, &mut params.key_deposit, update.key_deposit);
+    update_field(&mut changed, PoolDepositField, &mut params.pool_deposit, update.pool_deposit);
+    update_field(&mut changed, MaxEpochField, &mut params.max_epoch, update.max_epoch);
+    update_field(&mut changed, NOptField, &mut params.n_opt, update.n_opt);
+    update_field(
+      &mut changed,
+      PoolPledgeInfluenceField,
+      &mut params.pool_pledge_influence,
+      update.pool_pledge_influence,
+    );
+    update_field(
+      &mut changed,
+      ExpansionRateField,
+      &mut params.expansion_rate,
+      update.expansion_rate,
+    );
+    update_field(
+      &mut changed,
+      TreasuryGrowthRateField,
+      &mut params.treasury_growth_rate,
+      update.treasury_growth_rate,
+    );
+    update_field(
+      &mut changed,
+      ProtocolVersionField,
+      &mut params.protocol_version,
+      protocol_version.into(),
+    );
+    update_field(&mut changed, MinPoolCostField, &mut params.min_pool_cost, update.min_pool_cost);
+    update_field(
+      &mut changed,
+      CoinsPerUtxoByteField,
+      &mut params.coins_per_utxo_byte,
+      update.ada_per_utxo_byte,
+    );
+    if cost_models != current_cost_models {
+      changed.push(CostModelsField);
+    }
+    update_field(
+      &mut changed,
+      ExecutionCostsField,
+      &mut params.execution_costs,
+      update.execution_costs,
+    );
+    update_field(
+      &mut changed,
+      MaxTxExUnitsField,
+      &mut params.max_tx_ex_units,
+      update.max_tx_ex_units,
+    );
+    update_field(
+      &mut changed,
+      MaxBlockExUnitsField,
+      &mut params.max_block_ex_units,
+      update.max_block_ex_units,
+    );
+    update_field(
+      &mut changed,
+      MaxValueSizeField,
+      &mut params.max_value_size,
+      update.max_value_size,
+    );
+    if let Some(conway) = conway {
+      update_field(
+        &mut changed,
+        CollateralPercentageField,
+        &mut params.collateral_percentage,
+        conway.collateral_percentage,
+      );
+      update_field(
+        &mut changed,
+        MaxCollateralInputsField,
+        &mut params.max_collateral_inputs,
+        conway.max_collateral_inputs,
+      );
+      update_field(
+        &mut changed,
+        MinFeeRefScriptCostPerByteField,
+        &mut params.min_fee_ref_script_cost_per_byte,
+        conway.min_fee_ref_script_cost_per_byte,
+      );
+    }
+
+    // Whatever fits a block has to fit a transaction
+    if params.max_tx_size > params.max_block_body_size {
+      return Err("Max transaction size exceeds max block body size".into());
+    }
+    let max_tx_ex_units: RExUnits = params.max_tx_ex_units.into();
+    let max_block_ex_units: RExUnits = params.max_block_ex_units.into();
+    if max_tx_ex_units.mem() > max_block_ex_units.mem()
+      || max_tx_ex_units.steps() > max_block_ex_units.steps()
+    {
+      return Err("Max transaction execution units exceed max block ones".into());
+    }
+
+    params.cost_models = cost_models.into();
+    Ok(ProtocolParametersUpdate {
+      parameters: params,
+      changed: changed.into(),
+    })
+  }
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_protocol_parameters_from_json(
+  json: CharPtr, format: ProtocolParametersJsonFormat, result: &mut ProtocolParameters,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    json
+      .unowned()
+      .and_then(|json| ProtocolParameters::from_json(json, format))
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_protocol_parameters_to_transaction_builder_config(
+  params: ProtocolParameters, prefer_pure_change: bool, result: &mut TransactionBuilderConfig,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderConfig::from_protocol_parameters(&params, prefer_pure_change)
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_protocol_parameters_clone(
+  params: ProtocolParameters, result: &mut ProtocolParameters, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| params.try_clone()).response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_protocol_parameters_free(params: &mut ProtocolParameters) {
+  params.free()
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_protocol_parameters_apply_update(
+  params: ProtocolParameters, update: ProtocolParamUpdate, result: &mut ProtocolParametersUpdate,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| params.apply_update(&update)).response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_protocol_parameters_apply_conway_update(
+  params: ProtocolParameters, update: ConwayProtocolParamUpdate,
+  result: &mut ProtocolParametersUpdate, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| params.apply_conway_update(&update)).response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_protocol_parameters_update_free(
+  update: &mut ProtocolParametersUpdate,
+) {
+  update.free()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::cbor::CborDeserialize;
+  use crate::test_utils::hex;
+  use cardano_serialization_lib::{
+    utils::to_bignum, ProtocolParamUpdate as RProtocolParamUpdate,
+    ProtocolVersions as RProtocolVersions,
+  };
+
+  // Preprod parameters of epoch 150 as cardano-cli prints them, cost models cut short
+  const CLI_JSON: &str = r#"{
+    "collateralPercentage": 150,
+    "costModels": { "PlutusV1": [205665, 812, 1], "PlutusV2": [205665, 812, 1, 1] },
+    "executionUnitPrices": { "priceMemory": 0.0577, "priceSteps": 7.21e-5 },
+    "maxBlockBodySize": 90112,
+    "maxBlockExecutionUnits": { "memory": 62000000, "steps": 20000000000 },
+    "maxBlockHeaderSize": 1100,
+    "maxCollateralInputs": 3,
+    "maxTxExecutionUnits": { "memory": 14000000, "steps": 10000000000 },
+    "maxTxSize": 16384,
+    "maxValueSize": 5000,
+    "minPoolCost": 170000000,
+    "monetaryExpansion": 3.0e-3,
+    "poolPledgeInfluence": 0.3,
+    "poolRetireMaxEpoch": 18,
+    "protocolVersion": { "major": 9, "minor": 0 },
+    "stakeAddressDeposit": 2000000,
+    "stakePoolDeposit": 500000000,
+    "stakePoolTargetNum": 500,
+    "treasuryCut": 0.2,
+    "txFeeFixed": 155381,
+    "txFeePerByte": 44,
+    "utxoCostPerByte": 4310
+  }"#;
+
+  // Blockfrost epoch parameters with the cost models given by the test
+  fn blockfrost_json(cost_models: &str) -> String {
+    format!(
+      r#"{{
+        "min_fee_a": 44, "min_fee_b": 155381, "max_block_size": 90112, "max_tx_size": 16384,
+        "max_block_header_size": 1100, "key_deposit": "2000000", "pool_deposit": "500000000",
+        "e_max": 18, "n_opt": 500, "a0": 0.3, "rho": 0.003, "tau": 0.2,
+        "protocol_major_ver": 9, "protocol_minor_ver": 0, "min_pool_cost": "170000000",
+        "coins_per_utxo_size": "4310", "price_mem": 0.0577, "price_step": 0.0000721,
+        "max_tx_ex_mem": "14000000", "max_tx_ex_steps": "10000000000",
+        "max_block_ex_mem": "62000000", "max_block_ex_steps": "20000000000",
+        "max_val_size": "5000", "collateral_percent": 150, "max_collateral_inputs": 3,
+        {}
+      }}"#,
+      cost_models
+    )
+  }
+
+  fn params() -> ProtocolParameters {
+    ProtocolParameters::from_json(CLI_JSON, ProtocolParametersJsonFormat::CardanoCli).unwrap()
+  }
+
+  fn versions(major: u32, minor: u32) -> RProtocolVersions {
+    let mut versions = RProtocolVersions::new();
+    versions.add(&RProtocolVersion::new(major, minor));
+    versions
+  }
+
+  fn changed(update: &ProtocolParametersUpdate) -> Vec<u32> {
+    let changed = unsafe { update.changed.unowned().unwrap() };
+    changed.iter().map(|&field| field as u32).collect()
+  }
+
+  #[test]
+  fn update_reports_changed_fields() {
+    let mut update = RProtocolParamUpdate::new();
+    update.set_minfee_a(&to_bignum(45));
+    update.set_max_tx_size(16384);
+    update.set_protocol_version(&versions(10, 0));
+    let update: ProtocolParamUpdate = update.try_into().unwrap();
+    let updated = params().apply_update(&update).unwrap();
+    assert_eq!(updated.parameters.min_fee_a, 45);
+    assert!(updated.parameters.protocol_version == ProtocolVersion::new(10, 0));
+    let fields = [
+      ProtocolParameterField::MinFeeAField,
+      ProtocolParameterField::ProtocolVersionField,
+    ];
+    assert_eq!(changed(&updated), fields.map(|field| field as u32));
+  }
+
+  #[test]
+  fn update_rejects_version_jump_and_bad_sizes() {
+    let mut update = RProtocolParamUpdate::new();
+    update.set_protocol_version(&versions(11, 0));
+    let update: ProtocolParamUpdate = update.try_into().unwrap();
+    assert!(params().apply_update(&update).is_err());
+
+    let mut update = RProtocolParamUpdate::new();
+    update.set_max_tx_size(100_000);
+    let update: ProtocolParamUpdate = update.try_into().unwrap();
+    assert!(params().apply_update(&update).is_err());
+  }
+
+  #[test]
+  fn conway_update_changes_collateral_and_ref_script_fields() {
+    // Fee per byte 45, collateral 160%, 4 collateral inputs, reference scripts at 15 per byte
+    let update = hex("a400182d1718a01818041821d81e820f01");
+    let update = ConwayProtocolParamUpdate::from_cbor(&update).unwrap();
+    let updated = params().apply_conway_update(&update).unwrap();
+    assert_eq!(updated.parameters.min_fee_a, 45);
+    assert_eq!(updated.parameters.collateral_percentage, 160);
+    assert_eq!(updated.parameters.max_collateral_inputs, 4);
+    assert!(updated.parameters.min_fee_ref_script_cost_per_byte == SubCoin::new(15, 1));
+    let fields = [
+      ProtocolParameterField::MinFeeAField,
+      ProtocolParameterField::CollateralPercentageField,
+      ProtocolParameterField::MaxCollateralInputsField,
+      ProtocolParameterField::MinFeeRefScriptCostPerByteField,
+    ];
+    assert_eq!(changed(&updated), fields.map(|field| field as u32));
+
+    // Collateral percentage of zero and a reference script price over zero
+    for bytes in ["a11700", "a11821d81e820f00"] {
+      let update = ConwayProtocolParamUpdate::from_cbor(&hex(bytes)).unwrap();
+      assert!(params().apply_conway_update(&update).is_err());
+    }
+  }
+
+  #[test]
+  fn cost_models_are_read_from_lists_only() {
+    let named = r#""cost_models": { "PlutusV1": { "b-cpu": 2, "a-cpu": 1 } }"#;
+    let json = blockfrost_json(&format!(
+      r#"{}, "cost_models_raw": {{ "PlutusV1": [3, 1, 2] }}"#,
+      named
+    ));
+    let params = ProtocolParameters::from_json(&json, ProtocolParametersJsonFormat::Blockfrost)
+      .unwrap();
+    assert_eq!(params.cost_models().unwrap()[&Language::PlutusV1], vec![3, 1, 2]);
+    assert_eq!(params.coins_per_utxo_byte(), 4310);
+    let json = blockfrost_json(named);
+    let params = ProtocolParameters::from_json(&json, ProtocolParametersJsonFormat::Blockfrost);
+    assert!(params.is_err());
+  }
+}
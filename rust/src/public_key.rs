@@ -1,11 +1,14 @@
 use super::data::CData;
+use super::ed25519_signature::Ed25519Signature;
 use super::error::CError;
 use super::panic::*;
 use super::ptr::*;
 use super::stake_credential::Ed25519KeyHash;
 use super::string::CharPtr;
 use super::string::IntoCString;
-use cardano_serialization_lib::crypto::PublicKey as RPublicKey;
+use cardano_serialization_lib::crypto::{
+  Ed25519Signature as REd25519Signature, PublicKey as RPublicKey,
+};
 use std::convert::{TryFrom, TryInto};
 
 pub const PUBLIC_KEY_LENGTH: usize = 32;
@@ -82,3 +85,20 @@ pub unsafe extern "C" fn cardano_public_key_hash(
       .and_then(|public_key: RPublicKey| public_key.hash().try_into())
   }).response(result, error)
 }
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_public_key_verify(
+  public_key: PublicKey, data: CData, signature: Ed25519Signature, result: &mut bool,
+  error: &mut CError
+) -> bool {
+  handle_exception_result(|| {
+    public_key
+      .try_into()
+      .zip(signature.try_into())
+      .zip(data.unowned())
+      .map(|((public_key, signature), data): ((RPublicKey, REd25519Signature), &[u8])| {
+        public_key.verify(data, &signature)
+      })
+  }).response(result, error)
+}
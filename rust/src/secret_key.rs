@@ -0,0 +1,375 @@
+use crate::bip32_private_key::{Bip32PrivateKey, DerivationPath};
+use crate::bip32_public_key::Bip32PublicKey;
+use crate::bip39::Bip39Wordlist;
+use crate::data::CData;
+use crate::ed25519_signature::Ed25519Signature;
+use crate::error::CError;
+use crate::key_encryption::{decrypt_with_password, encrypt_with_password, KeyEncryptionFormat};
+use crate::panic::*;
+use crate::private_key::{PrivateKey, EXTENDED_PRIVATE_KEY_LENGTH, NORMAL_PRIVATE_KEY_LENGTH};
+use crate::ptr::*;
+use crate::public_key::PublicKey;
+use crate::string::CharPtr;
+use crate::transaction_hash::TransactionHash;
+use crate::vkeywitness::Vkeywitness;
+use cardano_serialization_lib::{
+  crypto::{Bip32PrivateKey as RBip32PrivateKey, PrivateKey as RPrivateKey},
+  utils::make_vkey_witness,
+};
+use std::convert::TryFrom;
+use std::sync::atomic::{compiler_fence, Ordering};
+
+const BIP32_PRIVATE_KEY_LENGTH: usize = 96;
+
+// Overwrites the bytes with zeros in a way the compiler can't optimize out
+pub fn wipe(bytes: &mut [u8]) {
+  for byte in bytes.iter_mut() {
+    unsafe { std::ptr::write_volatile(byte, 0) };
+  }
+  compiler_fence(Ordering::SeqCst);
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum SecretKeyKind {
+  Bip32,
+  Extended,
+  Normal,
+}
+
+// Opaque heap-allocated secret key. Its bytes never leave the Rust side
+// and are wiped when the handle is freed. The BIP32 keys of
+// cardano-serialization-lib wipe themselves on drop, its PrivateKey doesn't,
+// so one is only made for the call that signs with it.
+pub struct SecretKey {
+  kind: SecretKeyKind,
+  bytes: Vec<u8>,
+}
+
+impl Drop for SecretKey {
+  fn drop(&mut self) {
+    wipe(&mut self.bytes);
+  }
+}
+
+impl SecretKey {
+  // Takes the bytes over, the ones that don't make a valid key are wiped too.
+  // Plain keys are only checked by length, which is all their parsing does,
+  // so no unwiped copy of them is made.
+  fn new(kind: SecretKeyKind, mut bytes: Vec<u8>) -> Result<Self> {
+    let parsed = match kind {
+      SecretKeyKind::Bip32 => RBip32PrivateKey::from_bytes(&bytes).map(|_| ()).into_result(),
+      SecretKeyKind::Extended if bytes.len() == EXTENDED_PRIVATE_KEY_LENGTH => Ok(()),
+      SecretKeyKind::Normal if bytes.len() == NORMAL_PRIVATE_KEY_LENGTH => Ok(()),
+      _ => Err("Bad secret key length".into()),
+    };
+    match parsed {
+      Ok(()) => Ok(Self { kind, bytes }),
+      Err(err) => {
+        wipe(&mut bytes);
+        Err(err)
+      }
+    }
+  }
+
+  fn from_bip32(key: RBip32PrivateKey) -> Result<Self> {
+    Self::new(SecretKeyKind::Bip32, key.as_bytes())
+  }
+
+  // Kind of the key is told by the length of its bytes
+  fn from_bytes(bytes: Vec<u8>) -> Result<Self> {
+    match bytes.len() {
+      BIP32_PRIVATE_KEY_LENGTH => Self::new(SecretKeyKind::Bip32, bytes),
+      EXTENDED_PRIVATE_KEY_LENGTH => Self::new(SecretKeyKind::Extended, bytes),
+      NORMAL_PRIVATE_KEY_LENGTH => Self::new(SecretKeyKind::Normal, bytes),
+      _ => {
+        let mut bytes = bytes;
+        wipe(&mut bytes);
+        Err("Bad secret key length".into())
+      }
+    }
+  }
+
+  fn into_handle(self) -> *mut Self {
+    Box::into_raw(Box::new(self))
+  }
+
+  #[allow(clippy::missing_safety_doc)]
+  pub unsafe fn from_handle<'a>(handle: *const Self) -> Result<&'a Self> {
+    handle.as_ref().ok_or(CError::NullPtr)
+  }
+
+  // Wiped by its own drop
+  fn bip32(&self) -> Result<RBip32PrivateKey> {
+    match self.kind {
+      SecretKeyKind::Bip32 => RBip32PrivateKey::from_bytes(&self.bytes).into_result(),
+      _ => Err("Secret key is not a BIP32 key".into()),
+    }
+  }
+
+  // Copy of the key that isn't wiped on drop, so it must not outlive the call
+  // using it. For BIP32 keys it is the raw key of a wiped one.
+  pub fn private_key(&self) -> Result<RPrivateKey> {
+    match self.kind {
+      SecretKeyKind::Bip32 => self.bip32().map(|key| key.to_raw_key()),
+      SecretKeyKind::Extended => RPrivateKey::from_extended_bytes(&self.bytes).into_result(),
+      SecretKeyKind::Normal => RPrivateKey::from_normal_bytes(&self.bytes).into_result(),
+    }
+  }
+
+  pub fn derive_path(&self, path: &[u32]) -> Result<Self> {
+    self
+      .bip32()
+      .map(|key| path.iter().fold(key, |key, index| key.derive(*index)))
+      .and_then(Self::from_bip32)
+  }
+
+  pub fn sign(&self, data: &[u8]) -> Result<Ed25519Signature> {
+    self.private_key().map(|key| key.sign(data).into())
+  }
+
+  pub fn make_vkey_witness(&self, tx_body_hash: &TransactionHash) -> Result<Vkeywitness> {
+    self
+      .private_key()
+      .map(|key| make_vkey_witness(&(*tx_body_hash).into(), &key).into())
+  }
+
+  pub fn encrypt(&self, password: &[u8], format: KeyEncryptionFormat) -> Result<Vec<u8>> {
+    encrypt_with_password(password, &self.bytes, format)
+  }
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_secret_key_generate(
+  result: &mut *mut SecretKey, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    RBip32PrivateKey::generate_ed25519_bip32()
+      .into_result()
+      .and_then(SecretKey::from_bip32)
+      .map(SecretKey::into_handle)
+  })
+  .response(result, error)
+}
+
+// Wipes the given key once it is moved into the handle
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_secret_key_from_bip32_private_key(
+  pk: &mut Bip32PrivateKey, result: &mut *mut SecretKey, error: &mut CError,
+) -> bool {
+  let mut key = *pk;
+  pk.wipe();
+  let response = handle_exception_result(|| {
+    RBip32PrivateKey::try_from(key)
+      .and_then(SecretKey::from_bip32)
+      .map(SecretKey::into_handle)
+  })
+  .response(result, error);
+  key.wipe();
+  response
+}
+
+// Wipes the given key once it is moved into the handle
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_secret_key_from_private_key(
+  private_key: &mut PrivateKey, result: &mut *mut SecretKey, error: &mut CError,
+) -> bool {
+  let mut key = *private_key;
+  private_key.wipe();
+  let response = handle_exception_result(|| {
+    match &key {
+      PrivateKey::Extended(bytes) => SecretKey::new(SecretKeyKind::Extended, bytes.to_vec()),
+      PrivateKey::Normal(bytes) => SecretKey::new(SecretKeyKind::Normal, bytes.to_vec()),
+    }
+    .map(SecretKey::into_handle)
+  })
+  .response(result, error);
+  key.wipe();
+  response
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_secret_key_from_bip39_entropy(
+  entropy: CData, password: CData, result: &mut *mut SecretKey, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    entropy
+      .unowned()
+      .zip(password.unowned())
+      .map(|(ent, pwd)| RBip32PrivateKey::from_bip39_entropy(ent, pwd))
+      .and_then(SecretKey::from_bip32)
+      .map(SecretKey::into_handle)
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_secret_key_from_mnemonic(
+  wordlist: *const Bip39Wordlist, mnemonic: CharPtr, password: CData,
+  result: &mut *mut SecretKey, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    let mut entropy = Bip39Wordlist::from_handle(wordlist)
+      .zip(mnemonic.unowned())
+      .and_then(|(wordlist, mnemonic)| wordlist.mnemonic_to_entropy(mnemonic))?;
+    let key = password
+      .unowned()
+      .map(|pwd| RBip32PrivateKey::from_bip39_entropy(&entropy, pwd))
+      .and_then(SecretKey::from_bip32);
+    wipe(&mut entropy);
+    key.map(SecretKey::into_handle)
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_secret_key_decrypt(
+  data: CData, password: CData, format: KeyEncryptionFormat, result: &mut *mut SecretKey,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    password
+      .unowned()
+      .zip(data.unowned())
+      .and_then(|(password, data)| decrypt_with_password(password, data, format))
+      .and_then(SecretKey::from_bytes)
+      .map(SecretKey::into_handle)
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_secret_key_encrypt(
+  key: *const SecretKey, password: CData, format: KeyEncryptionFormat, result: &mut CData,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    SecretKey::from_handle(key)
+      .zip(password.unowned())
+      .and_then(|(key, password)| key.encrypt(password, format))
+      .map(|bytes| bytes.into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_secret_key_derive(
+  key: *const SecretKey, index: u32, result: &mut *mut SecretKey, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    SecretKey::from_handle(key)
+      .and_then(|key| key.derive_path(&[index]))
+      .map(SecretKey::into_handle)
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_secret_key_derive_path(
+  key: *const SecretKey, path: DerivationPath, result: &mut *mut SecretKey, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    SecretKey::from_handle(key)
+      .zip(path.unowned())
+      .and_then(|(key, path)| key.derive_path(path))
+      .map(SecretKey::into_handle)
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_secret_key_to_public(
+  key: *const SecretKey, result: &mut PublicKey, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    SecretKey::from_handle(key)
+      .and_then(|key| key.private_key())
+      .map(|key| key.to_public().into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_secret_key_to_bip32_public(
+  key: *const SecretKey, result: &mut Bip32PublicKey, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    SecretKey::from_handle(key)
+      .and_then(|key| key.bip32())
+      .map(|key| key.to_public().into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_secret_key_sign(
+  key: *const SecretKey, data: CData, result: &mut Ed25519Signature, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    SecretKey::from_handle(key)
+      .zip(data.unowned())
+      .and_then(|(key, data)| key.sign(data))
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_secret_key_make_vkey_witness(
+  key: *const SecretKey, tx_body_hash: TransactionHash, result: &mut Vkeywitness,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    SecretKey::from_handle(key).and_then(|key| key.make_vkey_witness(&tx_body_hash))
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_secret_key_free(key: &mut *mut SecretKey) {
+  if !key.is_null() {
+    let _ = Box::from_raw(*key);
+    *key = std::ptr::null_mut();
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn plain_keys_are_checked_by_length() {
+    let key = SecretKey::from_bytes(vec![3; NORMAL_PRIVATE_KEY_LENGTH]).unwrap();
+    assert!(key.kind == SecretKeyKind::Normal);
+    let public = RPrivateKey::from_normal_bytes(&[3; NORMAL_PRIVATE_KEY_LENGTH])
+      .unwrap()
+      .to_public();
+    assert_eq!(key.private_key().unwrap().to_public().as_bytes(), public.as_bytes());
+    assert!(key.bip32().is_err());
+    let key = SecretKey::from_bytes(vec![3; EXTENDED_PRIVATE_KEY_LENGTH]).unwrap();
+    assert!(key.kind == SecretKeyKind::Extended);
+    assert!(SecretKey::from_bytes(vec![3; 33]).is_err());
+    assert!(SecretKey::new(SecretKeyKind::Extended, vec![3; NORMAL_PRIVATE_KEY_LENGTH]).is_err());
+  }
+
+  #[test]
+  fn bip32_key_derives_like_the_library() {
+    let root = RBip32PrivateKey::from_bip39_entropy(&[1; 16], &[]);
+    let key = SecretKey::from_bip32(RBip32PrivateKey::from_bytes(&root.as_bytes()).unwrap());
+    let derived = key.unwrap().derive_path(&[0x8000_0000, 1]).unwrap();
+    let expected = root.derive(0x8000_0000).derive(1);
+    assert_eq!(derived.bytes, expected.as_bytes());
+  }
+}
@@ -0,0 +1,311 @@
+use crate::address::pointer::Slot;
+use crate::error::CError;
+use crate::panic::*;
+use crate::ptr::*;
+use crate::string::CharPtr;
+use crate::transaction_body::Epoch;
+use serde_json::Value;
+use std::convert::TryInto;
+
+// Byron slots are 20 seconds long on every network
+const BYRON_SLOT_LENGTH: u32 = 20_000;
+
+// Era history of a network: Byron slots up to the zero slot, where Shelley
+// starts, and Shelley slots after it. Times are POSIX milliseconds.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct SlotConfig {
+  system_start: u64,
+  byron_slot_length: u32,
+  byron_epoch_length: u32,
+  zero_slot: Slot,
+  zero_epoch: Epoch,
+  slot_length: u32,
+  epoch_length: u32,
+}
+
+// Days since the POSIX epoch of a proleptic Gregorian date
+fn days_from_civil(year: u64, month: u64, day: u64) -> Option<u64> {
+  let year = if month <= 2 { year.checked_sub(1)? } else { year };
+  let era = year / 400;
+  let year_of_era = year - era * 400;
+  let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+  let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+  (era * 146_097 + day_of_era).checked_sub(719_468)
+}
+
+// POSIX milliseconds of a UTC time like "2022-10-25T00:00:00Z"
+fn parse_utc_time(text: &str) -> Option<u64> {
+  let (date, time) = text.strip_suffix('Z')?.split_once('T')?;
+  let date: Vec<u64> = date.split('-').map(|part| part.parse().ok()).collect::<Option<_>>()?;
+  let (time, fraction) = time.split_once('.').unwrap_or((time, ""));
+  let time: Vec<u64> = time.split(':').map(|part| part.parse().ok()).collect::<Option<_>>()?;
+  match (date.as_slice(), time.as_slice()) {
+    (&[year, month, day], &[hour, minute, second])
+      if (1..=12).contains(&month)
+        && (1..=31).contains(&day)
+        && hour < 24
+        && minute < 60
+        && second < 60 =>
+    {
+      let millis: u64 = match fraction {
+        "" => 0,
+        _ => format!("{:0<3}", fraction.get(..3).unwrap_or(fraction)).parse().ok()?,
+      };
+      let seconds = days_from_civil(year, month, day)? * 86_400 + hour * 3_600 + minute * 60;
+      Some((seconds + second) * 1_000 + millis)
+    }
+    _ => None,
+  }
+}
+
+impl SlotConfig {
+  pub fn mainnet() -> Self {
+    Self {
+      system_start: 1_506_203_091_000,
+      byron_slot_length: BYRON_SLOT_LENGTH,
+      byron_epoch_length: 21_600,
+      zero_slot: 4_492_800,
+      zero_epoch: 208,
+      slot_length: 1_000,
+      epoch_length: 432_000,
+    }
+  }
+
+  pub fn preprod() -> Self {
+    Self {
+      system_start: 1_654_041_600_000,
+      byron_slot_length: BYRON_SLOT_LENGTH,
+      byron_epoch_length: 21_600,
+      zero_slot: 86_400,
+      zero_epoch: 4,
+      slot_length: 1_000,
+      epoch_length: 432_000,
+    }
+  }
+
+  // Preview starts in Shelley, it has no Byron slots
+  pub fn preview() -> Self {
+    Self {
+      system_start: 1_666_656_000_000,
+      byron_slot_length: BYRON_SLOT_LENGTH,
+      byron_epoch_length: 4_320,
+      zero_slot: 0,
+      zero_epoch: 0,
+      slot_length: 1_000,
+      epoch_length: 86_400,
+    }
+  }
+
+  // Shelley genesis has no hard fork epoch, so it is given. Byron epochs
+  // are ten times the security parameter long.
+  pub fn from_shelley_genesis(json: &str, zero_epoch: Epoch) -> Result<Self> {
+    let genesis: Value = serde_json::from_str(json)?;
+    let field = |key: &str| {
+      genesis
+        .get(key)
+        .ok_or_else(|| CError::from(format!("Shelley genesis {} is missing", key)))
+    };
+    let system_start = field("systemStart")?
+      .as_str()
+      .and_then(parse_utc_time)
+      .ok_or("Shelley genesis systemStart is malformed")?;
+    let slot_length = field("slotLength")?
+      .as_f64()
+      .map(|seconds| (seconds * 1_000.0).round())
+      .filter(|millis| *millis >= 1.0 && *millis <= u32::MAX as f64)
+      .ok_or("Shelley genesis slotLength is malformed")? as u32;
+    let epoch_length: u32 = field("epochLength")?
+      .as_u64()
+      .and_then(|length| length.try_into().ok())
+      .filter(|length| *length > 0)
+      .ok_or("Shelley genesis epochLength is malformed")?;
+    let byron_epoch_length: u32 = field("securityParam")?
+      .as_u64()
+      .and_then(|k| k.checked_mul(10))
+      .and_then(|length| length.try_into().ok())
+      .filter(|length| *length > 0)
+      .ok_or("Shelley genesis securityParam is malformed")?;
+    let zero_slot = zero_epoch
+      .checked_mul(byron_epoch_length)
+      .ok_or("Byron era is too long")?;
+    Ok(Self {
+      system_start,
+      byron_slot_length: BYRON_SLOT_LENGTH,
+      byron_epoch_length,
+      zero_slot,
+      zero_epoch,
+      slot_length,
+      epoch_length,
+    })
+  }
+
+  fn zero_time(&self) -> Result<u64> {
+    (self.zero_slot as u64)
+      .checked_mul(self.byron_slot_length as u64)
+      .and_then(|byron_time| byron_time.checked_add(self.system_start))
+      .ok_or_else(|| "Slot config is out of range".into())
+  }
+
+  pub fn slot_to_time(&self, slot: Slot) -> Result<u64> {
+    let (start, slots, slot_length) = if slot < self.zero_slot {
+      (self.system_start, slot, self.byron_slot_length)
+    } else {
+      (self.zero_time()?, slot - self.zero_slot, self.slot_length)
+    };
+    (slots as u64)
+      .checked_mul(slot_length as u64)
+      .and_then(|time| time.checked_add(start))
+      .ok_or_else(|| "Time is out of range".into())
+  }
+
+  // Slot the time falls in
+  pub fn time_to_slot(&self, time: u64) -> Result<Slot> {
+    if time < self.system_start {
+      return Err("Time is before the system start".into());
+    }
+    let zero_time = self.zero_time()?;
+    let slot = if time < zero_time {
+      (time - self.system_start).checked_div(self.byron_slot_length as u64)
+    } else {
+      (time - zero_time)
+        .checked_div(self.slot_length as u64)
+        .and_then(|slots| slots.checked_add(self.zero_slot as u64))
+    };
+    slot
+      .ok_or("Slot config is out of range")?
+      .try_into()
+      .map_err(|_| "Slot is out of range".into())
+  }
+
+  pub fn slot_to_epoch(&self, slot: Slot) -> Result<Epoch> {
+    if slot < self.zero_slot {
+      slot.checked_div(self.byron_epoch_length)
+    } else {
+      ((slot - self.zero_slot).checked_div(self.epoch_length))
+        .and_then(|epochs| epochs.checked_add(self.zero_epoch))
+    }
+    .ok_or_else(|| "Slot config is out of range".into())
+  }
+
+  // First slot of the epoch
+  pub fn epoch_to_slot(&self, epoch: Epoch) -> Result<Slot> {
+    if epoch < self.zero_epoch {
+      epoch.checked_mul(self.byron_epoch_length)
+    } else {
+      (epoch - self.zero_epoch)
+        .checked_mul(self.epoch_length)
+        .and_then(|slots| slots.checked_add(self.zero_slot))
+    }
+    .ok_or_else(|| "Slot is out of range".into())
+  }
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_slot_config_mainnet() -> SlotConfig {
+  SlotConfig::mainnet()
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_slot_config_preprod() -> SlotConfig {
+  SlotConfig::preprod()
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_slot_config_preview() -> SlotConfig {
+  SlotConfig::preview()
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_slot_config_from_shelley_genesis(
+  json: CharPtr, zero_epoch: Epoch, result: &mut SlotConfig, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    json
+      .unowned()
+      .and_then(|json| SlotConfig::from_shelley_genesis(json, zero_epoch))
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_slot_config_slot_to_time(
+  config: SlotConfig, slot: Slot, result: &mut u64, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| config.slot_to_time(slot)).response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_slot_config_time_to_slot(
+  config: SlotConfig, time: u64, result: &mut Slot, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| config.time_to_slot(time)).response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_slot_config_slot_to_epoch(
+  config: SlotConfig, slot: Slot, result: &mut Epoch, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| config.slot_to_epoch(slot)).response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_slot_config_epoch_to_slot(
+  config: SlotConfig, epoch: Epoch, result: &mut Slot, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| config.epoch_to_slot(epoch)).response(result, error)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn mainnet_shelley_starts_at_its_hard_fork() {
+    let config = SlotConfig::mainnet();
+    // 2020-07-29T21:44:51Z, first slot of epoch 208
+    assert_eq!(config.slot_to_time(4_492_800).unwrap(), 1_596_059_091_000);
+    assert_eq!(config.time_to_slot(1_596_059_091_999).unwrap(), 4_492_800);
+    assert_eq!(config.slot_to_epoch(4_492_799).unwrap(), 207);
+    assert_eq!(config.slot_to_epoch(4_492_800).unwrap(), 208);
+    assert_eq!(config.epoch_to_slot(209).unwrap(), 4_924_800);
+    // Byron slots are 20 seconds long
+    assert_eq!(config.time_to_slot(1_506_203_091_000 + 39_999).unwrap(), 1);
+    assert!(config.time_to_slot(1_506_203_090_999).is_err());
+  }
+
+  #[test]
+  fn preprod_shelley_starts_on_june_21() {
+    let config = SlotConfig::preprod();
+    assert_eq!(parse_utc_time("2022-06-21T00:00:00Z"), Some(1_655_769_600_000));
+    assert_eq!(config.slot_to_time(86_400).unwrap(), 1_655_769_600_000);
+    assert_eq!(config.epoch_to_slot(4).unwrap(), 86_400);
+  }
+
+  #[test]
+  fn genesis_of_preview_matches_the_preset() {
+    let genesis = r#"{
+      "systemStart": "2022-10-25T00:00:00Z",
+      "slotLength": 1,
+      "epochLength": 86400,
+      "securityParam": 432
+    }"#;
+    let config = SlotConfig::from_shelley_genesis(genesis, 0).unwrap();
+    let preview = SlotConfig::preview();
+    assert_eq!(config.system_start, preview.system_start);
+    assert_eq!(config.byron_epoch_length, preview.byron_epoch_length);
+    assert_eq!(config.epoch_length, preview.epoch_length);
+    assert_eq!(config.slot_to_time(1_000).unwrap(), preview.slot_to_time(1_000).unwrap());
+    assert!(SlotConfig::from_shelley_genesis("{}", 0).is_err());
+    assert_eq!(parse_utc_time("2022-10-25T00:00:00.5Z"), Some(1_666_656_000_500));
+    assert_eq!(parse_utc_time("2022-13-25T00:00:00Z"), None);
+  }
+}
@@ -0,0 +1,44 @@
+use crate::cbor::*;
+use crate::drep::DRep;
+use crate::panic::*;
+use crate::stake_credential::{Ed25519KeyHash, StakeCredential};
+use cbor_event::Len;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct StakeAndVoteDelegation {
+  stake_credential: StakeCredential,
+  pool_keyhash: Ed25519KeyHash,
+  drep: DRep,
+}
+
+impl StakeAndVoteDelegation {
+  pub fn stake_credential(&self) -> StakeCredential {
+    self.stake_credential
+  }
+}
+
+impl CborSerialize for StakeAndVoteDelegation {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    se.write_array(Len::Len(4))?;
+    se.write_unsigned_integer(10)?;
+    self.stake_credential.serialize(se)?;
+    self.pool_keyhash.serialize(se)?;
+    self.drep.serialize(se)
+  }
+}
+
+impl CborDeserialize for StakeAndVoteDelegation {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    let len = read_kind_array(de, 10, 3, "StakeAndVoteDelegation")?;
+    let stake_credential = StakeCredential::deserialize(de)?;
+    let pool_keyhash = Ed25519KeyHash::deserialize(de)?;
+    let drep = DRep::deserialize(de)?;
+    read_array_end(de, len)?;
+    Ok(Self {
+      stake_credential,
+      pool_keyhash,
+      drep,
+    })
+  }
+}
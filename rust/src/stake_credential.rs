@@ -3,12 +3,14 @@ use super::error::CError;
 use super::panic::*;
 use super::ptr::Ptr;
 use crate::array::CArray;
+use crate::cbor::*;
 use crate::ptr::Free;
 use cardano_serialization_lib::address::{StakeCredKind, StakeCredential as RStakeCredential};
 use cardano_serialization_lib::crypto::{
   Ed25519KeyHash as REd25519KeyHash, ScriptHash as RScriptHash,
 };
 use cardano_serialization_lib::Ed25519KeyHashes as REd25519KeyHashes;
+use cbor_event::Len;
 use std::convert::{TryFrom, TryInto};
 
 #[repr(C)]
@@ -136,6 +138,14 @@ impl From<StakeCredential> for RStakeCredential {
   }
 }
 
+pub type StakeCredentials = CArray<StakeCredential>;
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_stake_credentials_free(stake_credentials: &mut StakeCredentials) {
+  stake_credentials.free();
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn cardano_ed25519_key_hash_from_bytes(
   data: CData, result: &mut Ed25519KeyHash, error: &mut CError,
@@ -207,3 +217,65 @@ pub unsafe extern "C" fn cardano_stake_credential_to_bytes(
   })
   .response(result, error)
 }
+
+impl CborSerialize for Ed25519KeyHash {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    se.write_bytes(self.bytes)?;
+    Ok(())
+  }
+}
+
+impl CborDeserialize for Ed25519KeyHash {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    REd25519KeyHash::from_bytes(de.bytes()?)
+      .into_result()
+      .and_then(|hash| hash.try_into())
+  }
+}
+
+impl CborSerialize for ScriptHash {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    se.write_bytes(self.bytes)?;
+    Ok(())
+  }
+}
+
+impl CborDeserialize for ScriptHash {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    RScriptHash::from_bytes(de.bytes()?)
+      .into_result()
+      .and_then(|hash| hash.try_into())
+  }
+}
+
+impl CborSerialize for StakeCredential {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    se.write_array(Len::Len(2))?;
+    match self {
+      StakeCredential::Key(hash) => {
+        se.write_unsigned_integer(0)?;
+        hash.serialize(se)
+      }
+      StakeCredential::Script(hash) => {
+        se.write_unsigned_integer(1)?;
+        hash.serialize(se)
+      }
+    }
+  }
+}
+
+impl CborDeserialize for StakeCredential {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    let len = de.array()?;
+    if len != Len::Len(2) && len != Len::Indefinite {
+      return Err("Wrong StakeCredential array length".into());
+    }
+    let credential = match de.unsigned_integer()? {
+      0 => Ed25519KeyHash::deserialize(de).map(Self::Key),
+      1 => ScriptHash::deserialize(de).map(Self::Script),
+      kind => Err(format!("Wrong StakeCredential kind {}", kind).into()),
+    }?;
+    read_array_end(de, len)?;
+    Ok(credential)
+  }
+}
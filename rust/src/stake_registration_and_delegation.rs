@@ -0,0 +1,49 @@
+use crate::cbor::*;
+use crate::linear_fee::Coin;
+use crate::panic::*;
+use crate::stake_credential::{Ed25519KeyHash, StakeCredential};
+use cbor_event::Len;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct StakeRegistrationAndDelegation {
+  stake_credential: StakeCredential,
+  pool_keyhash: Ed25519KeyHash,
+  deposit: Coin,
+}
+
+impl StakeRegistrationAndDelegation {
+  pub fn stake_credential(&self) -> StakeCredential {
+    self.stake_credential
+  }
+
+  pub fn deposit(&self) -> Coin {
+    self.deposit
+  }
+}
+
+impl CborSerialize for StakeRegistrationAndDelegation {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    se.write_array(Len::Len(4))?;
+    se.write_unsigned_integer(11)?;
+    self.stake_credential.serialize(se)?;
+    self.pool_keyhash.serialize(se)?;
+    se.write_unsigned_integer(self.deposit)?;
+    Ok(())
+  }
+}
+
+impl CborDeserialize for StakeRegistrationAndDelegation {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    let len = read_kind_array(de, 11, 3, "StakeRegistrationAndDelegation")?;
+    let stake_credential = StakeCredential::deserialize(de)?;
+    let pool_keyhash = Ed25519KeyHash::deserialize(de)?;
+    let deposit = de.unsigned_integer()?;
+    read_array_end(de, len)?;
+    Ok(Self {
+      stake_credential,
+      pool_keyhash,
+      deposit,
+    })
+  }
+}
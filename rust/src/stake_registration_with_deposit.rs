@@ -0,0 +1,45 @@
+use crate::cbor::*;
+use crate::linear_fee::Coin;
+use crate::panic::*;
+use crate::stake_credential::StakeCredential;
+use cbor_event::Len;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct StakeRegistrationWithDeposit {
+  stake_credential: StakeCredential,
+  deposit: Coin,
+}
+
+impl StakeRegistrationWithDeposit {
+  pub fn stake_credential(&self) -> StakeCredential {
+    self.stake_credential
+  }
+
+  pub fn deposit(&self) -> Coin {
+    self.deposit
+  }
+}
+
+impl CborSerialize for StakeRegistrationWithDeposit {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    se.write_array(Len::Len(3))?;
+    se.write_unsigned_integer(7)?;
+    self.stake_credential.serialize(se)?;
+    se.write_unsigned_integer(self.deposit)?;
+    Ok(())
+  }
+}
+
+impl CborDeserialize for StakeRegistrationWithDeposit {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    let len = read_kind_array(de, 7, 2, "StakeRegistrationWithDeposit")?;
+    let stake_credential = StakeCredential::deserialize(de)?;
+    let deposit = de.unsigned_integer()?;
+    read_array_end(de, len)?;
+    Ok(Self {
+      stake_credential,
+      deposit,
+    })
+  }
+}
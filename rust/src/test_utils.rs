@@ -0,0 +1,7 @@
+// Bytes of a hex string, test vectors are written in hex
+pub fn hex(text: &str) -> Vec<u8> {
+  (0..text.len())
+    .step_by(2)
+    .map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap())
+    .collect()
+}
@@ -1,13 +1,35 @@
+use crate::address::address::Address;
+use crate::array::CArray;
+use crate::bip32_private_key::{derive_path, Bip32PrivateKey, DerivationPath, DerivationPaths};
+use crate::bootstrap_witness::BootstrapWitness;
+use crate::cbor::*;
 use crate::data::CData;
 use crate::error::CError;
 use crate::linear_fee::*;
 use crate::option::COption;
 use crate::panic::*;
+use crate::private_key::{PrivateKey, PrivateKeys};
 use crate::ptr::*;
+use crate::secret_key::SecretKey;
+use crate::stake_credential::{Ed25519KeyHash, Ed25519KeyHashes};
 use crate::transaction_body::TransactionBody;
+use crate::transaction_hash::{hash_transaction, TransactionHash};
+use crate::transaction_unspent_output::{TransactionUnspentOutput, TransactionUnspentOutputs};
 use crate::transaction_metadata::AuxiliaryData;
 use crate::transaction_witness_set::TransactionWitnessSet;
-use cardano_serialization_lib::{fees::min_fee, utils::from_bignum, Transaction as RTransaction};
+use crate::vkeywitness::Vkeywitness;
+use cardano_serialization_lib::{
+  address::{
+    Address as RAddress, BaseAddress as RBaseAddress, ByronAddress as RByronAddress,
+    EnterpriseAddress as REnterpriseAddress, PointerAddress as RPointerAddress,
+  },
+  crypto::Ed25519KeyHash as REd25519KeyHash,
+  metadata::AuxiliaryData as RAuxiliaryData,
+  utils::{make_icarus_bootstrap_witness, make_vkey_witness},
+  Transaction as RTransaction,
+};
+use cbor_event::{Len, Special};
+use std::collections::BTreeSet;
 use std::convert::{TryFrom, TryInto};
 
 #[repr(C)]
@@ -17,6 +39,8 @@ pub struct Transaction {
   witness_set: TransactionWitnessSet,
   is_valid: bool,
   auxiliary_data: COption<AuxiliaryData>,
+  // Pre-Alonzo transactions are encoded without the validity flag
+  pre_alonzo: bool,
 }
 
 impl Free for Transaction {
@@ -68,49 +92,336 @@ impl TryFrom<RTransaction> for Transaction {
         witness_set,
         is_valid: transaction.is_valid(),
         auxiliary_data: auxiliary_data.into(),
+        pre_alonzo: false,
       })
   }
 }
 
+impl CborSerialize for Transaction {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    if self.pre_alonzo && !self.is_valid {
+      return Err("Pre-Alonzo transaction can't be invalid".into());
+    }
+    se.write_array(Len::Len(if self.pre_alonzo { 3 } else { 4 }))?;
+    self.body.serialize(se)?;
+    self.witness_set.serialize(se)?;
+    if !self.pre_alonzo {
+      se.write_special(Special::Bool(self.is_valid))?;
+    }
+    let auxiliary_data: Option<AuxiliaryData> = self.auxiliary_data.into();
+    write_nullable(se, auxiliary_data, |se, auxiliary_data| {
+      let auxiliary_data: RAuxiliaryData = auxiliary_data.try_into()?;
+      se.write_raw_bytes(&auxiliary_data.to_bytes())?;
+      Ok(())
+    })
+  }
+}
+
+impl CborDeserialize for Transaction {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    let len = de.array()?;
+    let body = TransactionBody::deserialize(de)?;
+    let witness_set = TransactionWitnessSet::deserialize(de)?;
+    // Pre-Alonzo transactions have no validity flag
+    let pre_alonzo = len == Len::Len(3);
+    let is_valid = match pre_alonzo {
+      true => true,
+      false => de.bool()?,
+    };
+    let auxiliary_data = read_nullable(de, |de| {
+      raw_item(de)
+        .and_then(|bytes| RAuxiliaryData::from_bytes(bytes).into_result())
+        .and_then(|auxiliary_data| auxiliary_data.try_into())
+    })?;
+    read_array_end(de, len)?;
+    Ok(Self {
+      body,
+      witness_set,
+      is_valid,
+      auxiliary_data: auxiliary_data.into(),
+      pre_alonzo,
+    })
+  }
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn cardano_transaction_min_fee(
   transaction: Transaction, linear_fee: LinearFee, result: &mut Coin, error: &mut CError,
 ) -> bool {
   handle_exception_result(|| {
-    transaction
-      .try_into()
-      .and_then(|transaction| min_fee(&transaction, &linear_fee.into()).into_result())
-      .map(|coin| from_bignum(&coin))
+    transaction.to_cbor().and_then(|bytes| {
+      (bytes.len() as u64)
+        .checked_mul(linear_fee.coefficient)
+        .and_then(|fee| fee.checked_add(linear_fee.constant))
+        .ok_or_else(|| "Fee overflow".into())
+    })
   })
   .response(result, error)
 }
 
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct VkeywitnessVerification {
+  vkeywitness: Vkeywitness,
+  is_valid: bool,
+}
+
+impl Free for VkeywitnessVerification {
+  unsafe fn free(&mut self) {}
+}
+
+pub type VkeywitnessVerifications = CArray<VkeywitnessVerification>;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct TransactionWitnessesVerification {
+  vkeywitnesses: VkeywitnessVerifications,
+  missing_signers: Ed25519KeyHashes,
+}
+
+impl Free for TransactionWitnessesVerification {
+  unsafe fn free(&mut self) {
+    self.vkeywitnesses.free();
+    self.missing_signers.free();
+  }
+}
+
+// Payment keys of the spent outputs found in the UTxOs. Byron outputs are
+// signed with bootstrap witnesses, so their owners aren't counted.
+fn input_key_hashes(
+  body: &TransactionBody, utxos: &[TransactionUnspentOutput],
+) -> Result<BTreeSet<REd25519KeyHash>> {
+  let spent_inputs = body.spent_inputs()?;
+  let mut keys = BTreeSet::new();
+  for utxo in utxos {
+    if !spent_inputs.contains(&utxo.input.into()) {
+      continue;
+    }
+    let address = RAddress::try_from(utxo.output.address())?;
+    let payment = RBaseAddress::from_address(&address)
+      .map(|address| address.payment_cred())
+      .or_else(|| REnterpriseAddress::from_address(&address).map(|address| address.payment_cred()))
+      .or_else(|| RPointerAddress::from_address(&address).map(|address| address.payment_cred()));
+    keys.extend(payment.and_then(|payment| payment.to_keyhash()));
+  }
+  Ok(keys)
+}
+
+// Checks every vkey witness against the body hash. Missing signers are the keys
+// the body requires without a valid witness, with the owners of the spent inputs
+// found in the UTxOs.
+pub fn verify_vkey_witnesses(
+  body: &TransactionBody, witness_set: &TransactionWitnessSet, tx_body_hash: &TransactionHash,
+  utxos: &[TransactionUnspentOutput],
+) -> Result<TransactionWitnessesVerification> {
+  let mut missing_signers = body.required_key_hashes()?;
+  missing_signers.extend(input_key_hashes(body, utxos)?);
+  let mut vkeywitnesses = Vec::new();
+  for vkeywitness in witness_set.vkeywitnesses()? {
+    let is_valid = vkeywitness.verify(tx_body_hash)?;
+    if is_valid {
+      missing_signers.remove(&vkeywitness.key_hash()?);
+    }
+    vkeywitnesses.push(VkeywitnessVerification {
+      vkeywitness,
+      is_valid,
+    });
+  }
+  missing_signers
+    .into_iter()
+    .map(|key_hash| key_hash.try_into())
+    .collect::<Result<Vec<Ed25519KeyHash>>>()
+    .map(|missing_signers| TransactionWitnessesVerification {
+      vkeywitnesses: vkeywitnesses.into(),
+      missing_signers: missing_signers.into(),
+    })
+}
+
+pub fn make_vkey_witnesses(
+  tx_body_hash: &TransactionHash, keys: &[PrivateKey],
+) -> Result<Vec<Vkeywitness>> {
+  let tx_body_hash = (*tx_body_hash).into();
+  keys
+    .iter()
+    .map(|key| (*key).try_into())
+    .map(|key| key.map(|key| make_vkey_witness(&tx_body_hash, &key).into()))
+    .collect()
+}
+
+// Keys whose Icarus address is the one of a spent Byron output sign with
+// a bootstrap witness, the others with a vkey witness
+pub fn make_bip32_witnesses(
+  body: &TransactionBody, tx_body_hash: &TransactionHash, root_key: Bip32PrivateKey,
+  paths: &[DerivationPath], utxos: &[TransactionUnspentOutput],
+) -> Result<(Vec<Vkeywitness>, Vec<BootstrapWitness>)> {
+  let tx_body_hash = (*tx_body_hash).into();
+  let spent_inputs = body.spent_inputs()?;
+  let mut byron_addresses = Vec::new();
+  for utxo in utxos {
+    if let Address::Byron(address) = utxo.output.address() {
+      if spent_inputs.contains(&utxo.input.into()) {
+        byron_addresses.push(RByronAddress::try_from(address)?);
+      }
+    }
+  }
+  let mut vkeywitnesses = Vec::new();
+  let mut bootstraps = Vec::new();
+  for path in paths {
+    let key = derive_path(root_key, unsafe { path.unowned()? })?;
+    let public_key = key.to_public();
+    let byron_address = byron_addresses.iter().find(|address| {
+      let icarus = RByronAddress::icarus_from_key(&public_key, address.byron_protocol_magic());
+      icarus.to_bytes() == address.to_bytes()
+    });
+    match byron_address {
+      Some(address) => {
+        bootstraps.push(make_icarus_bootstrap_witness(&tx_body_hash, address, &key).into())
+      }
+      None => vkeywitnesses.push(make_vkey_witness(&tx_body_hash, &key.to_raw_key()).into()),
+    }
+  }
+  Ok((vkeywitnesses, bootstraps))
+}
+
+impl Transaction {
+  pub fn verify_witnesses(
+    &self, utxos: &[TransactionUnspentOutput],
+  ) -> Result<TransactionWitnessesVerification> {
+    let tx_body_hash = hash_transaction(&self.body)?;
+    verify_vkey_witnesses(&self.body, &self.witness_set, &tx_body_hash, utxos)
+  }
+
+  // Copy of the transaction with the witnesses added to its witness set
+  fn with_witnesses(
+    &self, vkeywitnesses: Vec<Vkeywitness>, bootstraps: Vec<BootstrapWitness>,
+  ) -> Result<Self> {
+    let mut transaction = Self::from_cbor(&self.to_cbor()?)?;
+    match transaction.witness_set.add_witnesses(vkeywitnesses, bootstraps) {
+      Ok(()) => Ok(transaction),
+      Err(error) => {
+        unsafe { transaction.free() };
+        Err(error)
+      }
+    }
+  }
+
+  // Copy of the transaction with the other witness set merged into its own
+  pub fn add_witness_set(&self, witness_set: &TransactionWitnessSet) -> Result<Self> {
+    let mut transaction = Self::from_cbor(&self.to_cbor()?)?;
+    match self.witness_set.merge(witness_set) {
+      Ok(witness_set) => {
+        unsafe { transaction.witness_set.free() };
+        transaction.witness_set = witness_set;
+        Ok(transaction)
+      }
+      Err(error) => {
+        unsafe { transaction.free() };
+        Err(error)
+      }
+    }
+  }
+
+  pub fn sign(&self, keys: &[PrivateKey]) -> Result<Self> {
+    let tx_body_hash = hash_transaction(&self.body)?;
+    self.with_witnesses(make_vkey_witnesses(&tx_body_hash, keys)?, Vec::new())
+  }
+
+  pub fn sign_with_secret_key(&self, key: &SecretKey) -> Result<Self> {
+    let tx_body_hash = hash_transaction(&self.body)?;
+    self.with_witnesses(vec![key.make_vkey_witness(&tx_body_hash)?], Vec::new())
+  }
+
+  pub fn sign_bip32(
+    &self, root_key: Bip32PrivateKey, paths: &[DerivationPath],
+    utxos: &[TransactionUnspentOutput],
+  ) -> Result<Self> {
+    let tx_body_hash = hash_transaction(&self.body)?;
+    let (vkeywitnesses, bootstraps) =
+      make_bip32_witnesses(&self.body, &tx_body_hash, root_key, paths, utxos)?;
+    self.with_witnesses(vkeywitnesses, bootstraps)
+  }
+}
+
 #[no_mangle]
-pub unsafe extern "C" fn cardano_transaction_to_bytes(
-  transaction: Transaction, result: &mut CData, error: &mut CError,
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_add_witnesses(
+  transaction: Transaction, witness_set: TransactionWitnessSet, result: &mut Transaction,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| transaction.add_witness_set(&witness_set)).response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_sign(
+  transaction: Transaction, keys: PrivateKeys, result: &mut Transaction, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| keys.unowned().and_then(|keys| transaction.sign(keys)))
+    .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_sign_with_secret_key(
+  transaction: Transaction, key: *const SecretKey, result: &mut Transaction, error: &mut CError,
 ) -> bool {
   handle_exception_result(|| {
-    transaction
-      .try_into()
-      .map(|transaction: RTransaction| transaction.to_bytes())
-      .map(|bytes| bytes.into())
+    SecretKey::from_handle(key).and_then(|key| transaction.sign_with_secret_key(key))
   })
   .response(result, error)
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn cardano_transaction_from_bytes(
-  data: CData, result: &mut Transaction, error: &mut CError,
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_sign_bip32(
+  transaction: Transaction, root_key: Bip32PrivateKey, paths: DerivationPaths,
+  utxos: TransactionUnspentOutputs, result: &mut Transaction, error: &mut CError,
 ) -> bool {
   handle_exception_result(|| {
-    data
+    paths
       .unowned()
-      .and_then(|bytes| RTransaction::from_bytes(bytes.to_vec()).into_result())
-      .and_then(|transaction| transaction.try_into())
+      .zip(utxos.unowned())
+      .and_then(|(paths, utxos)| transaction.sign_bip32(root_key, paths, utxos))
   })
   .response(result, error)
 }
 
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_verify_witnesses(
+  transaction: Transaction, utxos: TransactionUnspentOutputs,
+  result: &mut TransactionWitnessesVerification, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    utxos.unowned().and_then(|utxos| transaction.verify_witnesses(utxos))
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_witnesses_verification_free(
+  verification: &mut TransactionWitnessesVerification,
+) {
+  verification.free();
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn cardano_transaction_to_bytes(
+  transaction: Transaction, result: &mut CData, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| transaction.to_cbor().map(|bytes| bytes.into()))
+    .response(result, error)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn cardano_transaction_from_bytes(
+  data: CData, result: &mut Transaction, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| data.unowned().and_then(Transaction::from_cbor))
+    .response(result, error)
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn cardano_transaction_clone(
   transaction: Transaction, result: &mut Transaction, error: &mut CError,
@@ -122,3 +433,145 @@ pub unsafe extern "C" fn cardano_transaction_clone(
 pub unsafe extern "C" fn cardano_transaction_free(transaction: &mut Transaction) {
   transaction.free();
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_utils::hex;
+  use cardano_serialization_lib::address::ByronAddress as RByronAddress;
+  use cardano_serialization_lib::crypto::{
+    Bip32PrivateKey as RBip32PrivateKey, Ed25519KeyHash as REd25519KeyHash,
+    PrivateKey as RPrivateKey, TransactionHash as RTransactionHash,
+    Vkeywitnesses as RVkeywitnesses,
+  };
+  use cardano_serialization_lib::utils::{
+    hash_transaction as rhash_transaction, make_vkey_witness, to_bignum,
+    TransactionUnspentOutput as RTransactionUnspentOutput, Value as RValue,
+  };
+  use cardano_serialization_lib::{
+    TransactionBody as RTransactionBody, TransactionInput as RTransactionInput,
+    TransactionOutput as RTransactionOutput, TransactionWitnessSet as RTransactionWitnessSet,
+  };
+
+  const BODY: &str = "a3008001800200";
+
+  #[test]
+  fn pre_alonzo_transaction_keeps_its_arity() {
+    let bytes = hex(&format!("83{}a0f6", BODY));
+    let mut transaction = Transaction::from_cbor(&bytes).unwrap();
+    assert!(transaction.pre_alonzo && transaction.is_valid);
+    assert_eq!(transaction.to_cbor().unwrap(), bytes);
+    unsafe { transaction.free() };
+  }
+
+  #[test]
+  fn alonzo_transaction_keeps_validity_flag() {
+    let bytes = hex(&format!("84{}a0f4f6", BODY));
+    let mut transaction = Transaction::from_cbor(&bytes).unwrap();
+    assert!(!transaction.pre_alonzo && !transaction.is_valid);
+    assert_eq!(transaction.to_cbor().unwrap(), bytes);
+    transaction.pre_alonzo = true;
+    assert!(transaction.to_cbor().is_err());
+    unsafe { transaction.free() };
+  }
+
+  fn key_hash_bytes(key_hash: &Ed25519KeyHash) -> Vec<u8> {
+    REd25519KeyHash::from(*key_hash).to_bytes()
+  }
+
+  #[test]
+  fn verification_reports_missing_signers() {
+    let first = RPrivateKey::from_normal_bytes(&[1; 32]).unwrap();
+    let second = RPrivateKey::from_normal_bytes(&[2; 32]).unwrap();
+    let second_hash = second.to_public().hash().to_bytes();
+    // Body requiring both keys as signers
+    let body = [
+      hex("a40080018002000e82581c"),
+      first.to_public().hash().to_bytes(),
+      hex("581c"),
+      second_hash.clone(),
+    ]
+    .concat();
+    let body_hash = rhash_transaction(&RTransactionBody::from_bytes(body.clone()).unwrap());
+    let other_body = RTransactionBody::from_bytes(hex(BODY)).unwrap();
+    let other_hash = rhash_transaction(&other_body);
+    // Second key signed another body
+    let mut vkeys = RVkeywitnesses::new();
+    vkeys.add(&make_vkey_witness(&body_hash, &first));
+    vkeys.add(&make_vkey_witness(&other_hash, &second));
+    let mut witness_set = RTransactionWitnessSet::new();
+    witness_set.set_vkeys(&vkeys);
+    let bytes = [hex("84"), body, witness_set.to_bytes(), hex("f5f6")].concat();
+    let mut transaction = Transaction::from_cbor(&bytes).unwrap();
+    let mut verification = transaction.verify_witnesses(&[]).unwrap();
+    let vkeywitnesses = unsafe { verification.vkeywitnesses.unowned().unwrap() };
+    let valid: Vec<bool> = vkeywitnesses.iter().map(|witness| witness.is_valid).collect();
+    assert_eq!(valid, vec![true, false]);
+    let missing = unsafe { verification.missing_signers.unowned().unwrap() };
+    assert_eq!(missing.iter().map(key_hash_bytes).collect::<Vec<_>>(), vec![second_hash]);
+    unsafe {
+      verification.free();
+      transaction.free();
+    }
+  }
+
+  #[test]
+  fn verification_reports_owners_of_spent_inputs() {
+    use cardano_serialization_lib::address::{
+      EnterpriseAddress as REnterpriseAddress, StakeCredential as RStakeCredential,
+    };
+    let key = RPrivateKey::from_normal_bytes(&[1; 32]).unwrap();
+    let hash = key.to_public().hash();
+    let address = REnterpriseAddress::new(0, &RStakeCredential::from_keyhash(&hash));
+    let input = RTransactionInput::new(&RTransactionHash::from_bytes(vec![7; 32]).unwrap(), 0);
+    let output = RTransactionOutput::new(&address.to_address(), &RValue::new(&to_bignum(1)));
+    let mut utxo: TransactionUnspentOutput =
+      RTransactionUnspentOutput::new(&input, &output).try_into().unwrap();
+    // Body spending the output of the key
+    let bytes = [hex("84a3008182582007"), vec![7; 31], hex("0001800200a0f5f6")].concat();
+    let mut transaction = Transaction::from_cbor(&bytes).unwrap();
+    let mut verification = transaction.verify_witnesses(&[utxo]).unwrap();
+    let missing = unsafe { verification.missing_signers.unowned().unwrap() };
+    assert_eq!(missing.iter().map(key_hash_bytes).collect::<Vec<_>>(), vec![hash.to_bytes()]);
+    let mut signed = transaction.sign(&[key.try_into().unwrap()]).unwrap();
+    let mut signed_verification = signed.verify_witnesses(&[utxo]).unwrap();
+    assert!(unsafe { signed_verification.missing_signers.unowned().unwrap() }.is_empty());
+    unsafe {
+      verification.free();
+      signed_verification.free();
+      transaction.free();
+      signed.free();
+      utxo.free();
+    }
+  }
+
+  #[test]
+  fn bip32_signing_uses_bootstrap_witnesses_for_byron_inputs() {
+    let root = RBip32PrivateKey::from_bip39_entropy(&[1; 16], &[]);
+    let key = root.derive(0x8000_0000).derive(0).to_public();
+    let byron = RByronAddress::icarus_from_key(&key, 764824073).to_address();
+    let input = RTransactionInput::new(&RTransactionHash::from_bytes(vec![7; 32]).unwrap(), 0);
+    let output = RTransactionOutput::new(&byron, &RValue::new(&to_bignum(1_000_000)));
+    let utxo = RTransactionUnspentOutput::new(&input, &output).try_into().unwrap();
+    // Body spending the Byron output
+    let bytes = [hex("84a3008182582007"), vec![7; 31], hex("0001800200a0f5f6")].concat();
+    let mut transaction = Transaction::from_cbor(&bytes).unwrap();
+    let paths: Vec<DerivationPath> =
+      vec![vec![0x8000_0000u32, 0].into(), vec![0x8000_0000u32, 1].into()];
+    let mut signed = transaction.sign_bip32(root.into(), &paths, &[utxo]).unwrap();
+    let entries = map_entries(&signed.witness_set.to_cbor().unwrap()).unwrap();
+    let counts: Vec<(u64, usize)> = entries
+      .iter()
+      .map(|(key, items)| (*key, array_items(items).unwrap().len()))
+      .collect();
+    assert_eq!(counts, vec![(0, 1), (2, 1)]);
+    let mut verification = signed.verify_witnesses(&[utxo]).unwrap();
+    let vkeywitnesses = unsafe { verification.vkeywitnesses.unowned().unwrap() };
+    assert!(vkeywitnesses[0].is_valid);
+    unsafe {
+      verification.free();
+      transaction.free();
+      signed.free();
+    }
+  }
+}
@@ -1,6 +1,7 @@
 use crate::array::*;
 use crate::asset_name::AssetName;
-use crate::certificate::Certificates;
+use crate::cbor::*;
+use crate::certificate::{legacy_cert_keys, Certificates};
 use crate::data::CData;
 use crate::error::CError;
 use crate::genesis_key_delegation::GenesisHash;
@@ -9,19 +10,28 @@ use crate::linear_fee::Coin;
 use crate::multi_asset::PolicyID;
 use crate::option::COption;
 use crate::panic::*;
+use crate::proposal_procedure::ProposalProcedures;
 use crate::protocol_param_update::ProtocolParamUpdate;
 use crate::ptr::*;
 use crate::transaction_input::TransactionInputs;
-use crate::transaction_output::TransactionOutputs;
+use crate::transaction_output::{TransactionOutput, TransactionOutputs};
+use crate::voting_procedure::VotingProcedures;
 use crate::withdrawals::Withdrawals;
 use crate::{address::pointer::Slot, stake_credential::Ed25519KeyHashes};
+use crate::stake_credential::StakeCredential;
 use cardano_serialization_lib::{
-  crypto::{AuxiliaryDataHash as RAuxiliaryDataHash, ScriptDataHash as RScriptDataHash},
+  address::RewardAddress as RRewardAddress,
+  crypto::{
+    AuxiliaryDataHash as RAuxiliaryDataHash, Ed25519KeyHash as REd25519KeyHash,
+    ScriptDataHash as RScriptDataHash,
+  },
   utils::{from_bignum, to_bignum},
   Mint as RMint, MintAssets as RMintAssets, NetworkId as RNetworkId, NetworkIdKind,
   ProposedProtocolParameterUpdates as RProposedProtocolParameterUpdates,
-  TransactionBody as RTransactionBody, Update as RUpdate,
+  Certificates as RCertificates, TransactionBody as RTransactionBody,
+  TransactionInput as RTransactionInput, Update as RUpdate,
 };
+use std::collections::BTreeSet;
 use std::convert::{TryFrom, TryInto};
 
 pub type Epoch = u32;
@@ -175,7 +185,7 @@ impl TryFrom<MintAssets> for RMintAssets {
     let mut mint_assets = RMintAssets::new();
     for (asset_name, int) in map {
       let asset_name = asset_name.try_into()?;
-      mint_assets.insert(&asset_name, int.into());
+      mint_assets.insert(&asset_name, int.try_into()?);
     }
     Ok(mint_assets)
   }
@@ -192,8 +202,9 @@ impl TryFrom<RMintAssets> for MintAssets {
           mint_assets
             .get(&asset_name)
             .ok_or("Cannot get Int by AssetName".into())
+            .and_then(|int| int.try_into())
             .zip(asset_name.try_into())
-            .map(|(int, asset_name)| (asset_name, int.into()).into())
+            .map(|(int, asset_name)| (asset_name, int).into())
         })
         .collect::<Result<Vec<MintAssetsKeyValue>>>()
         .map(|mint_assets| mint_assets.into())
@@ -333,6 +344,13 @@ pub struct TransactionBody {
   collateral: COption<TransactionInputs>,
   required_signers: COption<RequiredSigners>,
   network_id: COption<NetworkId>,
+  collateral_return: COption<TransactionOutput>,
+  total_collateral: COption<Coin>,
+  reference_inputs: COption<TransactionInputs>,
+  voting_procedures: COption<VotingProcedures>,
+  proposal_procedures: COption<ProposalProcedures>,
+  current_treasury_value: COption<Coin>,
+  donation: COption<Coin>,
 }
 
 impl Free for TransactionBody {
@@ -345,6 +363,10 @@ impl Free for TransactionBody {
     self.mint.free();
     self.collateral.free();
     self.required_signers.free();
+    self.collateral_return.free();
+    self.reference_inputs.free();
+    self.voting_procedures.free();
+    self.proposal_procedures.free();
   }
 }
 
@@ -444,23 +466,182 @@ impl TryFrom<RTransactionBody> for TransactionBody {
             collateral: collateral.into(),
             required_signers: r_signers.into(),
             network_id: tb.network_id().map(|network_id| network_id.into()).into(),
+            collateral_return: COption::None,
+            total_collateral: COption::None,
+            reference_inputs: COption::None,
+            voting_procedures: COption::None,
+            proposal_procedures: COption::None,
+            current_treasury_value: COption::None,
+            donation: COption::None,
           }
         },
       )
   }
 }
 
+// Body keys holding sets, which Conway allows to be tagged
+const SET_KEYS: [u64; 4] = [0, 4, 13, 14];
+
+impl TransactionBody {
+  // Inputs and collateral, spending both needs the signatures of their owners
+  pub fn spent_inputs(&self) -> Result<BTreeSet<RTransactionInput>> {
+    let mut inputs: BTreeSet<RTransactionInput> =
+      unsafe { self.inputs.unowned()? }.iter().map(|input| (*input).into()).collect();
+    let collateral: Option<TransactionInputs> = self.collateral.into();
+    if let Some(collateral) = collateral {
+      inputs.extend(unsafe { collateral.unowned()? }.iter().map(|input| (*input).into()));
+    }
+    Ok(inputs)
+  }
+
+  // Keys the body itself asks signatures from: required signers, certificates,
+  // withdrawals and voters. Owners of the spent inputs aren't known here.
+  pub fn required_key_hashes(&self) -> Result<BTreeSet<REd25519KeyHash>> {
+    let mut keys = BTreeSet::new();
+    let required_signers: Option<RequiredSigners> = self.required_signers.into();
+    if let Some(required_signers) = required_signers {
+      keys.extend(unsafe { required_signers.unowned()? }.iter().map(|hash| (*hash).into()));
+    }
+    let certs: Option<Certificates> = self.certs.into();
+    if let Some(certs) = certs {
+      let mut legacy = RCertificates::new();
+      for cert in unsafe { certs.unowned()? } {
+        match cert.conway_credential() {
+          Some(StakeCredential::Key(hash)) => {
+            keys.insert(hash.into());
+          }
+          Some(StakeCredential::Script(_)) => {}
+          None => legacy.add(&(*cert).try_into()?),
+        }
+      }
+      legacy_cert_keys(&legacy, &mut keys);
+    }
+    let withdrawals: Option<Withdrawals> = self.withdrawals.into();
+    if let Some(withdrawals) = withdrawals {
+      for withdrawal in unsafe { withdrawals.unowned()? } {
+        let address: RRewardAddress = withdrawal.key.into();
+        keys.extend(address.payment_cred().to_keyhash());
+      }
+    }
+    let voting_procedures: Option<VotingProcedures> = self.voting_procedures.into();
+    if let Some(voting_procedures) = voting_procedures {
+      let voters = unsafe { voting_procedures.unowned()? };
+      let voter_keys = voters.iter().filter_map(|voter| voter.key.key_hash());
+      keys.extend(voter_keys.map(|hash| hash.into()));
+    }
+    Ok(keys)
+  }
+}
+
+impl CborSerialize for TransactionBody {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    // cardano-serialization-lib encodes everything except the keys it doesn't know
+    let mut legacy = *self;
+    legacy.certs = COption::None;
+    let legacy: RTransactionBody = legacy.try_into()?;
+    let mut entries = map_entries(&legacy.to_bytes())?;
+    take_entry(&mut entries, 1);
+    entries.push((1, self.outputs.to_cbor()?));
+    let certs: Option<Certificates> = self.certs.into();
+    if let Some(certs) = certs {
+      entries.push((4, certs.to_cbor()?));
+    }
+    let collateral_return: Option<TransactionOutput> = self.collateral_return.into();
+    if let Some(collateral_return) = collateral_return {
+      entries.push((16, collateral_return.to_cbor()?));
+    }
+    let total_collateral: Option<Coin> = self.total_collateral.into();
+    if let Some(total_collateral) = total_collateral {
+      entries.push((17, total_collateral.to_cbor()?));
+    }
+    let reference_inputs: Option<TransactionInputs> = self.reference_inputs.into();
+    if let Some(reference_inputs) = reference_inputs {
+      entries.push((18, reference_inputs.to_cbor()?));
+    }
+    let voting_procedures: Option<VotingProcedures> = self.voting_procedures.into();
+    if let Some(voting_procedures) = voting_procedures {
+      entries.push((19, voting_procedures.to_cbor()?));
+    }
+    let proposal_procedures: Option<ProposalProcedures> = self.proposal_procedures.into();
+    if let Some(proposal_procedures) = proposal_procedures {
+      entries.push((20, proposal_procedures.to_cbor()?));
+    }
+    let current_treasury_value: Option<Coin> = self.current_treasury_value.into();
+    if let Some(current_treasury_value) = current_treasury_value {
+      entries.push((21, current_treasury_value.to_cbor()?));
+    }
+    let donation: Option<Coin> = self.donation.into();
+    if let Some(donation) = donation {
+      entries.push((22, donation.to_cbor()?));
+    }
+    se.write_raw_bytes(&encode_map(entries)?)?;
+    Ok(())
+  }
+}
+
+impl CborDeserialize for TransactionBody {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    let mut entries = map_entries(&raw_item(de)?)?;
+    // Outputs may be in the post-Alonzo format, so the library gets an empty list
+    let outputs = take_entry(&mut entries, 1)
+      .ok_or("TransactionBody outputs are missing")
+      .map(|outputs| TransactionOutputs::from_cbor(&outputs))??;
+    entries.push((1, encode_array(Vec::new())?));
+    let certs = take_entry(&mut entries, 4)
+      .map(|certs| Certificates::from_cbor(&certs))
+      .transpose()?;
+    let collateral_return = take_entry(&mut entries, 16)
+      .map(|collateral_return| TransactionOutput::from_cbor(&collateral_return))
+      .transpose()?;
+    let total_collateral = take_entry(&mut entries, 17)
+      .map(|total_collateral| Coin::from_cbor(&total_collateral))
+      .transpose()?;
+    let reference_inputs = take_entry(&mut entries, 18)
+      .map(|reference_inputs| TransactionInputs::from_cbor(&reference_inputs))
+      .transpose()?;
+    let voting_procedures = take_entry(&mut entries, 19)
+      .map(|voting_procedures| VotingProcedures::from_cbor(&voting_procedures))
+      .transpose()?;
+    let proposal_procedures = take_entry(&mut entries, 20)
+      .map(|proposal_procedures| ProposalProcedures::from_cbor(&proposal_procedures))
+      .transpose()?;
+    let current_treasury_value = take_entry(&mut entries, 21)
+      .map(|current_treasury_value| Coin::from_cbor(&current_treasury_value))
+      .transpose()?;
+    let donation = take_entry(&mut entries, 22)
+      .map(|donation| Coin::from_cbor(&donation))
+      .transpose()?;
+    let entries = entries
+      .into_iter()
+      .map(|(key, value)| match SET_KEYS.contains(&key) {
+        true => (key, strip_set_tag(value)),
+        false => (key, value),
+      })
+      .collect();
+    RTransactionBody::from_bytes(encode_map(entries)?)
+      .into_result()
+      .and_then(|transaction_body| transaction_body.try_into())
+      .map(|transaction_body: Self| Self {
+        outputs,
+        certs: certs.into(),
+        collateral_return: collateral_return.into(),
+        total_collateral: total_collateral.into(),
+        reference_inputs: reference_inputs.into(),
+        voting_procedures: voting_procedures.into(),
+        proposal_procedures: proposal_procedures.into(),
+        current_treasury_value: current_treasury_value.into(),
+        donation: donation.into(),
+        ..transaction_body
+      })
+  }
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn cardano_transaction_body_to_bytes(
   transaction_body: TransactionBody, result: &mut CData, error: &mut CError,
 ) -> bool {
-  handle_exception_result(|| {
-    transaction_body
-      .try_into()
-      .map(|transaction_body: RTransactionBody| transaction_body.to_bytes())
-      .map(|bytes| bytes.into())
-  })
-  .response(result, error)
+  handle_exception_result(|| transaction_body.to_cbor().map(|bytes| bytes.into()))
+    .response(result, error)
 }
 
 #[no_mangle]
@@ -470,8 +651,7 @@ pub unsafe extern "C" fn cardano_transaction_body_from_bytes(
   handle_exception_result(|| {
     data
       .unowned()
-      .and_then(|bytes| RTransactionBody::from_bytes(bytes.to_vec()).into_result())
-      .and_then(|transaction_body| transaction_body.try_into())
+      .and_then(TransactionBody::from_cbor)
   })
   .response(result, error)
 }
@@ -2,41 +2,75 @@ use crate::address::address::Address;
 use crate::address::byron::ByronAddress;
 use crate::address::pointer::Slot;
 use crate::array::*;
-use crate::certificate::Certificates;
+use crate::cbor::*;
+use crate::certificate::{legacy_cert_keys, Certificates};
 use crate::data::CData;
 use crate::error::CError;
+use crate::general_transaction_metadata::{GeneralTransactionMetadata, TransactionMetadatum};
 use crate::linear_fee::{Coin, LinearFee};
 use crate::option::COption;
 use crate::panic::*;
+use crate::proposal_procedure::ProposalProcedures;
+use crate::protocol_param_update::{language_views, Costmdls, ExUnitPrices, Language, SubCoin};
+use crate::protocol_parameters::ProtocolParameters;
 use crate::ptr::*;
-use crate::stake_credential::{Ed25519KeyHash, ScriptHash};
-use crate::transaction_body::{Mint, TransactionBody};
+use crate::stake_credential::{Ed25519KeyHash, ScriptHash, StakeCredential};
+use crate::transaction_body::{Mint, RequiredSigners, TransactionBody};
 use crate::transaction_input::TransactionInput;
-use crate::transaction_metadata::{AuxiliaryData, NativeScripts};
-use crate::transaction_output::{TransactionOutput, TransactionOutputs};
-use crate::transaction_unspent_output::TransactionUnspentOutputs;
+use crate::transaction_metadata::{
+  plutus_script_entries, plutus_script_hash, AuxiliaryData, NativeScripts, PlutusScript,
+};
+use crate::transaction_metadatum_labels::TransactionMetadatumLabel;
+use crate::transaction_output::{
+  output_min_ada_required, output_min_ada_required_per_byte, ScriptRef, TransactionOutput,
+  TransactionOutputs,
+};
+use crate::transaction_unspent_output::{TransactionUnspentOutput, TransactionUnspentOutputs};
+use crate::transaction_witness_set::{PlutusData, Redeemer, TransactionWitnessSet};
 use crate::value::Value;
+use crate::voting_procedure::VotingProcedures;
 use crate::withdrawals::Withdrawals;
 use cardano_serialization_lib::{
-  address::{Address as RAddress, ByronAddress as RByronAddress},
-  crypto::{Ed25519KeyHash as REd25519KeyHash, ScriptHash as RScriptHash},
+  address::{
+    Address as RAddress, BaseAddress as RBaseAddress, ByronAddress as RByronAddress,
+    EnterpriseAddress as REnterpriseAddress, PointerAddress as RPointerAddress,
+  },
+  crypto::{
+    Ed25519KeyHash as REd25519KeyHash, ScriptDataHash as RScriptDataHash,
+    ScriptHash as RScriptHash,
+  },
   fees::LinearFee as RLinearFee,
-  metadata::AuxiliaryData as RAuxiliaryData,
+  metadata::{
+    AuxiliaryData as RAuxiliaryData, GeneralTransactionMetadata as RGeneralTransactionMetadata,
+    TransactionMetadatum as RTransactionMetadatum,
+  },
+  plutus::{
+    ExUnitPrices as RExUnitPrices, PlutusData as RPlutusData, PlutusList as RPlutusList,
+    Redeemer as RRedeemer, RedeemerTag as RRedeemerTag, Redeemers as RRedeemers,
+  },
   tx_builder::{
     CoinSelectionStrategyCIP2 as RCoinSelectionStrategyCIP2,
     TransactionBuilder as RTransactionBuilder,
     TransactionBuilderConfig as RTransactionBuilderConfig,
+    TransactionBuilderConfigBuilder as RTransactionBuilderConfigBuilder,
   },
   utils::{
     from_bignum, to_bignum, BigNum as RBigNum, Coin as RCoin,
+    TransactionUnspentOutput as RTransactionUnspentOutput,
     TransactionUnspentOutputs as RTransactionUnspentOutputs, Value as RValue,
   },
-  Certificates as RCertificates, Mint as RMint, NativeScripts as RNativeScripts,
-  TransactionInput as RTransactionInput, TransactionOutput as RTransactionOutput,
-  TransactionOutputs as RTransactionOutputs, Withdrawals as RWithdrawals,
+  UnitInterval as RUnitInterval,
+  Certificates as RCertificates, Ed25519KeyHashes as REd25519KeyHashes, Mint as RMint,
+  NativeScripts as RNativeScripts, RequiredSignersSet, TransactionBody as RTransactionBody,
+  TransactionInput as RTransactionInput, TransactionInputs as RTransactionInputs,
+  TransactionOutput as RTransactionOutput, TransactionWitnessSet as RTransactionWitnessSet,
+  Withdrawals as RWithdrawals,
 };
-use std::collections::BTreeSet;
+use cryptoxide::blake2b::Blake2b;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
 use std::convert::{TryFrom, TryInto};
+use std::ops::Deref;
 
 pub type BigNum = u64;
 
@@ -48,6 +82,10 @@ pub struct MockWitnessSet {
   bootstraps: CArray<CData>,
 }
 
+const _: () = assert!(
+  std::mem::size_of::<MockWitnessSet>() == 48 && std::mem::align_of::<MockWitnessSet>() == 8
+);
+
 impl Free for MockWitnessSet {
   unsafe fn free(&mut self) {
     self.vkeys.free();
@@ -56,7 +94,8 @@ impl Free for MockWitnessSet {
   }
 }
 
-// for transmute
+// Rust side of MockWitnessSet, mirrors the builder input types
+#[derive(Clone)]
 struct TMockWitnessSet {
   vkeys: BTreeSet<REd25519KeyHash>,
   scripts: BTreeSet<RScriptHash>,
@@ -131,6 +170,113 @@ pub unsafe extern "C" fn cardano_mock_witness_set_free(mock_witness_set: &mut Mo
   mock_witness_set.free()
 }
 
+// Witnesses the signed transaction is going to hold
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct ExpectedWitnesses {
+  vkeys: CArray<Ed25519KeyHash>,
+  bootstraps: CArray<ByronAddress>,
+  native_scripts: NativeScripts,
+}
+
+impl Free for ExpectedWitnesses {
+  unsafe fn free(&mut self) {
+    self.vkeys.free();
+    self.bootstraps.free();
+    self.native_scripts.free();
+  }
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc, clippy::clone_on_copy)]
+pub unsafe extern "C" fn cardano_expected_witnesses_clone(
+  expected_witnesses: ExpectedWitnesses, result: &mut ExpectedWitnesses, error: &mut CError,
+) -> bool {
+  handle_exception(|| expected_witnesses.clone()).response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_expected_witnesses_free(
+  expected_witnesses: &mut ExpectedWitnesses,
+) {
+  expected_witnesses.free()
+}
+
+// Rust side of ExpectedWitnesses, as witness set entries of the exact size
+struct TExpectedWitnesses {
+  vkeys: BTreeSet<REd25519KeyHash>,
+  // Attributes of the Byron addresses, one witness per distinct address
+  bootstraps: BTreeMap<Vec<u8>, Vec<u8>>,
+  native_scripts: BTreeSet<Vec<u8>>,
+}
+
+impl TryFrom<ExpectedWitnesses> for TExpectedWitnesses {
+  type Error = CError;
+
+  #[allow(clippy::clone_on_copy)]
+  fn try_from(expected_witnesses: ExpectedWitnesses) -> Result<Self> {
+    let vkeys = unsafe { expected_witnesses.vkeys.unowned()? };
+    let bootstraps = unsafe { expected_witnesses.bootstraps.unowned()? };
+    let native_scripts: RNativeScripts = expected_witnesses.native_scripts.try_into()?;
+    let vkeys = vkeys.iter().map(|vkey| (*vkey).into()).collect();
+    let bootstraps = bootstraps
+      .iter()
+      .map(|address| RByronAddress::try_from(address.clone()))
+      .map(|address| address.map(|address| (address.to_bytes(), address.attributes())))
+      .collect::<Result<BTreeMap<Vec<u8>, Vec<u8>>>>()?;
+    let native_scripts = (0..native_scripts.len())
+      .map(|index| native_scripts.get(index).to_bytes())
+      .collect();
+    Ok(Self {
+      vkeys,
+      bootstraps,
+      native_scripts,
+    })
+  }
+}
+
+impl TExpectedWitnesses {
+  // Witness set entries with zeroed keys and signatures
+  fn entries(&self) -> Result<Vec<(u64, Vec<u8>)>> {
+    let vkey = vec![0u8; 32].to_cbor()?;
+    let signature = vec![0u8; 64].to_cbor()?;
+    let chain_code = vec![0u8; 32].to_cbor()?;
+    let mut entries = Vec::new();
+    if !self.vkeys.is_empty() {
+      let vkeys = self
+        .vkeys
+        .iter()
+        .map(|_| encode_array(vec![vkey.clone(), signature.clone()]))
+        .collect::<Result<Vec<Vec<u8>>>>()?;
+      entries.push((0, encode_array(vkeys)?));
+    }
+    if !self.native_scripts.is_empty() {
+      entries.push((1, encode_array(self.native_scripts.iter().cloned().collect())?));
+    }
+    if !self.bootstraps.is_empty() {
+      let bootstraps = self
+        .bootstraps
+        .values()
+        .map(|attributes| {
+          attributes.clone().to_cbor().and_then(|attributes| {
+            encode_array(vec![vkey.clone(), signature.clone(), chain_code.clone(), attributes])
+          })
+        })
+        .collect::<Result<Vec<Vec<u8>>>>()?;
+      entries.push((2, encode_array(bootstraps)?));
+    }
+    Ok(entries)
+  }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct FeeEstimate {
+  fee: Coin,
+  size: u32,
+}
+
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub struct TransactionBuilderConfig {
@@ -141,58 +287,84 @@ pub struct TransactionBuilderConfig {
   max_tx_size: u32,
   coins_per_utxo_word: Coin,
   prefer_pure_change: bool,
+  ex_unit_prices: COption<ExUnitPrices>,
+  cost_models: COption<Costmdls>,
+  ref_script_coins_per_byte: COption<SubCoin>,
+  // Babbage min ADA by output size, which replaces coins per word when set
+  coins_per_utxo_byte: COption<Coin>,
 }
 
-// for transmute
-pub struct TTransactionBuilderConfig {
-  fee_algo: RLinearFee,
-  pool_deposit: RBigNum,
-  key_deposit: RBigNum,
-  max_value_size: u32,
-  max_tx_size: u32,
-  coins_per_utxo_word: RCoin,
-  prefer_pure_change: bool,
-}
+// Swift mirrors the config field by field, see TransactionBuilder.swift
+const _: () = assert!(
+  std::mem::size_of::<TransactionBuilderConfig>() == 160
+    && std::mem::align_of::<TransactionBuilderConfig>() == 8
+);
 
-impl From<TransactionBuilderConfig> for TTransactionBuilderConfig {
-  fn from(transaction_builder_config: TransactionBuilderConfig) -> Self {
-    Self {
-      fee_algo: transaction_builder_config.fee_algo.into(),
-      pool_deposit: to_bignum(transaction_builder_config.pool_deposit),
-      key_deposit: to_bignum(transaction_builder_config.key_deposit),
-      max_value_size: transaction_builder_config.max_value_size,
-      max_tx_size: transaction_builder_config.max_tx_size,
-      coins_per_utxo_word: to_bignum(transaction_builder_config.coins_per_utxo_word),
-      prefer_pure_change: transaction_builder_config.prefer_pure_change,
-    }
+impl Free for TransactionBuilderConfig {
+  unsafe fn free(&mut self) {
+    self.cost_models.free()
   }
 }
 
-impl From<TTransactionBuilderConfig> for TransactionBuilderConfig {
-  fn from(transaction_builder_config: TTransactionBuilderConfig) -> Self {
-    Self {
-      fee_algo: transaction_builder_config.fee_algo.into(),
-      pool_deposit: from_bignum(&transaction_builder_config.pool_deposit),
-      key_deposit: from_bignum(&transaction_builder_config.key_deposit),
-      max_value_size: transaction_builder_config.max_value_size,
-      max_tx_size: transaction_builder_config.max_tx_size,
-      coins_per_utxo_word: from_bignum(&transaction_builder_config.coins_per_utxo_word),
-      prefer_pure_change: transaction_builder_config.prefer_pure_change,
-    }
+impl TransactionBuilderConfig {
+  // Config of the builder for the given protocol parameters, with its own cost models
+  pub fn from_protocol_parameters(
+    params: &ProtocolParameters, prefer_pure_change: bool,
+  ) -> Result<Self> {
+    let cost_models = params.cost_models()?;
+    Ok(Self {
+      fee_algo: LinearFee {
+        constant: params.min_fee_b(),
+        coefficient: params.min_fee_a(),
+      },
+      pool_deposit: params.pool_deposit(),
+      key_deposit: params.key_deposit(),
+      max_value_size: params.max_value_size(),
+      max_tx_size: params.max_tx_size(),
+      // replaced by coins per byte
+      coins_per_utxo_word: 0,
+      prefer_pure_change,
+      ex_unit_prices: Some(params.execution_costs()).into(),
+      cost_models: Some(cost_models.into()).into(),
+      ref_script_coins_per_byte: Some(params.min_fee_ref_script_cost_per_byte()).into(),
+      coins_per_utxo_byte: Some(params.coins_per_utxo_byte()).into(),
+    })
+  }
+
+  fn min_ada_of(&self, output: &TTransactionOutput) -> Result<Coin> {
+    let mut output: TransactionOutput = output.clone().try_into()?;
+    let min_ada = self.min_ada_required(&output);
+    unsafe { output.free() };
+    min_ada
   }
-}
 
-impl From<TransactionBuilderConfig> for RTransactionBuilderConfig {
-  fn from(transaction_builder_config: TransactionBuilderConfig) -> Self {
-    let tbc: TTransactionBuilderConfig = transaction_builder_config.into();
-    unsafe { std::mem::transmute(tbc) }
+  pub fn min_ada_required(&self, output: &TransactionOutput) -> Result<Coin> {
+    let coins_per_utxo_byte: Option<Coin> = self.coins_per_utxo_byte.into();
+    match coins_per_utxo_byte {
+      Some(coins_per_utxo_byte) => output_min_ada_required_per_byte(output, coins_per_utxo_byte),
+      None => output_min_ada_required(output, self.coins_per_utxo_word),
+    }
   }
 }
 
-impl From<RTransactionBuilderConfig> for TransactionBuilderConfig {
-  fn from(transaction_builder_config: RTransactionBuilderConfig) -> Self {
-    let tbc: TTransactionBuilderConfig = unsafe { std::mem::transmute(transaction_builder_config) };
-    tbc.into()
+// CSL sizes outputs as Alonzo did, so its min ADA check is made to pass and
+// the builder checks the min ADA of the outputs and the change itself
+const CSL_COINS_PER_UTXO_WORD: Coin = 1;
+
+impl TryFrom<TransactionBuilderConfig> for RTransactionBuilderConfig {
+  type Error = CError;
+
+  fn try_from(transaction_builder_config: TransactionBuilderConfig) -> Result<Self> {
+    RTransactionBuilderConfigBuilder::new()
+      .fee_algo(&transaction_builder_config.fee_algo.into())
+      .pool_deposit(&to_bignum(transaction_builder_config.pool_deposit))
+      .key_deposit(&to_bignum(transaction_builder_config.key_deposit))
+      .max_value_size(transaction_builder_config.max_value_size)
+      .max_tx_size(transaction_builder_config.max_tx_size)
+      .coins_per_utxo_word(&to_bignum(CSL_COINS_PER_UTXO_WORD))
+      .prefer_pure_change(transaction_builder_config.prefer_pure_change)
+      .build()
+      .into_result()
   }
 }
 
@@ -201,6 +373,7 @@ impl From<RTransactionBuilderConfig> for TransactionBuilderConfig {
 pub struct TxBuilderInput {
   input: TransactionInput,
   amount: Value,
+  script_size: usize,
 }
 
 impl Free for TxBuilderInput {
@@ -209,10 +382,12 @@ impl Free for TxBuilderInput {
   }
 }
 
-// for transmute
+// Rust side of TxBuilderInput
+#[derive(Clone)]
 struct TTxBuilderInput {
   input: RTransactionInput,
   amount: RValue,
+  script_size: usize,
 }
 
 impl TryFrom<TxBuilderInput> for TTxBuilderInput {
@@ -222,6 +397,7 @@ impl TryFrom<TxBuilderInput> for TTxBuilderInput {
     tx_builder_input.amount.try_into().map(|amount| Self {
       input: tx_builder_input.input.into(),
       amount,
+      script_size: tx_builder_input.script_size,
     })
   }
 }
@@ -230,11 +406,16 @@ impl TryFrom<TTxBuilderInput> for TxBuilderInput {
   type Error = CError;
 
   fn try_from(tx_builder_input: TTxBuilderInput) -> Result<Self> {
+    let script_size = tx_builder_input.script_size;
     tx_builder_input
       .input
       .try_into()
       .zip(tx_builder_input.amount.try_into())
-      .map(|(input, amount)| Self { input, amount })
+      .map(|(input, amount)| Self {
+        input,
+        amount,
+        script_size,
+      })
   }
 }
 
@@ -250,6 +431,239 @@ pub unsafe extern "C" fn cardano_tx_builder_input_free(tx_builder_input: &mut Tx
   tx_builder_input.free()
 }
 
+// Witness kind of a builder input, the bootstrap one holds the Byron address bytes
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub enum TxBuilderInputKind {
+  KeyInputKind(Ed25519KeyHash),
+  ScriptInputKind(ScriptHash),
+  BootstrapInputKind(CData),
+}
+
+impl Free for TxBuilderInputKind {
+  unsafe fn free(&mut self) {
+    if let TxBuilderInputKind::BootstrapInputKind(bytes) = self {
+      bytes.free()
+    }
+  }
+}
+
+// Rust side of TxBuilderInputKind
+#[derive(Clone)]
+enum TTxBuilderInputKind {
+  Key(REd25519KeyHash),
+  Script(RScriptHash),
+  Bootstrap(RByronAddress),
+}
+
+impl TryFrom<TxBuilderInputKind> for TTxBuilderInputKind {
+  type Error = CError;
+
+  fn try_from(kind: TxBuilderInputKind) -> Result<Self> {
+    match kind {
+      TxBuilderInputKind::KeyInputKind(hash) => Ok(Self::Key(hash.into())),
+      TxBuilderInputKind::ScriptInputKind(hash) => Ok(Self::Script(hash.into())),
+      TxBuilderInputKind::BootstrapInputKind(bytes) => {
+        let bytes = unsafe { bytes.unowned()? };
+        RByronAddress::from_bytes(bytes.to_vec()).into_result().map(Self::Bootstrap)
+      }
+    }
+  }
+}
+
+impl TryFrom<TTxBuilderInputKind> for TxBuilderInputKind {
+  type Error = CError;
+
+  fn try_from(kind: TTxBuilderInputKind) -> Result<Self> {
+    match kind {
+      TTxBuilderInputKind::Key(hash) => hash.try_into().map(Self::KeyInputKind),
+      TTxBuilderInputKind::Script(hash) => hash.try_into().map(Self::ScriptInputKind),
+      TTxBuilderInputKind::Bootstrap(byron) => {
+        Ok(Self::BootstrapInputKind(byron.to_bytes().into()))
+      }
+    }
+  }
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_tx_builder_input_kind_free(kind: &mut TxBuilderInputKind) {
+  kind.free()
+}
+
+// Script given in the witness set or held by a reference input of the builder
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub enum PlutusScriptSource {
+  PlutusScriptKind(PlutusScript),
+  RefInputKind(TransactionInput),
+}
+
+impl Free for PlutusScriptSource {
+  unsafe fn free(&mut self) {
+    if let PlutusScriptSource::PlutusScriptKind(plutus_script) = self {
+      plutus_script.free();
+    }
+  }
+}
+
+// Rust side of PlutusScript
+#[derive(Clone)]
+struct TPlutusScript {
+  bytes: Vec<u8>,
+  language: Language,
+}
+
+impl TPlutusScript {
+  fn hash(&self) -> RScriptHash {
+    plutus_script_hash(&self.bytes, self.language)
+  }
+}
+
+impl TryFrom<PlutusScript> for TPlutusScript {
+  type Error = CError;
+
+  fn try_from(plutus_script: PlutusScript) -> Result<Self> {
+    plutus_script.bytes().map(|bytes| Self {
+      bytes,
+      language: plutus_script.language(),
+    })
+  }
+}
+
+impl From<TPlutusScript> for PlutusScript {
+  fn from(plutus_script: TPlutusScript) -> Self {
+    Self::new(plutus_script.bytes, plutus_script.language)
+  }
+}
+
+#[derive(Clone)]
+enum TPlutusScriptSource {
+  Script(TPlutusScript),
+  RefInput(RTransactionInput),
+}
+
+impl TryFrom<PlutusScriptSource> for TPlutusScriptSource {
+  type Error = CError;
+
+  fn try_from(source: PlutusScriptSource) -> Result<Self> {
+    match source {
+      PlutusScriptSource::PlutusScriptKind(plutus_script) => {
+        plutus_script.try_into().map(Self::Script)
+      }
+      PlutusScriptSource::RefInputKind(input) => Ok(Self::RefInput(input.into())),
+    }
+  }
+}
+
+impl TryFrom<TPlutusScriptSource> for PlutusScriptSource {
+  type Error = CError;
+
+  fn try_from(source: TPlutusScriptSource) -> Result<Self> {
+    match source {
+      TPlutusScriptSource::Script(plutus_script) => {
+        Ok(Self::PlutusScriptKind(plutus_script.into()))
+      }
+      TPlutusScriptSource::RefInput(input) => input.try_into().map(Self::RefInputKind),
+    }
+  }
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc, clippy::clone_on_copy)]
+pub unsafe extern "C" fn cardano_plutus_script_source_clone(
+  plutus_script_source: PlutusScriptSource, result: &mut PlutusScriptSource, error: &mut CError,
+) -> bool {
+  handle_exception(|| plutus_script_source.clone()).response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_plutus_script_source_free(
+  plutus_script_source: &mut PlutusScriptSource,
+) {
+  plutus_script_source.free()
+}
+
+// Witness of a Plutus script input. Datum is omitted when the output holds it inline.
+// Tag and index of the redeemer are set by the builder.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct PlutusWitness {
+  script: PlutusScriptSource,
+  datum: COption<PlutusData>,
+  redeemer: Redeemer,
+}
+
+impl Free for PlutusWitness {
+  unsafe fn free(&mut self) {
+    self.script.free();
+    self.datum.free();
+    self.redeemer.free();
+  }
+}
+
+// Rust side of PlutusWitness
+#[derive(Clone)]
+struct TPlutusWitness {
+  script: TPlutusScriptSource,
+  datum: Option<RPlutusData>,
+  redeemer: RRedeemer,
+}
+
+impl TryFrom<PlutusWitness> for TPlutusWitness {
+  type Error = CError;
+
+  fn try_from(plutus_witness: PlutusWitness) -> Result<Self> {
+    let datum: Option<PlutusData> = plutus_witness.datum.into();
+    plutus_witness
+      .script
+      .try_into()
+      .zip(datum.map(|datum| datum.try_into()).transpose())
+      .zip(plutus_witness.redeemer.try_into())
+      .map(|((script, datum), redeemer)| Self {
+        script,
+        datum,
+        redeemer,
+      })
+  }
+}
+
+impl TryFrom<TPlutusWitness> for PlutusWitness {
+  type Error = CError;
+
+  fn try_from(plutus_witness: TPlutusWitness) -> Result<Self> {
+    let script = plutus_witness.script.try_into()?;
+    plutus_witness
+      .datum
+      .map(|datum| datum.try_into())
+      .transpose()
+      .zip(plutus_witness.redeemer.try_into())
+      .map(|(datum, redeemer): (Option<PlutusData>, Redeemer)| Self {
+        script,
+        datum: datum.into(),
+        redeemer,
+      })
+  }
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc, clippy::clone_on_copy)]
+pub unsafe extern "C" fn cardano_plutus_witness_clone(
+  plutus_witness: PlutusWitness, result: &mut PlutusWitness, error: &mut CError,
+) -> bool {
+  handle_exception(|| plutus_witness.clone()).response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_plutus_witness_free(plutus_witness: &mut PlutusWitness) {
+  plutus_witness.free()
+}
+
+pub type PlutusWitnessesKeyValue = CKeyValue<TransactionInput, PlutusWitness>;
+pub type PlutusWitnesses = CArray<PlutusWitnessesKeyValue>;
+
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub enum CoinSelectionStrategyCIP2 {
@@ -294,200 +708,1416 @@ pub struct TransactionBuilder {
   auxiliary_data: COption<AuxiliaryData>,
   validity_start_interval: COption<Slot>,
   input_types: MockWitnessSet,
+  // kind of each input, in the order of the inputs
+  input_kinds: CArray<TxBuilderInputKind>,
   mint: COption<Mint>,
   mint_scripts: COption<NativeScripts>,
+  voting_procedures: COption<VotingProcedures>,
+  proposal_procedures: COption<ProposalProcedures>,
+  current_treasury_value: COption<Coin>,
+  donation: COption<Coin>,
+  plutus_witnesses: PlutusWitnesses,
+  collateral: CArray<TxBuilderInput>,
+  collateral_vkeys: CArray<Ed25519KeyHash>,
+  collateral_return: COption<TransactionOutput>,
+  required_signers: COption<RequiredSigners>,
+  reference_inputs: TransactionUnspentOutputs,
 }
 
 impl Free for TransactionBuilder {
   unsafe fn free(&mut self) {
+    self.config.free();
     self.inputs.free();
     self.outputs.free();
     self.certs.free();
     self.withdrawals.free();
     self.auxiliary_data.free();
     self.input_types.free();
+    self.input_kinds.free();
     self.mint_scripts.free();
+    self.voting_procedures.free();
+    self.proposal_procedures.free();
+    self.plutus_witnesses.free();
+    self.collateral.free();
+    self.collateral_vkeys.free();
+    self.collateral_return.free();
+    self.required_signers.free();
+    self.reference_inputs.free();
   }
 }
 
-// for transmute
-pub struct TTransactionBuilder {
-  config: RTransactionBuilderConfig,
-  inputs: Vec<TTxBuilderInput>,
-  outputs: RTransactionOutputs,
-  fee: Option<RCoin>,
-  ttl: Option<Slot>,
-  certs: Option<RCertificates>,
-  withdrawals: Option<RWithdrawals>,
-  auxiliary_data: Option<RAuxiliaryData>,
-  validity_start_interval: Option<Slot>,
-  input_types: TMockWitnessSet,
-  mint: Option<RMint>,
-  mint_scripts: Option<RNativeScripts>,
+// Rust side of Certificates. CSL gets only the legacy ones, so
+// deposits, refunds and witnesses of the Conway ones are kept here.
+#[derive(Clone)]
+struct TCertificates {
+  bytes: Vec<u8>,
+  legacy: RCertificates,
+  deposit: Coin,
+  refund: Coin,
+  signers: BTreeSet<REd25519KeyHash>,
 }
 
-impl TryFrom<TransactionBuilder> for TTransactionBuilder {
+impl TryFrom<Certificates> for TCertificates {
   type Error = CError;
 
-  fn try_from(tb: TransactionBuilder) -> Result<Self> {
-    let inputs = unsafe { tb.inputs.unowned()? };
-    inputs
-      .to_vec()
-      .into_iter()
-      .map(|input| input.try_into())
-      .collect::<Result<Vec<TTxBuilderInput>>>()
-      .zip(tb.outputs.try_into())
-      .zip({
-        let certs: Option<Certificates> = tb.certs.into();
-        certs.map(|certs| certs.try_into()).transpose()
-      })
-      .zip({
-        let withdrawals: Option<Withdrawals> = tb.withdrawals.into();
-        withdrawals.map(|wls| wls.try_into()).transpose()
-      })
-      .zip({
-        let auxiliary_data: Option<AuxiliaryData> = tb.auxiliary_data.into();
-        auxiliary_data
-          .map(|auxiliary_data| auxiliary_data.try_into())
-          .transpose()
-      })
-      .zip(tb.input_types.try_into())
-      .zip({
-        let mint: Option<Mint> = tb.mint.into();
-        mint.map(|mint| mint.try_into()).transpose()
-      })
-      .zip({
-        let mint_scripts: Option<NativeScripts> = tb.mint_scripts.into();
-        mint_scripts
-          .map(|mint_scripts| mint_scripts.try_into())
-          .transpose()
-      })
-      .map(
-        |(
-          ((((((inputs, outputs), certs), withdrawals), auxiliary_data), input_types), mint),
-          mint_scripts,
-        )| {
-          let fee: Option<Coin> = tb.fee.into();
-          Self {
-            config: tb.config.into(),
-            inputs,
-            outputs,
-            fee: fee.map(|fee| to_bignum(fee)),
-            ttl: tb.ttl.into(),
-            certs: certs.into(),
-            withdrawals: withdrawals.into(),
-            auxiliary_data: auxiliary_data.into(),
-            validity_start_interval: tb.validity_start_interval.into(),
-            input_types,
-            mint: mint.into(),
-            mint_scripts: mint_scripts.into(),
+  fn try_from(certs: Certificates) -> Result<Self> {
+    let mut legacy = RCertificates::new();
+    let mut deposit: Coin = 0;
+    let mut refund: Coin = 0;
+    let mut signers = BTreeSet::new();
+    for cert in unsafe { certs.unowned()? } {
+      match cert.conway_credential() {
+        Some(credential) => {
+          deposit = deposit
+            .checked_add(cert.conway_deposit())
+            .ok_or("Certificates deposit is too big")?;
+          refund = refund
+            .checked_add(cert.conway_refund())
+            .ok_or("Certificates refund is too big")?;
+          if let StakeCredential::Key(hash) = credential {
+            signers.insert(hash.into());
           }
-        },
-      )
+        }
+        None => legacy.add(&(*cert).try_into()?),
+      }
+    }
+    certs.to_cbor().map(|bytes| Self {
+      bytes,
+      legacy,
+      deposit,
+      refund,
+      signers,
+    })
   }
 }
 
-impl TryFrom<TTransactionBuilder> for TransactionBuilder {
+// Rust side of TransactionOutput. CSL gets the legacy projection,
+// the body holds the output as encoded here.
+#[derive(Clone)]
+struct TTransactionOutput {
+  bytes: Vec<u8>,
+  legacy: RTransactionOutput,
+}
+
+impl TryFrom<TransactionOutput> for TTransactionOutput {
   type Error = CError;
 
-  fn try_from(tb: TTransactionBuilder) -> Result<Self> {
-    let config = tb.config.into();
-    let fee = tb.fee.map(|fee| from_bignum(&fee)).into();
-    let ttl = tb.ttl.into();
-    let validity_start_interval = tb.validity_start_interval.into();
-    tb.inputs
-      .into_iter()
-      .map(|input| input.try_into())
-      .collect::<Result<Vec<TxBuilderInput>>>()
-      .zip(tb.outputs.try_into())
-      .zip(tb.certs.map(|certs| certs.try_into()).transpose())
-      .zip(tb.withdrawals.map(|wls| wls.try_into()).transpose())
-      .zip(
-        tb.auxiliary_data
-          .map(|auxiliary_data| auxiliary_data.try_into())
-          .transpose(),
-      )
-      .zip(tb.input_types.try_into())
-      .zip(tb.mint.map(|mint| mint.try_into()).transpose())
-      .zip(
-        tb.mint_scripts
-          .map(|mint_scripts| mint_scripts.try_into())
-          .transpose(),
-      )
-      .map(
-        |(
-          ((((((inputs, outputs), certs), withdrawals), auxiliary_data), input_types), mint),
-          mint_scripts,
-        )| Self {
-          config,
-          inputs: inputs.into(),
-          outputs,
-          fee,
-          ttl,
-          certs: certs.into(),
-          withdrawals: withdrawals.into(),
-          auxiliary_data: auxiliary_data.into(),
-          validity_start_interval,
-          input_types,
-          mint: mint.into(),
-          mint_scripts: mint_scripts.into(),
-        },
-      )
+  fn try_from(output: TransactionOutput) -> Result<Self> {
+    output
+      .to_cbor()
+      .zip(output.try_into())
+      .map(|(bytes, legacy)| Self { bytes, legacy })
   }
 }
 
-impl TryFrom<TransactionBuilder> for RTransactionBuilder {
+impl TryFrom<TTransactionOutput> for TransactionOutput {
   type Error = CError;
 
-  fn try_from(transaction_builder: TransactionBuilder) -> Result<Self> {
-    transaction_builder
-      .try_into()
-      .map(|transaction_builder: TTransactionBuilder| unsafe {
-        std::mem::transmute(transaction_builder)
-      })
+  fn try_from(output: TTransactionOutput) -> Result<Self> {
+    Self::from_cbor(&output.bytes)
   }
 }
 
-impl TryFrom<RTransactionBuilder> for TransactionBuilder {
-  type Error = CError;
-
-  fn try_from(transaction_builder: RTransactionBuilder) -> Result<Self> {
-    let transaction_builder: TTransactionBuilder =
-      unsafe { std::mem::transmute(transaction_builder) };
-    transaction_builder.try_into()
+impl From<RTransactionOutput> for TTransactionOutput {
+  fn from(output: RTransactionOutput) -> Self {
+    Self {
+      bytes: output.to_bytes(),
+      legacy: output,
+    }
   }
 }
 
-#[no_mangle]
-pub unsafe extern "C" fn cardano_transaction_builder_add_inputs_from(
-  tb: TransactionBuilder, inputs: TransactionUnspentOutputs, strategy: CoinSelectionStrategyCIP2,
-  result: &mut TransactionBuilder, error: &mut CError,
-) -> bool {
-  handle_exception_result(|| {
-    tb.try_into()
-      .zip(inputs.try_into())
-      .and_then(
-        |(mut tb, inputs): (RTransactionBuilder, RTransactionUnspentOutputs)| {
-          tb.add_inputs_from(&inputs, strategy.into())
-            .into_result()
-            .zip(tb.try_into())
-        },
-      )
-      .map(|(_, tb)| tb)
-  })
-  .response(result, error)
+// Size of the script reference of an output, charged by the reference script fee
+pub(crate) fn script_ref_size(script_ref: Option<ScriptRef>) -> Result<usize> {
+  script_ref.map(|script_ref| script_ref.size()).transpose().map(|size| size.unwrap_or(0))
+}
+
+// Output of a reference input with the size and the Plutus script of its script reference
+#[derive(Clone)]
+struct TReferenceInput {
+  output: TTransactionOutput,
+  script_size: usize,
+  plutus_script: Option<TPlutusScript>,
+}
+
+impl TryFrom<TransactionOutput> for TReferenceInput {
+  type Error = CError;
+
+  fn try_from(output: TransactionOutput) -> Result<Self> {
+    let script_ref = output.script_ref();
+    let script_size = script_ref_size(script_ref)?;
+    let plutus_script = script_ref
+      .and_then(|script_ref| script_ref.plutus_script())
+      .map(|plutus_script| plutus_script.try_into())
+      .transpose()?;
+    output.try_into().map(|output| Self {
+      output,
+      script_size,
+      plutus_script,
+    })
+  }
+}
+
+// Rust side of VotingProcedures with the keys of the voters
+#[derive(Clone)]
+struct TVotingProcedures {
+  bytes: Vec<u8>,
+  signers: BTreeSet<REd25519KeyHash>,
+}
+
+impl TryFrom<VotingProcedures> for TVotingProcedures {
+  type Error = CError;
+
+  fn try_from(voting_procedures: VotingProcedures) -> Result<Self> {
+    let voters = unsafe { voting_procedures.unowned()? };
+    let signers = voters
+      .iter()
+      .filter_map(|voter| voter.key.key_hash())
+      .map(|hash| hash.into())
+      .collect();
+    voting_procedures
+      .to_cbor()
+      .map(|bytes| Self { bytes, signers })
+  }
+}
+
+// Rust side of ProposalProcedures with their total deposit
+#[derive(Clone)]
+struct TProposalProcedures {
+  bytes: Vec<u8>,
+  deposit: Coin,
+}
+
+impl TryFrom<ProposalProcedures> for TProposalProcedures {
+  type Error = CError;
+
+  fn try_from(proposal_procedures: ProposalProcedures) -> Result<Self> {
+    let deposit = unsafe { proposal_procedures.unowned()? }
+      .iter()
+      .try_fold(0 as Coin, |sum, proposal| sum.checked_add(proposal.deposit()))
+      .ok_or("Proposals deposit is too big")?;
+    proposal_procedures
+      .to_cbor()
+      .map(|bytes| Self { bytes, deposit })
+  }
+}
+
+// Bytes of reference scripts charged at one price by the Conway fee
+const REF_SCRIPT_TIER_SIZE: u128 = 25600;
+
+// Random index below len for the random selection strategies
+fn random_index(len: usize) -> Result<usize> {
+  let mut bytes = [0u8; 8];
+  getrandom::getrandom(&mut bytes).map_err(|err| format!("{}", err))?;
+  Ok((u64::from_le_bytes(bytes) % len as u64) as usize)
+}
+
+// Size of a witness set entry holding the item
+fn witness_entry_size(bytes: Vec<u8>) -> usize {
+  1 + bytes.len()
+}
+
+// Size of the vkey witnesses entry holding the placeholder witnesses of the keys
+fn vkeys_size(count: usize) -> Result<usize> {
+  if count == 0 {
+    return Ok(0);
+  }
+  let witness = encode_array(vec![vec![0u8; 32].to_cbor()?, vec![0u8; 64].to_cbor()?])?;
+  encode_array(vec![witness; count]).map(witness_entry_size)
+}
+
+// CSL builder together with a mirror of the state it has no public getters for.
+// Every mutation goes through this type so the mirror stays in sync.
+// Conway parts of the transaction are unknown to CSL, so they are kept here
+// and their sizes, deposits and witnesses are added on top of CSL numbers.
+// Plutus witnesses, collateral and required signers aren't supported
+// by the CSL builder either and are handled the same way.
+#[derive(Clone)]
+pub struct TTransactionBuilder {
+  builder: RTransactionBuilder,
+  // cost models are kept below, so the config owns no memory
+  config: TransactionBuilderConfig,
+  cost_models: Option<BTreeMap<Language, Vec<i128>>>,
+  inputs: Vec<TTxBuilderInput>,
+  outputs: Vec<TTransactionOutput>,
+  ttl: Option<Slot>,
+  certs: Option<TCertificates>,
+  withdrawals: Option<RWithdrawals>,
+  validity_start_interval: Option<Slot>,
+  input_types: TMockWitnessSet,
+  input_kinds: Vec<TTxBuilderInputKind>,
+  voting_procedures: Option<TVotingProcedures>,
+  proposal_procedures: Option<TProposalProcedures>,
+  current_treasury_value: Option<Coin>,
+  donation: Option<Coin>,
+  plutus_witnesses: Vec<(RTransactionInput, TPlutusWitness)>,
+  collateral: Vec<TTxBuilderInput>,
+  collateral_vkeys: BTreeSet<REd25519KeyHash>,
+  collateral_return: Option<TTransactionOutput>,
+  required_signers: BTreeSet<REd25519KeyHash>,
+  reference_inputs: BTreeMap<RTransactionInput, TReferenceInput>,
+}
+
+impl Deref for TTransactionBuilder {
+  type Target = RTransactionBuilder;
+
+  fn deref(&self) -> &RTransactionBuilder {
+    &self.builder
+  }
+}
+
+// Body of the builder state. Fee is required by CSL, so a zero one is used if unset
+fn build_body(builder: &RTransactionBuilder) -> Result<RTransactionBody> {
+  let mut builder = builder.clone();
+  if builder.get_fee_if_set().is_none() {
+    builder.set_fee(&to_bignum(0));
+  }
+  builder.build().into_result()
+}
+
+impl TTransactionBuilder {
+  pub fn new(config: TransactionBuilderConfig) -> Result<Self> {
+    let cost_models: Option<Costmdls> = config.cost_models.into();
+    let cost_models = cost_models.map(|cost_models| cost_models.try_into()).transpose()?;
+    let config = TransactionBuilderConfig {
+      cost_models: COption::None,
+      ..config
+    };
+    config.try_into().map(|cfg: RTransactionBuilderConfig| Self {
+      builder: RTransactionBuilder::new(&cfg),
+      config,
+      cost_models,
+      inputs: Vec::new(),
+      outputs: Vec::new(),
+      ttl: None,
+      certs: None,
+      withdrawals: None,
+      validity_start_interval: None,
+      input_types: TMockWitnessSet {
+        vkeys: BTreeSet::new(),
+        scripts: BTreeSet::new(),
+        bootstraps: BTreeSet::new(),
+      },
+      input_kinds: Vec::new(),
+      voting_procedures: None,
+      proposal_procedures: None,
+      current_treasury_value: None,
+      donation: None,
+      plutus_witnesses: Vec::new(),
+      collateral: Vec::new(),
+      collateral_vkeys: BTreeSet::new(),
+      collateral_return: None,
+      required_signers: BTreeSet::new(),
+      reference_inputs: BTreeMap::new(),
+    })
+  }
+
+  fn body(&self) -> Result<RTransactionBody> {
+    build_body(&self.builder)
+  }
+
+  // Redeemers of the Plutus inputs. Spend index is the position of
+  // the input in the body inputs, which the ledger keeps sorted.
+  fn redeemers(&self) -> Result<RRedeemers> {
+    let mut inputs: Vec<&RTransactionInput> =
+      self.inputs.iter().map(|input| &input.input).collect();
+    inputs.sort();
+    let mut redeemers = self
+      .plutus_witnesses
+      .iter()
+      .map(|(input, witness)| {
+        inputs
+          .binary_search(&input)
+          .map(|index| (index, &witness.redeemer))
+          .map_err(|_| "Plutus input is not in the inputs".into())
+      })
+      .collect::<Result<Vec<(usize, &RRedeemer)>>>()?;
+    redeemers.sort_by_key(|(index, _)| *index);
+    let mut result = RRedeemers::new();
+    for (index, redeemer) in redeemers {
+      result.add(&RRedeemer::new(
+        &RRedeemerTag::new_spend(),
+        &to_bignum(index as u64),
+        &redeemer.data(),
+        &redeemer.ex_units(),
+      ));
+    }
+    Ok(result)
+  }
+
+  fn plutus_scripts(&self) -> Vec<(Language, Vec<u8>)> {
+    let mut hashes = BTreeSet::new();
+    let mut scripts = Vec::new();
+    for (_, witness) in &self.plutus_witnesses {
+      if let TPlutusScriptSource::Script(script) = &witness.script {
+        if hashes.insert(script.hash()) {
+          scripts.push((script.language, script.bytes.clone()));
+        }
+      }
+    }
+    scripts
+  }
+
+  // Languages of the scripts, given in the witness set or held by reference inputs
+  fn languages(&self) -> BTreeSet<Language> {
+    self
+      .plutus_witnesses
+      .iter()
+      .filter_map(|(_, witness)| match &witness.script {
+        TPlutusScriptSource::Script(script) => Some(script.language),
+        TPlutusScriptSource::RefInput(input) => self
+          .reference_inputs
+          .get(input)
+          .and_then(|reference_input| reference_input.plutus_script.as_ref())
+          .map(|script| script.language),
+      })
+      .collect()
+  }
+
+  fn plutus_data(&self) -> RPlutusList {
+    let mut datums = BTreeSet::new();
+    let mut list = RPlutusList::new();
+    for datum in self.plutus_witnesses.iter().filter_map(|(_, w)| w.datum.as_ref()) {
+      if datums.insert(datum.to_bytes()) {
+        list.add(datum);
+      }
+    }
+    list
+  }
+
+  // CSL witness set with the scripts of every language added
+  fn witness_set_bytes(&self) -> Result<Vec<u8>> {
+    let mut witness_set = RTransactionWitnessSet::new();
+    if !self.plutus_witnesses.is_empty() {
+      witness_set.set_redeemers(&self.redeemers()?);
+    }
+    let datums = self.plutus_data();
+    if datums.len() > 0 {
+      witness_set.set_plutus_data(&datums);
+    }
+    let mut entries = map_entries(&witness_set.to_bytes())?;
+    entries.extend(plutus_script_entries(&self.plutus_scripts())?);
+    encode_map(entries)
+  }
+
+  // Scripts, datums and redeemers of the Plutus inputs. Key witnesses are added by the signer.
+  pub fn witness_set(&self) -> Result<TransactionWitnessSet> {
+    TransactionWitnessSet::from_cbor(&self.witness_set_bytes()?)
+  }
+
+  // Hash of redeemers, datums and the cost models of the used languages
+  fn script_data_hash(&self) -> Result<Option<RScriptDataHash>> {
+    if self.plutus_witnesses.is_empty() {
+      return Ok(None);
+    }
+    let cost_models = self.cost_models.as_ref().ok_or("Cost models are missing")?;
+    let datums = self.plutus_data();
+    let mut bytes = self.redeemers()?.to_bytes();
+    if datums.len() > 0 {
+      bytes.extend(datums.to_bytes());
+    }
+    bytes.extend(language_views(cost_models, &self.languages())?);
+    let mut hash = [0; 32];
+    Blake2b::blake2b(&mut hash, &bytes, &[]);
+    Ok(Some(hash.into()))
+  }
+
+  // Collateral minus the collateral return
+  fn total_collateral(&self) -> Result<Coin> {
+    let collateral = self
+      .collateral
+      .iter()
+      .try_fold(0 as Coin, |sum, input| sum.checked_add(from_bignum(&input.amount.coin())))
+      .ok_or("Collateral is too big")?;
+    let returned = self
+      .collateral_return
+      .as_ref()
+      .map(|output| from_bignum(&output.legacy.amount().coin()))
+      .unwrap_or(0);
+    collateral
+      .checked_sub(returned)
+      .ok_or_else(|| "Collateral return is bigger than the collateral".into())
+  }
+
+  // CSL body with the fields its builder doesn't set added
+  fn body_bytes(&self, body: &RTransactionBody) -> Result<Vec<u8>> {
+    let mut body = body.clone();
+    if let Some(script_data_hash) = self.script_data_hash()? {
+      body.set_script_data_hash(&script_data_hash);
+    }
+    if !self.collateral.is_empty() {
+      let mut collateral = RTransactionInputs::new();
+      for input in &self.collateral {
+        collateral.add(&input.input);
+      }
+      body.set_collateral(&collateral);
+    }
+    if !self.required_signers.is_empty() {
+      let mut required_signers = REd25519KeyHashes::new();
+      for signer in &self.required_signers {
+        required_signers.add(signer);
+      }
+      body.set_required_signers(&required_signers);
+    }
+    let mut entries = map_entries(&body.to_bytes())?;
+    take_entry(&mut entries, 1);
+    let outputs = self.outputs.iter().map(|output| output.bytes.clone()).collect();
+    entries.push((1, encode_array(outputs)?));
+    if let Some(certs) = &self.certs {
+      take_entry(&mut entries, 4);
+      entries.push((4, certs.bytes.clone()));
+    }
+    if let Some(collateral_return) = &self.collateral_return {
+      entries.push((16, collateral_return.bytes.clone()));
+      entries.push((17, self.total_collateral()?.to_cbor()?));
+    }
+    if !self.reference_inputs.is_empty() {
+      let inputs = self.reference_inputs.keys().map(|input| input.to_bytes()).collect();
+      entries.push((18, encode_array(inputs)?));
+    }
+    if let Some(voting_procedures) = &self.voting_procedures {
+      entries.push((19, voting_procedures.bytes.clone()));
+    }
+    if let Some(proposal_procedures) = &self.proposal_procedures {
+      entries.push((20, proposal_procedures.bytes.clone()));
+    }
+    if let Some(current_treasury_value) = self.current_treasury_value {
+      entries.push((21, current_treasury_value.to_cbor()?));
+    }
+    if let Some(donation) = self.donation {
+      entries.push((22, donation.to_cbor()?));
+    }
+    encode_map(entries)
+  }
+
+  // Keys CSL counts in its fake witness set
+  fn legacy_vkeys(&self) -> BTreeSet<REd25519KeyHash> {
+    let mut vkeys = self.input_types.vkeys.clone();
+    if let Some(certs) = &self.certs {
+      legacy_cert_keys(&certs.legacy, &mut vkeys);
+    }
+    if let Some(withdrawals) = &self.withdrawals {
+      let addresses = withdrawals.keys();
+      for index in 0..addresses.len() {
+        vkeys.extend(addresses.get(index).payment_cred().to_keyhash());
+      }
+    }
+    if let Some(mint_scripts) = self.builder.get_mint_scripts() {
+      vkeys.extend(RequiredSignersSet::from(&mint_scripts));
+    }
+    vkeys
+  }
+
+  // Bytes the transaction has on top of the one CSL sizes: body fields
+  // and witnesses of Plutus inputs, collateral, required signers,
+  // Conway certificates and voters
+  fn extra_size(&self) -> Result<usize> {
+    let body = self.body()?;
+    let body_size = self.body_bytes(&body)?.len() - body.to_bytes().len();
+    let mut vkeys = self.legacy_vkeys();
+    let legacy_count = vkeys.len();
+    if let Some(certs) = &self.certs {
+      vkeys.extend(certs.signers.iter().cloned());
+    }
+    if let Some(voting_procedures) = &self.voting_procedures {
+      vkeys.extend(voting_procedures.signers.iter().cloned());
+    }
+    vkeys.extend(self.collateral_vkeys.iter().cloned());
+    vkeys.extend(self.required_signers.iter().cloned());
+    let witnesses_size: usize = map_entries(&self.witness_set_bytes()?)?
+      .into_iter()
+      .map(|(_, value)| witness_entry_size(value))
+      .sum();
+    Ok(body_size + witnesses_size + vkeys_size(vkeys.len())? - vkeys_size(legacy_count)?)
+  }
+
+  // Price of the execution units of all redeemers, rounded up
+  fn script_fee(&self) -> Result<Coin> {
+    if self.plutus_witnesses.is_empty() {
+      return Ok(0);
+    }
+    let prices: Option<ExUnitPrices> = self.config.ex_unit_prices.into();
+    let prices: RExUnitPrices = prices.ok_or("Execution unit prices are missing")?.into();
+    let (mem, steps) = self
+      .plutus_witnesses
+      .iter()
+      .map(|(_, witness)| witness.redeemer.ex_units())
+      .try_fold((0u128, 0u128), |(mem, steps), ex_units| {
+        Some((
+          mem.checked_add(from_bignum(&ex_units.mem()) as u128)?,
+          steps.checked_add(from_bignum(&ex_units.steps()) as u128)?,
+        ))
+      })
+      .ok_or("Execution units are too big")?;
+    let (mem_price, step_price) = (prices.mem_price(), prices.step_price());
+    let mem_num = from_bignum(&mem_price.numerator()) as u128;
+    let mem_den = from_bignum(&mem_price.denominator()) as u128;
+    let step_num = from_bignum(&step_price.numerator()) as u128;
+    let step_den = from_bignum(&step_price.denominator()) as u128;
+    // mem * mem_num / mem_den + steps * step_num / step_den
+    let numerator = mem
+      .checked_mul(mem_num)
+      .and_then(|mem| mem.checked_mul(step_den))
+      .zip(steps.checked_mul(step_num).and_then(|steps| steps.checked_mul(mem_den)))
+      .and_then(|(mem, steps)| mem.checked_add(steps));
+    let denominator = mem_den.checked_mul(step_den).filter(|den| *den > 0);
+    numerator
+      .zip(denominator)
+      .and_then(|(num, den)| Coin::try_from(num.div_ceil(den)).ok())
+      .ok_or_else(|| "Script fee is too big".into())
+  }
+
+  // Conway fee of the scripts held by the reference and the spent inputs.
+  // Every 25 KiB cost 1.2 times the previous ones and the sum is rounded
+  // down once.
+  fn ref_script_fee(&self) -> Result<Coin> {
+    let size = self
+      .reference_inputs
+      .values()
+      .map(|reference_input| reference_input.script_size)
+      .chain(self.inputs.iter().map(|input| input.script_size))
+      .map(|size| size as u128)
+      .sum::<u128>();
+    if size == 0 {
+      return Ok(0);
+    }
+    let price: Option<SubCoin> = self.config.ref_script_coins_per_byte.into();
+    let price: RUnitInterval = price.ok_or("Reference script price is missing")?.into();
+    let mut numerator = from_bignum(&price.numerator()) as u128;
+    let mut denominator = from_bignum(&price.denominator()) as u128;
+    // fee is sum / denominator
+    let mut sum: u128 = 0;
+    let mut left = size;
+    loop {
+      let tier = left.min(REF_SCRIPT_TIER_SIZE);
+      sum = tier
+        .checked_mul(numerator)
+        .and_then(|fee| sum.checked_add(fee))
+        .ok_or("Reference script fee is too big")?;
+      left -= tier;
+      if left == 0 {
+        break;
+      }
+      sum = sum.checked_mul(5).ok_or("Reference script fee is too big")?;
+      numerator = numerator.checked_mul(6).ok_or("Reference script fee is too big")?;
+      denominator = denominator.checked_mul(5).ok_or("Reference script fee is too big")?;
+    }
+    sum
+      .checked_div(denominator)
+      .and_then(|fee| Coin::try_from(fee).ok())
+      .ok_or_else(|| "Reference script fee is too big".into())
+  }
+
+  fn extra_fee(&self) -> Result<Coin> {
+    let fee_algo: RLinearFee = self.config.fee_algo.into();
+    let size = self.extra_size()? as Coin;
+    let script_fee = self.script_fee()?;
+    let ref_script_fee = self.ref_script_fee()?;
+    from_bignum(&fee_algo.coefficient())
+      .checked_mul(size)
+      .and_then(|fee| fee.checked_add(script_fee))
+      .and_then(|fee| fee.checked_add(ref_script_fee))
+      .ok_or_else(|| "Fee is too big".into())
+  }
+
+  // Everything the transaction takes in besides the fee: inputs, mint,
+  // withdrawals and refunds of the legacy and Conway certificates
+  fn total_input(&self) -> Result<RValue> {
+    let refund = self.certs.as_ref().map(|certs| certs.refund).unwrap_or(0);
+    self
+      .builder
+      .get_total_input()
+      .and_then(|input| input.checked_add(&RValue::new(&to_bignum(refund))))
+      .into_result()
+  }
+
+  // Everything the transaction gives out besides the fee: outputs, deposits
+  // of certificates and proposals and the treasury donation. The current
+  // treasury value only states the treasury and moves no coin.
+  fn total_output(&self) -> Result<RValue> {
+    let deposit = self.get_deposit()?;
+    let donation = to_bignum(self.donation.unwrap_or(0));
+    self
+      .builder
+      .get_explicit_output()
+      .and_then(|output| output.checked_add(&RValue::new(&deposit)))
+      .and_then(|output| output.checked_add(&RValue::new(&donation)))
+      .into_result()
+  }
+
+  // What the inputs have to pay for: the given total output and the fee
+  fn needed_input(&self, output: &RValue) -> Result<RValue> {
+    output.checked_add(&RValue::new(&self.min_fee()?)).into_result()
+  }
+
+  fn push_input(
+    &mut self, input: &RTransactionInput, amount: &RValue, script_size: usize,
+    kind: TTxBuilderInputKind,
+  ) {
+    self.inputs.push(TTxBuilderInput {
+      input: input.clone(),
+      amount: amount.clone(),
+      script_size,
+    });
+    self.input_kinds.push(kind);
+  }
+
+  // Inputs with the kind of each one, as the C builder keeps them
+  fn add_typed_inputs(
+    &mut self, inputs: Vec<TTxBuilderInput>, kinds: Vec<TTxBuilderInputKind>,
+  ) -> Result<()> {
+    if inputs.len() != kinds.len() {
+      return Err("Input kinds don't match the inputs".into());
+    }
+    for (TTxBuilderInput { input, amount, script_size }, kind) in inputs.into_iter().zip(kinds) {
+      match kind {
+        TTxBuilderInputKind::Key(hash) => self.add_key_input(&hash, &input, &amount, script_size),
+        TTxBuilderInputKind::Script(hash) => {
+          self.add_script_input(&hash, &input, &amount, script_size)
+        }
+        TTxBuilderInputKind::Bootstrap(byron) => {
+          self.add_bootstrap_input(&byron, &input, &amount, script_size)
+        }
+      }
+    }
+    Ok(())
+  }
+
+  // CSL selects the inputs for the outputs it knows about. Conway deposits,
+  // the donation and the fees CSL doesn't count are covered afterwards by
+  // more inputs, the largest or random ones as the strategy picks them.
+  pub fn add_inputs_from(
+    &mut self, inputs: &[TransactionUnspentOutput], strategy: RCoinSelectionStrategyCIP2,
+  ) -> Result<()> {
+    let random = matches!(
+      strategy,
+      RCoinSelectionStrategyCIP2::RandomImprove
+        | RCoinSelectionStrategyCIP2::RandomImproveMultiAsset
+    );
+    // selection may fail halfway, so it runs on a copy
+    let mut selection = self.clone();
+    // each UTxO with the size of the script reference its output holds
+    let mut available = inputs
+      .iter()
+      .map(|&utxo| {
+        let script_size = script_ref_size(utxo.output.script_ref())?;
+        RTransactionUnspentOutput::try_from(utxo).map(|utxo| (utxo, script_size))
+      })
+      .collect::<Result<Vec<_>>>()?;
+    let mut utxos = RTransactionUnspentOutputs::new();
+    for (utxo, _) in &available {
+      utxos.add(utxo);
+    }
+    let mut builder = selection.builder.clone();
+    builder.add_inputs_from(&utxos, strategy).into_result()?;
+    let selected = build_body(&builder)?.inputs();
+    for index in selection.inputs.len()..selected.len() {
+      let input = selected.get(index);
+      let position = available
+        .iter()
+        .position(|(utxo, _)| utxo.input().to_bytes() == input.to_bytes())
+        .ok_or_else(|| CError::from("Selected input is not in the UTxO list"))?;
+      let (utxo, script_size) = available.remove(position);
+      selection.add_input(&utxo.output().address(), &input, &utxo.output().amount(), script_size);
+    }
+    available.retain(|(utxo, _)| {
+      let input = utxo.input();
+      selection.inputs.iter().all(|selected| selected.input != input)
+    });
+    // largest last, so they are taken from the end
+    if !random {
+      available.sort_by_key(|(utxo, _)| from_bignum(&utxo.output().amount().coin()));
+    }
+    // The fee encodes the whole body, so it is computed again only once the
+    // inputs cover the outputs and the fee computed last
+    let output = selection.total_output()?;
+    let mut input = selection.total_input()?;
+    let mut needed = selection.needed_input(&output)?;
+    let mut fee_is_current = true;
+    loop {
+      if matches!(input.partial_cmp(&needed), Some(Ordering::Equal | Ordering::Greater)) {
+        if fee_is_current {
+          break;
+        }
+        needed = selection.needed_input(&output)?;
+        fee_is_current = true;
+        continue;
+      }
+      if available.is_empty() {
+        return Err("UTxO Balance Insufficient".into());
+      }
+      let index = match random {
+        true => random_index(available.len())?,
+        false => available.len() - 1,
+      };
+      let (utxo, script_size) = available.swap_remove(index);
+      let (address, amount) = (utxo.output().address(), utxo.output().amount());
+      selection.add_input(&address, &utxo.input(), &amount, script_size);
+      input = input.checked_add(&amount).into_result()?;
+      fee_is_current = false;
+    }
+    *self = selection;
+    Ok(())
+  }
+
+  // The script size of an input is the size of the script reference of its
+  // output, which is charged by the reference script fee
+  pub fn add_key_input(
+    &mut self, hash: &REd25519KeyHash, input: &RTransactionInput, amount: &RValue,
+    script_size: usize,
+  ) {
+    self.builder.add_key_input(hash, input, amount);
+    self.push_input(input, amount, script_size, TTxBuilderInputKind::Key(hash.clone()));
+    self.input_types.vkeys.insert(hash.clone());
+  }
+
+  pub fn add_script_input(
+    &mut self, hash: &RScriptHash, input: &RTransactionInput, amount: &RValue, script_size: usize,
+  ) {
+    self.builder.add_script_input(hash, input, amount);
+    self.push_input(input, amount, script_size, TTxBuilderInputKind::Script(hash.clone()));
+    self.input_types.scripts.insert(hash.clone());
+  }
+
+  fn add_plutus_witness(&mut self, input: &RTransactionInput, witness: TPlutusWitness) {
+    self.plutus_witnesses.push((input.clone(), witness));
+  }
+
+  // Script input spent with a Plutus witness. The input is added as a
+  // script input with the hash of the witness script. A script held by
+  // a reference input needs the reference input to be added first.
+  pub fn add_plutus_script_input(
+    &mut self, witness: PlutusWitness, input: &RTransactionInput, amount: &RValue,
+    script_size: usize,
+  ) -> Result<()> {
+    let witness: TPlutusWitness = witness.try_into()?;
+    let hash = match &witness.script {
+      TPlutusScriptSource::Script(script) => script.hash(),
+      TPlutusScriptSource::RefInput(reference_input) => self
+        .reference_inputs
+        .get(reference_input)
+        .and_then(|reference_input| reference_input.plutus_script.as_ref())
+        .map(|script| script.hash())
+        .ok_or("Reference input doesn't hold a Plutus script")?,
+    };
+    self.add_script_input(&hash, input, amount, script_size);
+    self.add_plutus_witness(input, witness);
+    Ok(())
+  }
+
+  // Collateral should be locked by a key, whose witness is counted in the fee
+  pub fn add_collateral(
+    &mut self, hash: &REd25519KeyHash, input: &RTransactionInput, amount: &RValue,
+  ) {
+    self.collateral.push(TTxBuilderInput {
+      input: input.clone(),
+      amount: amount.clone(),
+      script_size: 0,
+    });
+    self.collateral_vkeys.insert(hash.clone());
+  }
+
+  pub fn set_collateral_return(&mut self, collateral_return: TransactionOutput) -> Result<()> {
+    self.collateral_return = Some(collateral_return.try_into()?);
+    Ok(())
+  }
+
+  pub fn add_required_signer(&mut self, hash: &REd25519KeyHash) {
+    self.required_signers.insert(hash.clone());
+  }
+
+  // Output is read only. Its script reference is counted in the fee.
+  pub fn add_reference_input(&mut self, utxo: TransactionUnspentOutput) -> Result<()> {
+    let reference_input = utxo.output.try_into()?;
+    self.reference_inputs.insert(utxo.input.into(), reference_input);
+    Ok(())
+  }
+
+  pub fn add_bootstrap_input(
+    &mut self, hash: &RByronAddress, input: &RTransactionInput, amount: &RValue,
+    script_size: usize,
+  ) {
+    self.builder.add_bootstrap_input(hash, input, amount);
+    self.push_input(input, amount, script_size, TTxBuilderInputKind::Bootstrap(hash.clone()));
+    self.input_types.bootstraps.insert(hash.to_bytes());
+  }
+
+  pub fn add_input(
+    &mut self, address: &RAddress, input: &RTransactionInput, amount: &RValue, script_size: usize,
+  ) {
+    self.builder.add_input(address, input, amount);
+    self.mirror_input(address, input, amount, script_size);
+  }
+
+  // Same dispatch as RTransactionBuilder::add_input, applied to the mirror only
+  fn mirror_input(
+    &mut self, address: &RAddress, input: &RTransactionInput, amount: &RValue, script_size: usize,
+  ) {
+    let payment = RBaseAddress::from_address(address)
+      .map(|addr| addr.payment_cred())
+      .or_else(|| REnterpriseAddress::from_address(address).map(|addr| addr.payment_cred()))
+      .or_else(|| RPointerAddress::from_address(address).map(|addr| addr.payment_cred()));
+    if let Some(payment) = payment {
+      if let Some(hash) = payment.to_keyhash() {
+        self.input_types.vkeys.insert(hash.clone());
+        self.push_input(input, amount, script_size, TTxBuilderInputKind::Key(hash));
+      } else if let Some(hash) = payment.to_scripthash() {
+        self.input_types.scripts.insert(hash.clone());
+        self.push_input(input, amount, script_size, TTxBuilderInputKind::Script(hash));
+      }
+    } else if let Some(byron) = RByronAddress::from_address(address) {
+      self.input_types.bootstraps.insert(byron.to_bytes());
+      self.push_input(input, amount, script_size, TTxBuilderInputKind::Bootstrap(byron));
+    }
+  }
+
+  pub fn add_output(&mut self, output: TransactionOutput) -> Result<()> {
+    let min_ada = self.config.min_ada_required(&output)?;
+    let output: TTransactionOutput = output.try_into()?;
+    let coin = from_bignum(&output.legacy.amount().coin());
+    if coin < min_ada {
+      return Err(format!("Value {} less than the minimum UTXO value {}", coin, min_ada).into());
+    }
+    self.push_output(output)
+  }
+
+  fn push_output(&mut self, output: TTransactionOutput) -> Result<()> {
+    self.builder.add_output(&output.legacy).into_result()?;
+    self.outputs.push(output);
+    Ok(())
+  }
+
+  // Fee the input adds, its witness and the Conway parts of the body included
+  pub fn fee_for_input(
+    &self, address: &RAddress, input: &RTransactionInput, amount: &RValue,
+  ) -> Result<RCoin> {
+    let mut builder = self.clone();
+    builder.add_input(address, input, amount, 0);
+    builder
+      .min_fee()?
+      .checked_sub(&self.min_fee()?)
+      .into_result()
+  }
+
+  pub fn fee_for_output(&self, output: TransactionOutput) -> Result<RCoin> {
+    let mut builder = self.clone();
+    builder.add_output(output)?;
+    builder
+      .min_fee()?
+      .checked_sub(&self.min_fee()?)
+      .into_result()
+  }
+
+  pub fn set_fee(&mut self, fee: &RCoin) {
+    self.builder.set_fee(fee);
+  }
+
+  pub fn set_ttl(&mut self, ttl: Slot) {
+    self.builder.set_ttl(ttl);
+    self.ttl = Some(ttl);
+  }
+
+  pub fn set_validity_start_interval(&mut self, validity_start_interval: Slot) {
+    self
+      .builder
+      .set_validity_start_interval(validity_start_interval);
+    self.validity_start_interval = Some(validity_start_interval);
+  }
+
+  pub fn set_certs(&mut self, certs: Certificates) -> Result<()> {
+    let certs: TCertificates = certs.try_into()?;
+    self.builder.set_certs(&certs.legacy);
+    self.certs = Some(certs);
+    Ok(())
+  }
+
+  pub fn set_withdrawals(&mut self, withdrawals: &RWithdrawals) {
+    self.builder.set_withdrawals(withdrawals);
+    self.withdrawals = Some(withdrawals.clone());
+  }
+
+  pub fn set_auxiliary_data(&mut self, auxiliary_data: &RAuxiliaryData) {
+    self.builder.set_auxiliary_data(auxiliary_data);
+  }
+
+  pub fn set_metadata(&mut self, metadata: &RGeneralTransactionMetadata) {
+    self.builder.set_metadata(metadata);
+  }
+
+  pub fn add_metadatum(&mut self, key: &RBigNum, val: &RTransactionMetadatum) {
+    self.builder.add_metadatum(key, val);
+  }
+
+  pub fn set_mint(&mut self, mint: &RMint, mint_scripts: &RNativeScripts) -> Result<()> {
+    self.builder.set_mint(mint, mint_scripts).into_result()
+  }
+
+  pub fn set_voting_procedures(&mut self, voting_procedures: VotingProcedures) -> Result<()> {
+    self.voting_procedures = Some(voting_procedures.try_into()?);
+    Ok(())
+  }
+
+  pub fn set_proposal_procedures(&mut self, proposal_procedures: ProposalProcedures) -> Result<()> {
+    self.proposal_procedures = Some(proposal_procedures.try_into()?);
+    Ok(())
+  }
+
+  pub fn set_current_treasury_value(&mut self, current_treasury_value: Coin) {
+    self.current_treasury_value = Some(current_treasury_value);
+  }
+
+  pub fn set_donation(&mut self, donation: Coin) {
+    self.donation = Some(donation);
+  }
+
+  // withdrawals and refunds, including the Conway ones
+  pub fn get_implicit_input(&self) -> Result<RValue> {
+    let refund = self.certs.as_ref().map(|certs| certs.refund).unwrap_or(0);
+    self
+      .builder
+      .get_implicit_input()
+      .and_then(|input| input.checked_add(&RValue::new(&to_bignum(refund))))
+      .into_result()
+  }
+
+  // certificate and proposal deposits
+  pub fn get_deposit(&self) -> Result<RCoin> {
+    let deposit = self.certs.as_ref().map(|certs| certs.deposit).unwrap_or(0);
+    let proposals = self.proposal_procedures.as_ref().map(|pp| pp.deposit).unwrap_or(0);
+    self
+      .builder
+      .get_deposit()
+      .and_then(|csl| csl.checked_add(&to_bignum(deposit)))
+      .and_then(|sum| sum.checked_add(&to_bignum(proposals)))
+      .into_result()
+  }
+
+  pub fn min_fee(&self) -> Result<RCoin> {
+    let extra_fee = self.extra_fee()?;
+    self
+      .builder
+      .min_fee()
+      .and_then(|fee| fee.checked_add(&to_bignum(extra_fee)))
+      .into_result()
+  }
+
+  // Fee and size of the signed transaction. Its witness set holds placeholders
+  // of the exact size for the expected witnesses instead of the CSL guesses.
+  pub fn estimate_fee(&self, witnesses: ExpectedWitnesses) -> Result<FeeEstimate> {
+    let witnesses: TExpectedWitnesses = witnesses.try_into()?;
+    let mut body_entries = map_entries(&self.body_bytes(&self.body()?)?)?;
+    take_entry(&mut body_entries, 2);
+    let mut witness_entries = map_entries(&self.witness_set_bytes()?)?;
+    witness_entries.extend(witnesses.entries()?);
+    let witness_set = encode_map(witness_entries)?;
+    let auxiliary_data = match self.builder.get_auxiliary_data() {
+      Some(auxiliary_data) => auxiliary_data.to_bytes(),
+      None => vec![0xf6],
+    };
+    let fee_algo: RLinearFee = self.config.fee_algo.into();
+    let script_fee = self.script_fee()?
+      .checked_add(self.ref_script_fee()?)
+      .ok_or("Fee is too big")?;
+    // The fee is part of the body, so it's raised until it pays for its own encoding
+    let mut fee: Coin = 0;
+    loop {
+      let mut entries = body_entries.clone();
+      entries.push((2, fee.to_cbor()?));
+      let transaction = encode_array(vec![
+        encode_map(entries)?,
+        witness_set.clone(),
+        vec![0xf5],
+        auxiliary_data.clone(),
+      ])?;
+      let size = transaction.len();
+      let min_fee = from_bignum(&fee_algo.coefficient())
+        .checked_mul(size as Coin)
+        .and_then(|fee| fee.checked_add(from_bignum(&fee_algo.constant())))
+        .and_then(|fee| fee.checked_add(script_fee))
+        .ok_or("Fee is too big")?;
+      if min_fee <= fee {
+        return Ok(FeeEstimate {
+          fee,
+          size: size as u32,
+        });
+      }
+      fee = min_fee;
+    }
+  }
+
+  // Balances the transaction with one change output holding everything
+  // left over. Leftover coin below the min ADA of the change is paid as fee.
+  pub fn add_change_if_needed(&mut self, address: &RAddress) -> Result<bool> {
+    if self.get_fee_if_set().is_some() {
+      return Err("Cannot calculate change if fee was explicitly specified".into());
+    }
+    let input = self.total_input()?;
+    let output = self.total_output()?;
+    let mut fee = self.min_fee()?;
+    match input.partial_cmp(&output.checked_add(&RValue::new(&fee)).into_result()?) {
+      Some(Ordering::Equal) => {
+        self.set_fee(&fee);
+        return Ok(false);
+      }
+      Some(Ordering::Greater) => {}
+      _ => return Err("Insufficient input in transaction".into()),
+    }
+    let leftover = input.checked_sub(&output).into_result()?;
+    let has_assets = leftover.multiasset().map(|assets| assets.len() > 0).unwrap_or(false);
+    // The change makes the fee grow, which makes the change shrink
+    loop {
+      let mut change = leftover.clone();
+      change.set_coin(&leftover.coin().checked_sub(&fee).unwrap_or_else(|_| to_bignum(0)));
+      let change = TTransactionOutput::from(RTransactionOutput::new(address, &change));
+      let min_ada = self.config.min_ada_of(&change)?;
+      if from_bignum(&change.legacy.amount().coin()) < min_ada {
+        if has_assets {
+          return Err(
+            "Not enough ADA leftover to include non-ADA assets in a change address".into(),
+          );
+        }
+        self.set_fee(&leftover.coin());
+        return Ok(false);
+      }
+      let mut builder = self.clone();
+      builder.push_output(change)?;
+      let new_fee = builder.min_fee()?;
+      if new_fee <= fee {
+        builder.set_fee(&fee);
+        *self = builder;
+        return Ok(true);
+      }
+      fee = new_fee;
+    }
+  }
+
+  pub fn build(&self) -> Result<TransactionBody> {
+    if !self.plutus_witnesses.is_empty() && self.collateral.is_empty() {
+      return Err("Collateral is required to spend Plutus inputs".into());
+    }
+    let body = self.builder.build().into_result()?;
+    let size = self.builder.full_size().into_result()? + self.extra_size()?;
+    if size > self.config.max_tx_size as usize {
+      return Err(
+        format!(
+          "Maximum transaction size of {} exceeded. Found: {}",
+          self.config.max_tx_size, size
+        )
+        .into(),
+      );
+    }
+    TransactionBody::from_cbor(&self.body_bytes(&body)?)
+  }
+}
+
+impl TryFrom<TransactionBuilder> for TTransactionBuilder {
+  type Error = CError;
+
+  fn try_from(tb: TransactionBuilder) -> Result<Self> {
+    let mut builder = Self::new(tb.config)?;
+    let inputs = unsafe { tb.inputs.unowned()? }
+      .iter()
+      .map(|input| (*input).try_into())
+      .collect::<Result<Vec<TTxBuilderInput>>>()?;
+    let input_kinds = unsafe { tb.input_kinds.unowned()? }
+      .iter()
+      .map(|&kind| kind.try_into())
+      .collect::<Result<Vec<TTxBuilderInputKind>>>()?;
+    builder.add_typed_inputs(inputs, input_kinds)?;
+    for &output in unsafe { tb.outputs.unowned()? } {
+      builder.add_output(output)?;
+    }
+    let fee: Option<Coin> = tb.fee.into();
+    if let Some(fee) = fee {
+      builder.set_fee(&to_bignum(fee));
+    }
+    let ttl: Option<Slot> = tb.ttl.into();
+    if let Some(ttl) = ttl {
+      builder.set_ttl(ttl);
+    }
+    let certs: Option<Certificates> = tb.certs.into();
+    if let Some(certs) = certs {
+      builder.set_certs(certs)?;
+    }
+    let withdrawals: Option<Withdrawals> = tb.withdrawals.into();
+    if let Some(withdrawals) = withdrawals {
+      builder.set_withdrawals(&withdrawals.try_into()?);
+    }
+    let auxiliary_data: Option<AuxiliaryData> = tb.auxiliary_data.into();
+    if let Some(auxiliary_data) = auxiliary_data {
+      builder.set_auxiliary_data(&auxiliary_data.try_into()?);
+    }
+    let validity_start_interval: Option<Slot> = tb.validity_start_interval.into();
+    if let Some(validity_start_interval) = validity_start_interval {
+      builder.set_validity_start_interval(validity_start_interval);
+    }
+    let mint: Option<Mint> = tb.mint.into();
+    let mint_scripts: Option<NativeScripts> = tb.mint_scripts.into();
+    match (mint, mint_scripts) {
+      (Some(mint), Some(mint_scripts)) => {
+        builder.set_mint(&mint.try_into()?, &mint_scripts.try_into()?)?
+      }
+      (None, None) => (),
+      _ => return Err("Mint and mint scripts should be set together".into()),
+    }
+    let voting_procedures: Option<VotingProcedures> = tb.voting_procedures.into();
+    if let Some(voting_procedures) = voting_procedures {
+      builder.set_voting_procedures(voting_procedures)?;
+    }
+    let proposal_procedures: Option<ProposalProcedures> = tb.proposal_procedures.into();
+    if let Some(proposal_procedures) = proposal_procedures {
+      builder.set_proposal_procedures(proposal_procedures)?;
+    }
+    let current_treasury_value: Option<Coin> = tb.current_treasury_value.into();
+    if let Some(current_treasury_value) = current_treasury_value {
+      builder.set_current_treasury_value(current_treasury_value);
+    }
+    let donation: Option<Coin> = tb.donation.into();
+    if let Some(donation) = donation {
+      builder.set_donation(donation);
+    }
+    for &utxo in unsafe { tb.reference_inputs.unowned()? } {
+      builder.add_reference_input(utxo)?;
+    }
+    // the inputs are added above, so only the witnesses are restored
+    for witness in unsafe { tb.plutus_witnesses.unowned()? } {
+      builder.add_plutus_witness(&witness.key.into(), witness.val.try_into()?);
+    }
+    let collateral = unsafe { tb.collateral.unowned()? }
+      .iter()
+      .map(|input| (*input).try_into())
+      .collect::<Result<Vec<TTxBuilderInput>>>()?;
+    let collateral_vkeys = unsafe { tb.collateral_vkeys.unowned()? };
+    builder.collateral = collateral;
+    builder.collateral_vkeys = collateral_vkeys.iter().map(|&hash| hash.into()).collect();
+    let collateral_return: Option<TransactionOutput> = tb.collateral_return.into();
+    if let Some(collateral_return) = collateral_return {
+      builder.set_collateral_return(collateral_return)?;
+    }
+    let required_signers: Option<RequiredSigners> = tb.required_signers.into();
+    if let Some(required_signers) = required_signers {
+      for &signer in unsafe { required_signers.unowned()? } {
+        builder.add_required_signer(&signer.into());
+      }
+    }
+    Ok(builder)
+  }
+}
+
+impl TryFrom<TTransactionBuilder> for TransactionBuilder {
+  type Error = CError;
+
+  fn try_from(tb: TTransactionBuilder) -> Result<Self> {
+    let config = TransactionBuilderConfig {
+      cost_models: tb.cost_models.clone().map(|cost_models| cost_models.into()).into(),
+      ..tb.config
+    };
+    let fee = tb.get_fee_if_set().map(|fee| from_bignum(&fee)).into();
+    let ttl = tb.ttl.into();
+    let validity_start_interval = tb.validity_start_interval.into();
+    let current_treasury_value = tb.current_treasury_value.into();
+    let donation = tb.donation.into();
+    let voting_procedures = tb
+      .voting_procedures
+      .map(|voting_procedures| VotingProcedures::from_cbor(&voting_procedures.bytes))
+      .transpose()?;
+    let proposal_procedures = tb
+      .proposal_procedures
+      .map(|proposal_procedures| ProposalProcedures::from_cbor(&proposal_procedures.bytes))
+      .transpose()?;
+    let plutus_witnesses = tb
+      .plutus_witnesses
+      .into_iter()
+      .map(|(input, witness)| {
+        input
+          .try_into()
+          .zip(witness.try_into())
+          .map(|kv: (TransactionInput, PlutusWitness)| kv.into())
+      })
+      .collect::<Result<Vec<PlutusWitnessesKeyValue>>>()?;
+    let collateral = tb
+      .collateral
+      .into_iter()
+      .map(|input| input.try_into())
+      .collect::<Result<Vec<TxBuilderInput>>>()?;
+    let collateral_vkeys = tb
+      .collateral_vkeys
+      .into_iter()
+      .map(|hash| hash.try_into())
+      .collect::<Result<Vec<Ed25519KeyHash>>>()?;
+    let collateral_return = tb
+      .collateral_return
+      .map(|collateral_return| collateral_return.try_into())
+      .transpose()?;
+    let reference_inputs = tb
+      .reference_inputs
+      .into_iter()
+      .map(|(input, reference_input)| {
+        input
+          .try_into()
+          .zip(reference_input.output.try_into())
+          .map(|(input, output)| TransactionUnspentOutput { input, output })
+      })
+      .collect::<Result<Vec<TransactionUnspentOutput>>>()?;
+    let input_kinds = tb
+      .input_kinds
+      .into_iter()
+      .map(|kind| kind.try_into())
+      .collect::<Result<Vec<TxBuilderInputKind>>>()?;
+    let required_signers = match tb.required_signers.is_empty() {
+      true => None,
+      false => Some(
+        tb.required_signers
+          .into_iter()
+          .map(|hash| hash.try_into())
+          .collect::<Result<Vec<Ed25519KeyHash>>>()?,
+      ),
+    };
+    tb.inputs
+      .into_iter()
+      .map(|input| input.try_into())
+      .collect::<Result<Vec<TxBuilderInput>>>()
+      .zip(
+        tb.outputs
+          .into_iter()
+          .map(|output| output.try_into())
+          .collect::<Result<Vec<TransactionOutput>>>(),
+      )
+      .zip(
+        tb.certs
+          .map(|certs| Certificates::from_cbor(&certs.bytes))
+          .transpose(),
+      )
+      .zip(tb.withdrawals.map(|wls| wls.try_into()).transpose())
+      .zip(
+        tb.builder
+          .get_auxiliary_data()
+          .map(|auxiliary_data| auxiliary_data.try_into())
+          .transpose(),
+      )
+      .zip(tb.input_types.try_into())
+      .zip(tb.builder.get_mint().map(|mint| mint.try_into()).transpose())
+      .zip(
+        tb.builder
+          .get_mint_scripts()
+          .map(|mint_scripts| mint_scripts.try_into())
+          .transpose(),
+      )
+      .map(
+        |(
+          ((((((inputs, outputs), certs), withdrawals), auxiliary_data), input_types), mint),
+          mint_scripts,
+        )| Self {
+          config,
+          inputs: inputs.into(),
+          outputs: outputs.into(),
+          fee,
+          ttl,
+          certs: certs.into(),
+          withdrawals: withdrawals.into(),
+          auxiliary_data: auxiliary_data.into(),
+          validity_start_interval,
+          input_types,
+          input_kinds: input_kinds.into(),
+          mint: mint.into(),
+          mint_scripts: mint_scripts.into(),
+          voting_procedures: voting_procedures.into(),
+          proposal_procedures: proposal_procedures.into(),
+          current_treasury_value,
+          donation,
+          plutus_witnesses: plutus_witnesses.into(),
+          collateral: collateral.into(),
+          collateral_vkeys: collateral_vkeys.into(),
+          collateral_return: collateral_return.into(),
+          required_signers: required_signers.map(|signers| signers.into()).into(),
+          reference_inputs: reference_inputs.into(),
+        },
+      )
+  }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn cardano_transaction_builder_add_inputs_from(
+  tb: TransactionBuilder, inputs: TransactionUnspentOutputs, strategy: CoinSelectionStrategyCIP2,
+  result: &mut TransactionBuilder, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    let inputs = inputs.unowned()?;
+    tb.try_into()
+      .and_then(|mut tb: TTransactionBuilder| {
+        tb.add_inputs_from(inputs, strategy.into()).map(|_| tb)
+      })
+      .and_then(|tb| tb.try_into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn cardano_transaction_builder_add_key_input(
+  tb: TransactionBuilder, hash: Ed25519KeyHash, input: TransactionInput, amount: Value,
+  script_ref: COption<ScriptRef>, result: &mut TransactionBuilder, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    tb.try_into()
+      .zip(amount.try_into())
+      .zip(script_ref_size(script_ref.into()))
+      .map(|((mut tb, amount), script_size): ((TTransactionBuilder, RValue), usize)| {
+        tb.add_key_input(&hash.into(), &input.into(), &amount, script_size);
+        tb
+      })
+      .and_then(|tb| tb.try_into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn cardano_transaction_builder_add_script_input(
+  tb: TransactionBuilder, hash: ScriptHash, input: TransactionInput, amount: Value,
+  script_ref: COption<ScriptRef>, result: &mut TransactionBuilder, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    tb.try_into()
+      .zip(amount.try_into())
+      .zip(script_ref_size(script_ref.into()))
+      .map(|((mut tb, amount), script_size): ((TTransactionBuilder, RValue), usize)| {
+        tb.add_script_input(&hash.into(), &input.into(), &amount, script_size);
+        tb
+      })
+      .and_then(|tb| tb.try_into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_add_plutus_script_input(
+  tb: TransactionBuilder, witness: PlutusWitness, input: TransactionInput, amount: Value,
+  script_ref: COption<ScriptRef>, result: &mut TransactionBuilder, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    tb.try_into()
+      .zip(amount.try_into())
+      .zip(script_ref_size(script_ref.into()))
+      .and_then(|((mut tb, amount), script_size): ((TTransactionBuilder, RValue), usize)| {
+        tb.add_plutus_script_input(witness, &input.into(), &amount, script_size)
+          .map(|_| tb)
+      })
+      .and_then(|tb| tb.try_into())
+  })
+  .response(result, error)
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn cardano_transaction_builder_add_key_input(
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_add_collateral(
   tb: TransactionBuilder, hash: Ed25519KeyHash, input: TransactionInput, amount: Value,
   result: &mut TransactionBuilder, error: &mut CError,
 ) -> bool {
   handle_exception_result(|| {
     tb.try_into()
       .zip(amount.try_into())
-      .map(|(mut tb, amount): (RTransactionBuilder, RValue)| {
-        tb.add_key_input(&hash.into(), &input.into(), &amount);
+      .map(|(mut tb, amount): (TTransactionBuilder, RValue)| {
+        tb.add_collateral(&hash.into(), &input.into(), &amount);
         tb
       })
       .and_then(|tb| tb.try_into())
@@ -496,15 +2126,45 @@ pub unsafe extern "C" fn cardano_transaction_builder_add_key_input(
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn cardano_transaction_builder_add_script_input(
-  tb: TransactionBuilder, hash: ScriptHash, input: TransactionInput, amount: Value,
-  result: &mut TransactionBuilder, error: &mut CError,
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_add_reference_input(
+  tb: TransactionBuilder, utxo: TransactionUnspentOutput, result: &mut TransactionBuilder,
+  error: &mut CError,
 ) -> bool {
   handle_exception_result(|| {
     tb.try_into()
-      .zip(amount.try_into())
-      .map(|(mut tb, amount): (RTransactionBuilder, RValue)| {
-        tb.add_script_input(&hash.into(), &input.into(), &amount);
+      .and_then(|mut tb: TTransactionBuilder| tb.add_reference_input(utxo).map(|_| tb))
+      .and_then(|tb| tb.try_into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_set_collateral_return(
+  tb: TransactionBuilder, collateral_return: TransactionOutput, result: &mut TransactionBuilder,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    tb.try_into()
+      .and_then(|mut tb: TTransactionBuilder| {
+        tb.set_collateral_return(collateral_return).map(|_| tb)
+      })
+      .and_then(|tb| tb.try_into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_add_required_signer(
+  tb: TransactionBuilder, hash: Ed25519KeyHash, result: &mut TransactionBuilder,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    tb.try_into()
+      .map(|mut tb: TTransactionBuilder| {
+        tb.add_required_signer(&hash.into());
         tb
       })
       .and_then(|tb| tb.try_into())
@@ -515,15 +2175,19 @@ pub unsafe extern "C" fn cardano_transaction_builder_add_script_input(
 #[no_mangle]
 pub unsafe extern "C" fn cardano_transaction_builder_add_bootstrap_input(
   tb: TransactionBuilder, hash: ByronAddress, input: TransactionInput, amount: Value,
-  result: &mut TransactionBuilder, error: &mut CError,
+  script_ref: COption<ScriptRef>, result: &mut TransactionBuilder, error: &mut CError,
 ) -> bool {
   handle_exception_result(|| {
     tb.try_into()
       .zip(hash.try_into())
       .zip(amount.try_into())
+      .zip(script_ref_size(script_ref.into()))
       .map(
-        |((mut tb, hash), amount): ((RTransactionBuilder, RByronAddress), RValue)| {
-          tb.add_bootstrap_input(&hash, &input.into(), &amount);
+        |(((mut tb, hash), amount), script_size): (
+          ((TTransactionBuilder, RByronAddress), RValue),
+          usize,
+        )| {
+          tb.add_bootstrap_input(&hash, &input.into(), &amount, script_size);
           tb
         },
       )
@@ -535,15 +2199,19 @@ pub unsafe extern "C" fn cardano_transaction_builder_add_bootstrap_input(
 #[no_mangle]
 pub unsafe extern "C" fn cardano_transaction_builder_add_input(
   tb: TransactionBuilder, address: Address, input: TransactionInput, amount: Value,
-  result: &mut TransactionBuilder, error: &mut CError,
+  script_ref: COption<ScriptRef>, result: &mut TransactionBuilder, error: &mut CError,
 ) -> bool {
   handle_exception_result(|| {
     tb.try_into()
       .zip(address.try_into())
       .zip(amount.try_into())
+      .zip(script_ref_size(script_ref.into()))
       .map(
-        |((mut tb, address), amount): ((RTransactionBuilder, RAddress), RValue)| {
-          tb.add_input(&address, &input.into(), &amount);
+        |(((mut tb, address), amount), script_size): (
+          ((TTransactionBuilder, RAddress), RValue),
+          usize,
+        )| {
+          tb.add_input(&address, &input.into(), &amount, script_size);
           tb
         },
       )
@@ -562,9 +2230,8 @@ pub unsafe extern "C" fn cardano_transaction_builder_fee_for_input(
       .zip(address.try_into())
       .zip(amount.try_into())
       .and_then(
-        |((tb, address), amount): ((RTransactionBuilder, RAddress), RValue)| {
+        |((tb, address), amount): ((TTransactionBuilder, RAddress), RValue)| {
           tb.fee_for_input(&address, &input.into(), &amount)
-            .into_result()
         },
       )
       .map(|fee| from_bignum(&fee))
@@ -579,12 +2246,7 @@ pub unsafe extern "C" fn cardano_transaction_builder_add_output(
 ) -> bool {
   handle_exception_result(|| {
     tb.try_into()
-      .zip(output.try_into())
-      .and_then(
-        |(mut tb, output): (RTransactionBuilder, RTransactionOutput)| {
-          tb.add_output(&output).into_result().map(|_| tb)
-        },
-      )
+      .and_then(|mut tb: TTransactionBuilder| tb.add_output(output).map(|_| tb))
       .and_then(|tb| tb.try_into())
   })
   .response(result, error)
@@ -596,10 +2258,7 @@ pub unsafe extern "C" fn cardano_transaction_builder_fee_for_output(
 ) -> bool {
   handle_exception_result(|| {
     tb.try_into()
-      .zip(output.try_into())
-      .and_then(|(tb, output): (RTransactionBuilder, RTransactionOutput)| {
-        tb.fee_for_output(&output).into_result()
-      })
+      .and_then(|tb: TTransactionBuilder| tb.fee_for_output(output))
       .map(|fee| from_bignum(&fee))
   })
   .response(result, error)
@@ -611,11 +2270,7 @@ pub unsafe extern "C" fn cardano_transaction_builder_set_certs(
 ) -> bool {
   handle_exception_result(|| {
     tb.try_into()
-      .zip(certs.try_into())
-      .map(|(mut tb, certs): (RTransactionBuilder, RCertificates)| {
-        tb.set_certs(&certs);
-        tb
-      })
+      .and_then(|mut tb: TTransactionBuilder| tb.set_certs(certs).map(|_| tb))
       .and_then(|tb| tb.try_into())
   })
   .response(result, error)
@@ -630,7 +2285,7 @@ pub unsafe extern "C" fn cardano_transaction_builder_set_withdrawals(
     tb.try_into()
       .zip(withdrawals.try_into())
       .map(
-        |(mut tb, withdrawals): (RTransactionBuilder, RWithdrawals)| {
+        |(mut tb, withdrawals): (TTransactionBuilder, RWithdrawals)| {
           tb.set_withdrawals(&withdrawals);
           tb
         },
@@ -640,11 +2295,233 @@ pub unsafe extern "C" fn cardano_transaction_builder_set_withdrawals(
   .response(result, error)
 }
 
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_set_fee(
+  tb: TransactionBuilder, fee: Coin, result: &mut TransactionBuilder, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    tb.try_into()
+      .map(|mut tb: TTransactionBuilder| {
+        tb.set_fee(&to_bignum(fee));
+        tb
+      })
+      .and_then(|tb| tb.try_into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_set_ttl(
+  tb: TransactionBuilder, ttl: Slot, result: &mut TransactionBuilder, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    tb.try_into()
+      .map(|mut tb: TTransactionBuilder| {
+        tb.set_ttl(ttl);
+        tb
+      })
+      .and_then(|tb| tb.try_into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_set_validity_start_interval(
+  tb: TransactionBuilder, validity_start_interval: Slot, result: &mut TransactionBuilder,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    tb.try_into()
+      .map(|mut tb: TTransactionBuilder| {
+        tb.set_validity_start_interval(validity_start_interval);
+        tb
+      })
+      .and_then(|tb| tb.try_into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_set_auxiliary_data(
+  tb: TransactionBuilder, auxiliary_data: AuxiliaryData, result: &mut TransactionBuilder,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    tb.try_into()
+      .zip(auxiliary_data.try_into())
+      .map(
+        |(mut tb, auxiliary_data): (TTransactionBuilder, RAuxiliaryData)| {
+          tb.set_auxiliary_data(&auxiliary_data);
+          tb
+        },
+      )
+      .and_then(|tb| tb.try_into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_set_metadata(
+  tb: TransactionBuilder, metadata: GeneralTransactionMetadata, result: &mut TransactionBuilder,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    tb.try_into()
+      .zip(metadata.try_into())
+      .map(
+        |(mut tb, metadata): (TTransactionBuilder, RGeneralTransactionMetadata)| {
+          tb.set_metadata(&metadata);
+          tb
+        },
+      )
+      .and_then(|tb| tb.try_into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_add_metadatum(
+  tb: TransactionBuilder, key: TransactionMetadatumLabel, val: TransactionMetadatum,
+  result: &mut TransactionBuilder, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    tb.try_into()
+      .zip(val.try_into())
+      .map(|(mut tb, val): (TTransactionBuilder, RTransactionMetadatum)| {
+        tb.add_metadatum(&to_bignum(key), &val);
+        tb
+      })
+      .and_then(|tb| tb.try_into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_set_mint(
+  tb: TransactionBuilder, mint: Mint, mint_scripts: NativeScripts, result: &mut TransactionBuilder,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    tb.try_into()
+      .zip(mint.try_into())
+      .zip(mint_scripts.try_into())
+      .and_then(
+        |((mut tb, mint), mint_scripts): ((TTransactionBuilder, RMint), RNativeScripts)| {
+          tb.set_mint(&mint, &mint_scripts).into_result().map(|_| tb)
+        },
+      )
+      .and_then(|tb| tb.try_into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_set_mint_scripts(
+  tb: TransactionBuilder, mint_scripts: NativeScripts, result: &mut TransactionBuilder,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    tb.try_into()
+      .zip(mint_scripts.try_into())
+      .and_then(
+        |(mut tb, mint_scripts): (TTransactionBuilder, RNativeScripts)| {
+          tb.get_mint()
+            .ok_or("Mint should be set before mint scripts".into())
+            .and_then(|mint| tb.set_mint(&mint, &mint_scripts).into_result())
+            .map(|_| tb)
+        },
+      )
+      .and_then(|tb| tb.try_into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_set_voting_procedures(
+  tb: TransactionBuilder, voting_procedures: VotingProcedures, result: &mut TransactionBuilder,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    tb.try_into()
+      .and_then(|mut tb: TTransactionBuilder| {
+        tb.set_voting_procedures(voting_procedures).map(|_| tb)
+      })
+      .and_then(|tb| tb.try_into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_set_proposal_procedures(
+  tb: TransactionBuilder, proposal_procedures: ProposalProcedures, result: &mut TransactionBuilder,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    tb.try_into()
+      .and_then(|mut tb: TTransactionBuilder| {
+        tb.set_proposal_procedures(proposal_procedures).map(|_| tb)
+      })
+      .and_then(|tb| tb.try_into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_set_current_treasury_value(
+  tb: TransactionBuilder, current_treasury_value: Coin, result: &mut TransactionBuilder,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    tb.try_into()
+      .map(|mut tb: TTransactionBuilder| {
+        tb.set_current_treasury_value(current_treasury_value);
+        tb
+      })
+      .and_then(|tb| tb.try_into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_set_donation(
+  tb: TransactionBuilder, donation: Coin, result: &mut TransactionBuilder, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    tb.try_into()
+      .map(|mut tb: TTransactionBuilder| {
+        tb.set_donation(donation);
+        tb
+      })
+      .and_then(|tb| tb.try_into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_config_free(
+  config: &mut TransactionBuilderConfig,
+) {
+  config.free()
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn cardano_transaction_builder_new(
   config: TransactionBuilderConfig, result: &mut TransactionBuilder, error: &mut CError,
 ) -> bool {
-  handle_exception_result(|| RTransactionBuilder::new(&config.into()).try_into())
+  handle_exception_result(|| TTransactionBuilder::new(config).and_then(|tb| tb.try_into()))
     .response(result, error)
 }
 
@@ -654,7 +2531,7 @@ pub unsafe extern "C" fn cardano_transaction_builder_get_explicit_input(
 ) -> bool {
   handle_exception_result(|| {
     tb.try_into()
-      .and_then(|tb: RTransactionBuilder| tb.get_explicit_input().into_result())
+      .and_then(|tb: TTransactionBuilder| tb.get_explicit_input().into_result())
       .and_then(|input| input.try_into())
   })
   .response(result, error)
@@ -666,7 +2543,7 @@ pub unsafe extern "C" fn cardano_transaction_builder_get_implicit_input(
 ) -> bool {
   handle_exception_result(|| {
     tb.try_into()
-      .and_then(|tb: RTransactionBuilder| tb.get_implicit_input().into_result())
+      .and_then(|tb: TTransactionBuilder| tb.get_implicit_input().into_result())
       .and_then(|input| input.try_into())
   })
   .response(result, error)
@@ -678,7 +2555,7 @@ pub unsafe extern "C" fn cardano_transaction_builder_get_explicit_output(
 ) -> bool {
   handle_exception_result(|| {
     tb.try_into()
-      .and_then(|tb: RTransactionBuilder| tb.get_explicit_output().into_result())
+      .and_then(|tb: TTransactionBuilder| tb.get_explicit_output().into_result())
       .and_then(|output| output.try_into())
   })
   .response(result, error)
@@ -690,7 +2567,7 @@ pub unsafe extern "C" fn cardano_transaction_builder_get_deposit(
 ) -> bool {
   handle_exception_result(|| {
     tb.try_into()
-      .and_then(|tb: RTransactionBuilder| tb.get_deposit().into_result())
+      .and_then(|tb: TTransactionBuilder| tb.get_deposit().into_result())
       .map(|deposit| from_bignum(&deposit))
   })
   .response(result, error)
@@ -706,7 +2583,7 @@ pub unsafe extern "C" fn cardano_transaction_builder_add_change_if_needed(
 ) -> bool {
   handle_exception_result(|| {
     tb.try_into().zip(address.try_into()).and_then(
-      |(mut tb, address): (RTransactionBuilder, RAddress)| {
+      |(mut tb, address): (TTransactionBuilder, RAddress)| {
         tb.add_change_if_needed(&address)
           .into_result()
           .and_then(|result| tb.try_into().map(|tb| TransactionBuilderBool(tb, result)))
@@ -722,8 +2599,19 @@ pub unsafe extern "C" fn cardano_transaction_builder_build(
 ) -> bool {
   handle_exception_result(|| {
     tb.try_into()
-      .and_then(|tb: RTransactionBuilder| tb.build().into_result())
-      .and_then(|transaction_body| transaction_body.try_into())
+      .and_then(|tb: TTransactionBuilder| tb.build())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_witness_set(
+  tb: TransactionBuilder, result: &mut TransactionWitnessSet, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    tb.try_into()
+      .and_then(|tb: TTransactionBuilder| tb.witness_set())
   })
   .response(result, error)
 }
@@ -734,12 +2622,25 @@ pub unsafe extern "C" fn cardano_transaction_builder_min_fee(
 ) -> bool {
   handle_exception_result(|| {
     tb.try_into()
-      .and_then(|tb: RTransactionBuilder| tb.min_fee().into_result())
+      .and_then(|tb: TTransactionBuilder| tb.min_fee().into_result())
       .map(|fee| from_bignum(&fee))
   })
   .response(result, error)
 }
 
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_estimate_fee(
+  tb: TransactionBuilder, witnesses: ExpectedWitnesses, result: &mut FeeEstimate,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    tb.try_into()
+      .and_then(|tb: TTransactionBuilder| tb.estimate_fee(witnesses))
+  })
+  .response(result, error)
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn cardano_transaction_builder_clone(
   transaction_builder: TransactionBuilder, result: &mut TransactionBuilder, error: &mut CError,
@@ -753,3 +2654,328 @@ pub unsafe extern "C" fn cardano_transaction_builder_free(
 ) {
   transaction_builder.free()
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+  use super::*;
+  use cardano_serialization_lib::crypto::TransactionHash as RTransactionHash;
+  use cardano_serialization_lib::plutus::ExUnits as RExUnits;
+  use cardano_serialization_lib::utils::BigInt as RBigInt;
+
+  pub(crate) fn config() -> TransactionBuilderConfig {
+    TransactionBuilderConfig {
+      fee_algo: LinearFee {
+        constant: 155381,
+        coefficient: 44,
+      },
+      pool_deposit: 500000000,
+      key_deposit: 2000000,
+      max_value_size: 5000,
+      max_tx_size: 16384,
+      coins_per_utxo_word: 34482,
+      prefer_pure_change: false,
+      ex_unit_prices: COption::None,
+      cost_models: COption::None,
+      ref_script_coins_per_byte: COption::None,
+      coins_per_utxo_byte: COption::Some(4310),
+    }
+  }
+
+  pub(crate) fn key(byte: u8) -> (REd25519KeyHash, RAddress) {
+    let hash = REd25519KeyHash::from_bytes(vec![byte; 28]).unwrap();
+    let credential = cardano_serialization_lib::address::StakeCredential::from_keyhash(&hash);
+    (hash, REnterpriseAddress::new(0, &credential).to_address())
+  }
+
+  pub(crate) fn input(index: u32) -> RTransactionInput {
+    RTransactionInput::new(&RTransactionHash::from_bytes(vec![7; 32]).unwrap(), index)
+  }
+
+  pub(crate) fn coin(coin: Coin) -> RValue {
+    RValue::new(&to_bignum(coin))
+  }
+
+  fn utxos(address: &RAddress, coins: &[Coin]) -> Vec<TransactionUnspentOutput> {
+    coins
+      .iter()
+      .enumerate()
+      .map(|(index, &amount)| {
+        let output = RTransactionOutput::new(address, &coin(amount));
+        RTransactionUnspentOutput::new(&input(index as u32), &output).try_into().unwrap()
+      })
+      .collect()
+  }
+
+  fn balance(tb: &TTransactionBuilder) -> (Coin, Coin) {
+    let input = from_bignum(&tb.get_explicit_input().unwrap().coin());
+    let output = from_bignum(&tb.get_explicit_output().unwrap().coin())
+      + from_bignum(&tb.get_fee_if_set().unwrap())
+      + tb.donation.unwrap_or(0);
+    (input, output)
+  }
+
+  #[test]
+  fn change_pays_for_the_donation() {
+    let (hash, address) = key(1);
+    let mut tb = TTransactionBuilder::new(config()).unwrap();
+    tb.add_key_input(&hash, &input(0), &coin(10_000_000), 0);
+    tb.set_donation(2_000_000);
+    assert!(tb.add_change_if_needed(&address).unwrap());
+    let (input, output) = balance(&tb);
+    assert_eq!(input, output);
+    assert!(from_bignum(&tb.get_fee_if_set().unwrap()) >= from_bignum(&tb.min_fee().unwrap()));
+  }
+
+  #[test]
+  fn small_leftover_is_paid_as_fee() {
+    let (hash, address) = key(1);
+    let mut tb = TTransactionBuilder::new(config()).unwrap();
+    tb.add_key_input(&hash, &input(0), &coin(3_000_000), 0);
+    tb.set_donation(2_500_000);
+    assert!(!tb.add_change_if_needed(&address).unwrap());
+    assert_eq!(from_bignum(&tb.get_fee_if_set().unwrap()), 500_000);
+    let mut tb = TTransactionBuilder::new(config()).unwrap();
+    tb.add_key_input(&hash, &input(0), &coin(3_000_000), 0);
+    tb.set_donation(3_000_000);
+    assert!(tb.add_change_if_needed(&address).is_err());
+  }
+
+  #[test]
+  fn selection_covers_the_donation() {
+    let (_, address) = key(1);
+    let utxos = utxos(&address, &[3_000_000, 5_000_000, 4_000_000]);
+    let mut tb = TTransactionBuilder::new(config()).unwrap();
+    let output = RTransactionOutput::new(&address, &coin(1_000_000));
+    tb.add_output(output.try_into().unwrap()).unwrap();
+    tb.set_donation(6_000_000);
+    tb.add_inputs_from(&utxos, RCoinSelectionStrategyCIP2::LargestFirst).unwrap();
+    assert_eq!(tb.inputs.len(), 2);
+    assert!(tb.add_change_if_needed(&address).unwrap());
+    let (input, output) = balance(&tb);
+    assert_eq!(input, output);
+    let mut tb = TTransactionBuilder::new(config()).unwrap();
+    tb.set_donation(20_000_000);
+    assert!(tb.add_inputs_from(&utxos, RCoinSelectionStrategyCIP2::LargestFirst).is_err());
+    for mut utxo in utxos {
+      unsafe { utxo.free() };
+    }
+  }
+
+  #[test]
+  fn selection_tops_up_with_many_small_inputs() {
+    let (_, address) = key(1);
+    let utxos = utxos(&address, &[1_000_000; 200]);
+    let mut tb = TTransactionBuilder::new(config()).unwrap();
+    tb.set_donation(150_000_000);
+    tb.add_inputs_from(&utxos, RCoinSelectionStrategyCIP2::LargestFirst).unwrap();
+    let input = from_bignum(&tb.get_explicit_input().unwrap().coin());
+    let fee = from_bignum(&tb.min_fee().unwrap());
+    assert!(input >= 150_000_000 + fee);
+    // and not a whole input more
+    assert!(input - 1_000_000 < 150_000_000 + fee);
+    for mut utxo in utxos {
+      unsafe { utxo.free() };
+    }
+  }
+
+  #[test]
+  fn input_fee_counts_known_signers_once() {
+    let (hash, address) = key(1);
+    let mut tb = TTransactionBuilder::new(config()).unwrap();
+    tb.add_key_input(&key(2).0, &input(0), &coin(5_000_000), 0);
+    tb.add_required_signer(&hash);
+    let amount = coin(1_000_000);
+    let fee = from_bignum(&tb.fee_for_input(&address, &input(1), &amount).unwrap());
+    let legacy = from_bignum(&tb.builder.fee_for_input(&address, &input(1), &amount).unwrap());
+    // the witness of the required signer is paid already
+    assert!(fee < legacy);
+    assert!(fee > 0);
+  }
+
+  fn plutus_witness() -> PlutusWitness {
+    let data = RPlutusData::new_integer(&RBigInt::from_str("42").unwrap());
+    let ex_units = RExUnits::new(&to_bignum(1000), &to_bignum(1000));
+    let redeemer = RRedeemer::new(&RRedeemerTag::new_spend(), &to_bignum(0), &data, &ex_units);
+    PlutusWitness {
+      script: PlutusScriptSource::PlutusScriptKind(PlutusScript::new(
+        vec![0x4d, 0x01, 0x00, 0x00, 0x33, 0x22, 0x22, 0x20, 0x05, 0x12, 0x00, 0x12, 0x00, 0x11],
+        Language::PlutusV2,
+      )),
+      datum: COption::Some(data.try_into().unwrap()),
+      redeemer: redeemer.try_into().unwrap(),
+    }
+  }
+
+  #[test]
+  fn plutus_input_needs_collateral_and_sets_script_data_hash() {
+    let (hash, address) = key(1);
+    let mut config = config();
+    let mut cost_models = BTreeMap::new();
+    cost_models.insert(Language::PlutusV2, vec![1; 175]);
+    config.cost_models = COption::Some(cost_models.into());
+    let mut tb = TTransactionBuilder::new(config).unwrap();
+    tb.add_plutus_script_input(plutus_witness(), &input(0), &coin(10_000_000), 0).unwrap();
+    tb.add_output(RTransactionOutput::new(&address, &coin(5_000_000)).try_into().unwrap())
+      .unwrap();
+    tb.set_fee(&to_bignum(1_000_000));
+    assert!(tb.build().is_err());
+    tb.add_collateral(&hash, &input(1), &coin(5_000_000));
+    let body = RTransactionBody::from_bytes(tb.build().unwrap().to_cbor().unwrap()).unwrap();
+    assert!(body.script_data_hash().is_some());
+    assert_eq!(body.collateral().unwrap().len(), 1);
+  }
+
+  #[test]
+  fn ref_script_fee_grows_by_tier() {
+    let (_, address) = key(1);
+    let mut config = config();
+    config.ref_script_coins_per_byte = COption::Some(SubCoin::new(15, 1));
+    let mut tb = TTransactionBuilder::new(config).unwrap();
+    assert_eq!(tb.ref_script_fee().unwrap(), 0);
+    let output: TransactionOutput = RTransactionOutput::new(&address, &coin(2_000_000))
+      .try_into()
+      .unwrap();
+    let mut reference_input: TReferenceInput = output.try_into().unwrap();
+    reference_input.script_size = 30_000;
+    tb.reference_inputs.insert(input(0), reference_input);
+    // 25600 bytes at 15 and 4400 bytes at 18
+    assert_eq!(tb.ref_script_fee().unwrap(), 463_200);
+  }
+
+  #[test]
+  fn spent_inputs_pay_for_their_reference_scripts() {
+    let (hash, address) = key(1);
+    let mut config = config();
+    config.ref_script_coins_per_byte = COption::Some(SubCoin::new(15, 1));
+    let mut tb = TTransactionBuilder::new(config).unwrap();
+    tb.add_key_input(&hash, &input(0), &coin(3_000_000), 30_000);
+    assert_eq!(tb.ref_script_fee().unwrap(), 463_200);
+    // Enterprise testnet address and a Plutus V2 reference script of 100 bytes
+    let output = crate::test_utils::hex(&format!(
+      "a300581d60{}011a004c4b4003d818586882025864{}",
+      "00".repeat(28),
+      "00".repeat(100),
+    ));
+    let mut utxo = TransactionUnspentOutput {
+      input: input(1).try_into().unwrap(),
+      output: TransactionOutput::from_cbor(&output).unwrap(),
+    };
+    let mut tb = TTransactionBuilder::new(config).unwrap();
+    let output = RTransactionOutput::new(&address, &coin(1_000_000));
+    tb.add_output(output.try_into().unwrap()).unwrap();
+    tb.add_inputs_from(&[utxo], RCoinSelectionStrategyCIP2::LargestFirst).unwrap();
+    assert_eq!(tb.inputs[0].script_size, 100);
+    assert_eq!(tb.ref_script_fee().unwrap(), 1_500);
+    unsafe { utxo.free() };
+  }
+
+  #[test]
+  fn babbage_min_ada_overrides_the_alonzo_one() {
+    let (hash, address) = key(1);
+    let mut tb = TTransactionBuilder::new(config()).unwrap();
+    let output: TransactionOutput = RTransactionOutput::new(&address, &coin(900_000))
+      .try_into()
+      .unwrap();
+    assert_eq!(tb.config.min_ada_required(&output).unwrap(), 849_070);
+    tb.add_output(output).unwrap();
+    let output = RTransactionOutput::new(&address, &coin(849_069));
+    assert!(tb.add_output(output.try_into().unwrap()).is_err());
+    let mut tb = TTransactionBuilder::new(config()).unwrap();
+    tb.add_key_input(&hash, &input(0), &coin(1_100_000), 0);
+    assert!(tb.add_change_if_needed(&address).unwrap());
+    let change = from_bignum(&tb.outputs[0].legacy.amount().coin());
+    assert!((849_070..999_920).contains(&change));
+  }
+
+  #[test]
+  fn c_builder_keeps_the_kind_of_each_input() {
+    let (hash, address) = key(1);
+    let script = RScriptHash::from_bytes(vec![2; 28]).unwrap();
+    let byron = "Ae2tdPwUPEZ5uzkzh1o2DHECiUi3iugvnnKHRisPgRRP3CTF4KCMvy54Xd3";
+    let byron = RByronAddress::from_base58(byron).unwrap();
+    let mut tb = TTransactionBuilder::new(config()).unwrap();
+    tb.add_key_input(&hash, &input(0), &coin(3_000_000), 0);
+    tb.add_key_input(&hash, &input(1), &coin(3_000_000), 0);
+    tb.add_script_input(&script, &input(2), &coin(3_000_000), 0);
+    tb.add_bootstrap_input(&byron, &input(3), &coin(3_000_000), 0);
+    let fee = from_bignum(&tb.min_fee().unwrap());
+    let c_tb: TransactionBuilder = tb.try_into().unwrap();
+    let mut tb: TTransactionBuilder = c_tb.try_into().unwrap();
+    assert_eq!(from_bignum(&tb.min_fee().unwrap()), fee);
+    assert!(matches!(tb.input_kinds[2], TTxBuilderInputKind::Script(_)));
+    assert!(matches!(tb.input_kinds[3], TTxBuilderInputKind::Bootstrap(_)));
+    let inputs = tb.inputs.clone();
+    assert!(tb.add_typed_inputs(inputs, vec![TTxBuilderInputKind::Key(hash)]).is_err());
+    assert!(tb.add_change_if_needed(&address).unwrap());
+  }
+
+  #[test]
+  fn one_bootstrap_witness_per_byron_address() {
+    use cardano_serialization_lib::crypto::Bip32PrivateKey as RBip32PrivateKey;
+    let byron = |byte: u8| {
+      let key = RBip32PrivateKey::from_bip39_entropy(&[byte; 16], &[]).to_public();
+      RByronAddress::icarus_from_key(&key, 764824073)
+    };
+    let (first, second) = (byron(1), byron(2));
+    assert_eq!(first.attributes(), second.attributes());
+    let addresses = vec![first.clone(), second, first];
+    let expected = ExpectedWitnesses {
+      vkeys: Vec::<Ed25519KeyHash>::new().into(),
+      bootstraps: addresses.into_iter().map(ByronAddress::from).collect::<Vec<_>>().into(),
+      native_scripts: RNativeScripts::new().try_into().unwrap(),
+    };
+    let expected: TExpectedWitnesses = expected.try_into().unwrap();
+    let entries = expected.entries().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].0, 2);
+    // Array of two bootstrap witnesses
+    assert_eq!(entries[0].1[0], 0x82);
+  }
+
+  // Runs a C setter on the builder in place, freeing the one it replaces
+  fn set(
+    tb: &mut TransactionBuilder,
+    setter: impl FnOnce(TransactionBuilder, &mut TransactionBuilder, &mut CError) -> bool,
+  ) {
+    let mut result = *tb;
+    let mut error = CError::NullPtr;
+    assert!(setter(*tb, &mut result, &mut error));
+    unsafe { tb.free() };
+    *tb = result;
+  }
+
+  #[test]
+  fn c_setters_reach_the_body() {
+    use cardano_serialization_lib::utils::Int as RInt;
+    let (hash, address) = key(1);
+    let mut tb = TTransactionBuilder::new(config()).unwrap();
+    tb.add_key_input(&hash, &input(0), &coin(3_000_000), 0);
+    let output = RTransactionOutput::new(&address, &coin(2_800_000));
+    tb.add_output(output.try_into().unwrap()).unwrap();
+    let mut c_tb: TransactionBuilder = tb.try_into().unwrap();
+    let metadatum = RTransactionMetadatum::new_int(&RInt::new_i32(7));
+    unsafe {
+      set(&mut c_tb, |tb, result, error| {
+        cardano_transaction_builder_set_fee(tb, 200_000, result, error)
+      });
+      set(&mut c_tb, |tb, result, error| {
+        cardano_transaction_builder_set_ttl(tb, 1000, result, error)
+      });
+      set(&mut c_tb, |tb, result, error| {
+        cardano_transaction_builder_set_validity_start_interval(tb, 500, result, error)
+      });
+      set(&mut c_tb, |tb, result, error| {
+        let metadatum = metadatum.try_into().unwrap();
+        cardano_transaction_builder_add_metadatum(tb, 674, metadatum, result, error)
+      });
+    }
+    let tb: TTransactionBuilder = c_tb.try_into().unwrap();
+    let body = tb.body().unwrap();
+    assert_eq!(from_bignum(&body.fee()), 200_000);
+    assert_eq!(body.ttl(), Some(1000));
+    assert_eq!(body.validity_start_interval(), Some(500));
+    assert!(body.auxiliary_data_hash().is_some());
+    let metadata = tb.get_auxiliary_data().unwrap().metadata().unwrap();
+    assert!(metadata.get(&to_bignum(674)).is_some());
+  }
+}
@@ -0,0 +1,687 @@
+use crate::address::address::Address;
+use crate::address::byron::ByronAddress;
+use crate::address::pointer::Slot;
+use crate::certificate::Certificates;
+use crate::error::CError;
+use crate::general_transaction_metadata::{GeneralTransactionMetadata, TransactionMetadatum};
+use crate::linear_fee::Coin;
+use crate::option::COption;
+use crate::panic::*;
+use crate::proposal_procedure::ProposalProcedures;
+use crate::ptr::Ptr;
+use crate::stake_credential::{Ed25519KeyHash, ScriptHash};
+use crate::transaction_body::{Mint, TransactionBody};
+use crate::transaction_builder::{
+  script_ref_size, CoinSelectionStrategyCIP2, ExpectedWitnesses, FeeEstimate, PlutusWitness,
+  TTransactionBuilder, TransactionBuilder, TransactionBuilderConfig,
+};
+use crate::transaction_input::TransactionInput;
+use crate::transaction_metadata::{AuxiliaryData, NativeScripts};
+use crate::transaction_metadatum_labels::TransactionMetadatumLabel;
+use crate::transaction_output::{ScriptRef, TransactionOutput};
+use crate::transaction_unspent_output::{TransactionUnspentOutput, TransactionUnspentOutputs};
+use crate::transaction_witness_set::TransactionWitnessSet;
+use crate::value::Value;
+use crate::voting_procedure::VotingProcedures;
+use crate::withdrawals::Withdrawals;
+use cardano_serialization_lib::{
+  address::{Address as RAddress, ByronAddress as RByronAddress},
+  metadata::{
+    AuxiliaryData as RAuxiliaryData, GeneralTransactionMetadata as RGeneralTransactionMetadata,
+    TransactionMetadatum as RTransactionMetadatum,
+  },
+  utils::{from_bignum, to_bignum},
+  Mint as RMint, NativeScripts as RNativeScripts, Withdrawals as RWithdrawals,
+};
+use std::convert::TryInto;
+
+// Opaque heap-allocated builder. Methods mutate it in place instead of
+// converting the whole TransactionBuilder struct on every call.
+pub struct TransactionBuilderHandle {
+  builder: TTransactionBuilder,
+}
+
+impl TransactionBuilderHandle {
+  fn new(builder: TTransactionBuilder) -> *mut Self {
+    Box::into_raw(Box::new(Self { builder }))
+  }
+
+  unsafe fn builder<'a>(handle: *const Self) -> Result<&'a TTransactionBuilder> {
+    handle
+      .as_ref()
+      .map(|handle| &handle.builder)
+      .ok_or(CError::NullPtr)
+  }
+
+  unsafe fn builder_mut<'a>(handle: *mut Self) -> Result<&'a mut TTransactionBuilder> {
+    handle
+      .as_mut()
+      .map(|handle| &mut handle.builder)
+      .ok_or(CError::NullPtr)
+  }
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_new(
+  config: TransactionBuilderConfig, result: &mut *mut TransactionBuilderHandle,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| TTransactionBuilder::new(config).map(TransactionBuilderHandle::new))
+    .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_from_transaction_builder(
+  tb: TransactionBuilder, result: &mut *mut TransactionBuilderHandle, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| tb.try_into().map(TransactionBuilderHandle::new))
+    .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_snapshot(
+  handle: *const TransactionBuilderHandle, result: &mut TransactionBuilder, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder(handle).and_then(|tb| tb.clone().try_into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_add_inputs_from(
+  handle: *mut TransactionBuilderHandle, inputs: TransactionUnspentOutputs,
+  strategy: CoinSelectionStrategyCIP2, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    let inputs = inputs.unowned()?;
+    TransactionBuilderHandle::builder_mut(handle)
+      .and_then(|tb| tb.add_inputs_from(inputs, strategy.into()))
+  })
+  .response(&mut (), error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_add_key_input(
+  handle: *mut TransactionBuilderHandle, hash: Ed25519KeyHash, input: TransactionInput,
+  amount: Value, script_ref: COption<ScriptRef>, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder_mut(handle)
+      .zip(amount.try_into())
+      .zip(script_ref_size(script_ref.into()))
+      .map(|((tb, amount), script_size)| {
+        tb.add_key_input(&hash.into(), &input.into(), &amount, script_size)
+      })
+  })
+  .response(&mut (), error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_add_script_input(
+  handle: *mut TransactionBuilderHandle, hash: ScriptHash, input: TransactionInput, amount: Value,
+  script_ref: COption<ScriptRef>, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder_mut(handle)
+      .zip(amount.try_into())
+      .zip(script_ref_size(script_ref.into()))
+      .map(|((tb, amount), script_size)| {
+        tb.add_script_input(&hash.into(), &input.into(), &amount, script_size)
+      })
+  })
+  .response(&mut (), error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_add_plutus_script_input(
+  handle: *mut TransactionBuilderHandle, witness: PlutusWitness, input: TransactionInput,
+  amount: Value, script_ref: COption<ScriptRef>, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder_mut(handle)
+      .zip(amount.try_into())
+      .zip(script_ref_size(script_ref.into()))
+      .and_then(|((tb, amount), script_size)| {
+        tb.add_plutus_script_input(witness, &input.into(), &amount, script_size)
+      })
+  })
+  .response(&mut (), error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_add_collateral(
+  handle: *mut TransactionBuilderHandle, hash: Ed25519KeyHash, input: TransactionInput,
+  amount: Value, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder_mut(handle)
+      .zip(amount.try_into())
+      .map(|(tb, amount)| tb.add_collateral(&hash.into(), &input.into(), &amount))
+  })
+  .response(&mut (), error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_add_reference_input(
+  handle: *mut TransactionBuilderHandle, utxo: TransactionUnspentOutput, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder_mut(handle).and_then(|tb| tb.add_reference_input(utxo))
+  })
+  .response(&mut (), error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_set_collateral_return(
+  handle: *mut TransactionBuilderHandle, collateral_return: TransactionOutput, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder_mut(handle)
+      .and_then(|tb| tb.set_collateral_return(collateral_return))
+  })
+  .response(&mut (), error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_add_required_signer(
+  handle: *mut TransactionBuilderHandle, hash: Ed25519KeyHash, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder_mut(handle).map(|tb| tb.add_required_signer(&hash.into()))
+  })
+  .response(&mut (), error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_add_bootstrap_input(
+  handle: *mut TransactionBuilderHandle, hash: ByronAddress, input: TransactionInput,
+  amount: Value, script_ref: COption<ScriptRef>, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder_mut(handle)
+      .zip(hash.try_into())
+      .zip(amount.try_into())
+      .zip(script_ref_size(script_ref.into()))
+      .map(|(((tb, hash), amount), script_size): (((_, RByronAddress), _), _)| {
+        tb.add_bootstrap_input(&hash, &input.into(), &amount, script_size)
+      })
+  })
+  .response(&mut (), error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_add_input(
+  handle: *mut TransactionBuilderHandle, address: Address, input: TransactionInput, amount: Value,
+  script_ref: COption<ScriptRef>, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder_mut(handle)
+      .zip(address.try_into())
+      .zip(amount.try_into())
+      .zip(script_ref_size(script_ref.into()))
+      .map(|(((tb, address), amount), script_size): (((_, RAddress), _), _)| {
+        tb.add_input(&address, &input.into(), &amount, script_size)
+      })
+  })
+  .response(&mut (), error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_fee_for_input(
+  handle: *const TransactionBuilderHandle, address: Address, input: TransactionInput,
+  amount: Value, result: &mut Coin, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder(handle)
+      .zip(address.try_into())
+      .zip(amount.try_into())
+      .and_then(|((tb, address), amount): ((_, RAddress), _)| {
+        tb.fee_for_input(&address, &input.into(), &amount)
+      })
+      .map(|fee| from_bignum(&fee))
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_add_output(
+  handle: *mut TransactionBuilderHandle, output: TransactionOutput, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder_mut(handle).and_then(|tb| tb.add_output(output))
+  })
+  .response(&mut (), error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_fee_for_output(
+  handle: *const TransactionBuilderHandle, output: TransactionOutput, result: &mut Coin,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder(handle).and_then(|tb| tb.fee_for_output(output))
+      .map(|fee| from_bignum(&fee))
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_set_certs(
+  handle: *mut TransactionBuilderHandle, certs: Certificates, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder_mut(handle).and_then(|tb| tb.set_certs(certs))
+  })
+  .response(&mut (), error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_set_withdrawals(
+  handle: *mut TransactionBuilderHandle, withdrawals: Withdrawals, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder_mut(handle)
+      .zip(withdrawals.try_into())
+      .map(|(tb, withdrawals): (_, RWithdrawals)| tb.set_withdrawals(&withdrawals))
+  })
+  .response(&mut (), error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_set_fee(
+  handle: *mut TransactionBuilderHandle, fee: Coin, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder_mut(handle).map(|tb| tb.set_fee(&to_bignum(fee)))
+  })
+  .response(&mut (), error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_set_ttl(
+  handle: *mut TransactionBuilderHandle, ttl: Slot, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder_mut(handle).map(|tb| tb.set_ttl(ttl))
+  })
+  .response(&mut (), error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_set_validity_start_interval(
+  handle: *mut TransactionBuilderHandle, validity_start_interval: Slot, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder_mut(handle)
+      .map(|tb| tb.set_validity_start_interval(validity_start_interval))
+  })
+  .response(&mut (), error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_set_auxiliary_data(
+  handle: *mut TransactionBuilderHandle, auxiliary_data: AuxiliaryData, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder_mut(handle)
+      .zip(auxiliary_data.try_into())
+      .map(|(tb, auxiliary_data): (_, RAuxiliaryData)| tb.set_auxiliary_data(&auxiliary_data))
+  })
+  .response(&mut (), error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_set_metadata(
+  handle: *mut TransactionBuilderHandle, metadata: GeneralTransactionMetadata, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder_mut(handle)
+      .zip(metadata.try_into())
+      .map(|(tb, metadata): (_, RGeneralTransactionMetadata)| tb.set_metadata(&metadata))
+  })
+  .response(&mut (), error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_add_metadatum(
+  handle: *mut TransactionBuilderHandle, key: TransactionMetadatumLabel, val: TransactionMetadatum,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder_mut(handle)
+      .zip(val.try_into())
+      .map(|(tb, val): (_, RTransactionMetadatum)| tb.add_metadatum(&to_bignum(key), &val))
+  })
+  .response(&mut (), error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_set_mint(
+  handle: *mut TransactionBuilderHandle, mint: Mint, mint_scripts: NativeScripts,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder_mut(handle)
+      .zip(mint.try_into())
+      .zip(mint_scripts.try_into())
+      .and_then(|((tb, mint), mint_scripts): ((_, RMint), RNativeScripts)| {
+        tb.set_mint(&mint, &mint_scripts).into_result()
+      })
+  })
+  .response(&mut (), error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_set_mint_scripts(
+  handle: *mut TransactionBuilderHandle, mint_scripts: NativeScripts, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder_mut(handle)
+      .zip(mint_scripts.try_into())
+      .and_then(|(tb, mint_scripts): (_, RNativeScripts)| {
+        tb.get_mint()
+          .ok_or("Mint should be set before mint scripts".into())
+          .and_then(|mint| tb.set_mint(&mint, &mint_scripts).into_result())
+      })
+  })
+  .response(&mut (), error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_set_voting_procedures(
+  handle: *mut TransactionBuilderHandle, voting_procedures: VotingProcedures, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder_mut(handle)
+      .and_then(|tb| tb.set_voting_procedures(voting_procedures))
+  })
+  .response(&mut (), error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_set_proposal_procedures(
+  handle: *mut TransactionBuilderHandle, proposal_procedures: ProposalProcedures,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder_mut(handle)
+      .and_then(|tb| tb.set_proposal_procedures(proposal_procedures))
+  })
+  .response(&mut (), error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_set_current_treasury_value(
+  handle: *mut TransactionBuilderHandle, current_treasury_value: Coin, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder_mut(handle)
+      .map(|tb| tb.set_current_treasury_value(current_treasury_value))
+  })
+  .response(&mut (), error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_set_donation(
+  handle: *mut TransactionBuilderHandle, donation: Coin, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder_mut(handle).map(|tb| tb.set_donation(donation))
+  })
+  .response(&mut (), error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_get_explicit_input(
+  handle: *const TransactionBuilderHandle, result: &mut Value, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder(handle)
+      .and_then(|tb| tb.get_explicit_input().into_result())
+      .and_then(|input| input.try_into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_get_implicit_input(
+  handle: *const TransactionBuilderHandle, result: &mut Value, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder(handle)
+      .and_then(|tb| tb.get_implicit_input().into_result())
+      .and_then(|input| input.try_into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_get_explicit_output(
+  handle: *const TransactionBuilderHandle, result: &mut Value, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder(handle)
+      .and_then(|tb| tb.get_explicit_output().into_result())
+      .and_then(|output| output.try_into())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_get_deposit(
+  handle: *const TransactionBuilderHandle, result: &mut Coin, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder(handle)
+      .and_then(|tb| tb.get_deposit().into_result())
+      .map(|deposit| from_bignum(&deposit))
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_add_change_if_needed(
+  handle: *mut TransactionBuilderHandle, address: Address, result: &mut bool, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder_mut(handle)
+      .zip(address.try_into())
+      .and_then(|(tb, address): (_, RAddress)| tb.add_change_if_needed(&address).into_result())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_build(
+  handle: *const TransactionBuilderHandle, result: &mut TransactionBody, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder(handle)
+      .and_then(|tb| tb.build())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_witness_set(
+  handle: *const TransactionBuilderHandle, result: &mut TransactionWitnessSet, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder(handle).and_then(|tb| tb.witness_set())
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_min_fee(
+  handle: *const TransactionBuilderHandle, result: &mut Coin, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder(handle)
+      .and_then(|tb| tb.min_fee().into_result())
+      .map(|fee| from_bignum(&fee))
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_estimate_fee(
+  handle: *const TransactionBuilderHandle, witnesses: ExpectedWitnesses,
+  result: &mut FeeEstimate, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder(handle).and_then(|tb| tb.estimate_fee(witnesses))
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_clone(
+  handle: *const TransactionBuilderHandle, result: &mut *mut TransactionBuilderHandle,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    TransactionBuilderHandle::builder(handle).map(|tb| TransactionBuilderHandle::new(tb.clone()))
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_builder_handle_free(
+  handle: &mut *mut TransactionBuilderHandle,
+) {
+  if !handle.is_null() {
+    let _ = Box::from_raw(*handle);
+    *handle = std::ptr::null_mut();
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::cbor::{CborDeserialize, CborSerialize};
+  use crate::ptr::Free;
+  use crate::test_utils::hex;
+  use crate::transaction_builder::tests::{coin, config, input, key};
+  use cardano_serialization_lib::TransactionOutput as RTransactionOutput;
+
+  // DRep vote signed by key(2) and an info action with a 100 ada deposit
+  fn votes() -> VotingProcedures {
+    let votes = format!("a18202581c{}a1825820{}008201f6", "02".repeat(28), "07".repeat(32));
+    VotingProcedures::from_cbor(&hex(&votes)).unwrap()
+  }
+
+  fn proposals() -> ProposalProcedures {
+    let proposals =
+      format!("81841a05f5e100581de0{}810682605820{}", "03".repeat(28), "09".repeat(32));
+    ProposalProcedures::from_cbor(&hex(&proposals)).unwrap()
+  }
+
+  #[test]
+  fn handle_builds_like_the_struct_builder() {
+    let (hash, address) = key(1);
+    let output = RTransactionOutput::new(&address, &coin(1_000_000));
+    let (mut votes, mut proposals) = (votes(), proposals());
+    let mut tb = TTransactionBuilder::new(config()).unwrap();
+    tb.add_key_input(&hash, &input(0), &coin(300_000_000), 0);
+    tb.add_output(output.clone().try_into().unwrap()).unwrap();
+    tb.set_voting_procedures(votes).unwrap();
+    tb.set_proposal_procedures(proposals).unwrap();
+    tb.set_current_treasury_value(5_000_000);
+    tb.set_donation(2_000_000);
+    assert!(tb.add_change_if_needed(&address).unwrap());
+    let fee = from_bignum(&tb.min_fee().unwrap());
+    let mut body = tb.build().unwrap();
+
+    let mut error = CError::NullPtr;
+    let mut handle = std::ptr::null_mut();
+    let (mut added, mut handle_fee) = (false, 0);
+    unsafe {
+      assert!(cardano_transaction_builder_handle_new(config(), &mut handle, &mut error));
+      assert!(cardano_transaction_builder_handle_add_key_input(
+        handle,
+        hash.try_into().unwrap(),
+        input(0).try_into().unwrap(),
+        coin(300_000_000).try_into().unwrap(),
+        COption::None,
+        &mut error,
+      ));
+      let output = output.try_into().unwrap();
+      assert!(cardano_transaction_builder_handle_add_output(handle, output, &mut error));
+      assert!(cardano_transaction_builder_handle_set_voting_procedures(handle, votes, &mut error));
+      assert!(cardano_transaction_builder_handle_set_proposal_procedures(
+        handle, proposals, &mut error,
+      ));
+      assert!(cardano_transaction_builder_handle_set_current_treasury_value(
+        handle, 5_000_000, &mut error,
+      ));
+      assert!(cardano_transaction_builder_handle_set_donation(handle, 2_000_000, &mut error));
+      let mut snapshot = std::mem::zeroed();
+      assert!(cardano_transaction_builder_handle_snapshot(handle, &mut snapshot, &mut error));
+      let address = address.try_into().unwrap();
+      assert!(cardano_transaction_builder_handle_add_change_if_needed(
+        handle, address, &mut added, &mut error,
+      ));
+      assert!(cardano_transaction_builder_handle_min_fee(handle, &mut handle_fee, &mut error));
+      let mut handle_body = std::mem::zeroed();
+      assert!(cardano_transaction_builder_handle_build(handle, &mut handle_body, &mut error));
+      assert_eq!(handle_body.to_cbor().unwrap(), body.to_cbor().unwrap());
+      // The snapshot is a copy, the change added after it isn't in there
+      let copy: TTransactionBuilder = snapshot.try_into().unwrap();
+      let explicit_output = copy.get_explicit_output().unwrap();
+      assert_eq!(from_bignum(&explicit_output.coin()), 1_000_000);
+      snapshot.free();
+      handle_body.free();
+      body.free();
+      votes.free();
+      proposals.free();
+      cardano_transaction_builder_handle_free(&mut handle);
+      assert!(handle.is_null());
+      assert!(!cardano_transaction_builder_handle_min_fee(handle, &mut handle_fee, &mut error));
+    }
+    assert!(added);
+    assert_eq!(handle_fee, fee);
+  }
+}
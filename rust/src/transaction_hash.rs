@@ -2,10 +2,10 @@ use super::data::CData;
 use super::error::CError;
 use super::panic::*;
 use super::ptr::Ptr;
+use crate::cbor::*;
 use crate::transaction_body::TransactionBody;
-use cardano_serialization_lib::{
-  crypto::TransactionHash as RTransactionHash, utils::hash_transaction,
-};
+use cardano_serialization_lib::crypto::TransactionHash as RTransactionHash;
+use cryptoxide::blake2b::Blake2b;
 use std::convert::{TryFrom, TryInto};
 
 #[repr(C)]
@@ -30,17 +30,22 @@ impl From<TransactionHash> for RTransactionHash {
   }
 }
 
+// Hash of the body as encoded, whatever encoding the bytes use
+pub fn hash_body_bytes(bytes: &[u8]) -> TransactionHash {
+  let mut hash = [0; 32];
+  Blake2b::blake2b(&mut hash, bytes, &[]);
+  TransactionHash(hash)
+}
+
+pub fn hash_transaction(tx_body: &TransactionBody) -> Result<TransactionHash> {
+  tx_body.to_cbor().map(|bytes| hash_body_bytes(&bytes))
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn cardano_transaction_hash_hash_transaction(
   tx_body: TransactionBody, result: &mut TransactionHash, error: &mut CError,
 ) -> bool {
-  handle_exception_result(|| {
-    tx_body
-      .try_into()
-      .map(|tx_body| hash_transaction(&tx_body))
-      .and_then(|transaction_hash| transaction_hash.try_into())
-  })
-  .response(result, error)
+  handle_exception_result(|| hash_transaction(&tx_body)).response(result, error)
 }
 
 #[no_mangle]
@@ -66,3 +71,19 @@ pub unsafe extern "C" fn cardano_transaction_hash_from_bytes(
   })
   .response(result, error)
 }
+
+impl CborSerialize for TransactionHash {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    se.write_bytes(self.0)?;
+    Ok(())
+  }
+}
+
+impl CborDeserialize for TransactionHash {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    de.bytes()?
+      .try_into()
+      .map(Self)
+      .map_err(|_| CError::DataLengthMismatch)
+  }
+}
@@ -1,5 +1,6 @@
 use super::transaction_hash::TransactionHash;
 use crate::array::CArray;
+use crate::cbor::*;
 use crate::data::CData;
 use crate::error::CError;
 use crate::panic::*;
@@ -7,6 +8,7 @@ use crate::ptr::*;
 use cardano_serialization_lib::{
   TransactionInput as RTransactionInput, TransactionInputs as RTransactionInputs,
 };
+use cbor_event::Len;
 use std::convert::{TryFrom, TryInto};
 
 pub type TransactionIndex = u32;
@@ -41,6 +43,22 @@ impl TryFrom<RTransactionInput> for TransactionInput {
   }
 }
 
+impl CborSerialize for TransactionInput {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    let transaction_input: RTransactionInput = (*self).into();
+    se.write_raw_bytes(&transaction_input.to_bytes())?;
+    Ok(())
+  }
+}
+
+impl CborDeserialize for TransactionInput {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    raw_item(de)
+      .and_then(|bytes| RTransactionInput::from_bytes(bytes).into_result())
+      .and_then(|transaction_input| transaction_input.try_into())
+  }
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn cardano_transaction_input_to_bytes(
   transaction_input: TransactionInput, result: &mut CData, error: &mut CError,
@@ -96,6 +114,24 @@ impl TryFrom<RTransactionInputs> for TransactionInputs {
   }
 }
 
+impl CborSerialize for TransactionInputs {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    let transaction_inputs = unsafe { self.unowned()? };
+    se.write_array(Len::Len(transaction_inputs.len() as u64))?;
+    for transaction_input in transaction_inputs {
+      transaction_input.serialize(se)?;
+    }
+    Ok(())
+  }
+}
+
+impl CborDeserialize for TransactionInputs {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    read_array(de, TransactionInput::deserialize)
+      .map(|transaction_inputs| transaction_inputs.into())
+  }
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn cardano_transaction_inputs_free(
   transaction_inputs: &mut TransactionInputs,
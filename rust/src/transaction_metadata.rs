@@ -1,21 +1,31 @@
+use crate::address::address::Address;
 use crate::address::pointer::Slot;
 use crate::array::*;
+use crate::cbor::*;
 use crate::data::CData;
 use crate::error::CError;
 use crate::general_transaction_metadata::GeneralTransactionMetadata;
 use crate::option::COption;
 use crate::panic::*;
+use crate::protocol_param_update::Language;
 use crate::ptr::*;
-use crate::stake_credential::Ed25519KeyHash;
+use crate::stake_credential::{Ed25519KeyHash, Ed25519KeyHashes, StakeCredential};
 use crate::stake_credential::ScriptHash;
 use cardano_serialization_lib::{
+  address::{
+    BaseAddress as RBaseAddress, EnterpriseAddress as REnterpriseAddress,
+    StakeCredential as RStakeCredential,
+  },
+  crypto::ScriptHash as RScriptHash,
   metadata::AuxiliaryData as RAuxiliaryData,
   plutus::{PlutusScript as RPlutusScript, PlutusScripts as RPlutusScripts},
   NativeScript as RNativeScript, NativeScriptKind, NativeScripts as RNativeScripts,
+  RequiredSignersSet,
   ScriptAll as RScriptAll, ScriptAny as RScriptAny, ScriptHashNamespace as RScriptHashNamespace,
   ScriptNOfK as RScriptNOfK, ScriptPubkey as RScriptPubkey, TimelockExpiry as RTimelockExpiry,
   TimelockStart as RTimelockStart,
 };
+use cryptoxide::blake2b::Blake2b;
 use std::convert::{TryFrom, TryInto};
 
 #[repr(C)]
@@ -140,6 +150,88 @@ pub unsafe extern "C" fn cardano_native_script_hash(
   .response(result, error)
 }
 
+impl NativeScript {
+  // Time locks are checked against a single slot, the way the ledger checks
+  // them against both ends of the transaction validity interval.
+  pub fn evaluate(&self, signers: &[Ed25519KeyHash], slot: Slot) -> Result<bool> {
+    let evaluate_all = |native_scripts: &NativeScripts| -> Result<Vec<bool>> {
+      unsafe { native_scripts.unowned()? }
+        .iter()
+        .map(|native_script| native_script.evaluate(signers, slot))
+        .collect()
+    };
+    match self {
+      Self::ScriptPubkeyKind(script_pubkey) => Ok(signers.contains(&script_pubkey.addr_keyhash)),
+      Self::ScriptAllKind(script_all) => {
+        evaluate_all(&script_all.native_scripts).map(|results| results.iter().all(|ok| *ok))
+      }
+      Self::ScriptAnyKind(script_any) => {
+        evaluate_all(&script_any.native_scripts).map(|results| results.iter().any(|ok| *ok))
+      }
+      Self::ScriptNOfKKind(script_n_of_k) => evaluate_all(&script_n_of_k.native_scripts)
+        .map(|results| results.iter().filter(|ok| **ok).count() >= script_n_of_k.n as usize),
+      Self::TimelockStartKind(timelock_start) => Ok(slot >= timelock_start.slot),
+      Self::TimelockExpiryKind(timelock_expiry) => Ok(slot < timelock_expiry.slot),
+    }
+  }
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_native_script_to_address(
+  native_script: NativeScript, network: u8, stake: COption<StakeCredential>,
+  result: &mut Address, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    native_script
+      .try_into()
+      .map(|native_script: RNativeScript| {
+        let hash = native_script.hash(RScriptHashNamespace::NativeScript);
+        RStakeCredential::from_scripthash(&hash)
+      })
+      .and_then(|payment| {
+        let stake: Option<StakeCredential> = stake.into();
+        match stake {
+          Some(stake) => RBaseAddress::new(network, &payment, &stake.into()).to_address(),
+          None => REnterpriseAddress::new(network, &payment).to_address(),
+        }
+        .try_into()
+      })
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_native_script_evaluate(
+  native_script: NativeScript, signers: Ed25519KeyHashes, slot: Slot, result: &mut bool,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    signers
+      .unowned()
+      .and_then(|signers| native_script.evaluate(signers, slot))
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_native_script_required_signers(
+  native_script: NativeScript, result: &mut Ed25519KeyHashes, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    native_script.try_into().and_then(|native_script: RNativeScript| {
+      RequiredSignersSet::from(&native_script)
+        .into_iter()
+        .map(|key_hash| key_hash.try_into())
+        .collect::<Result<Vec<Ed25519KeyHash>>>()
+        .map(|key_hashes| key_hashes.into())
+    })
+  })
+  .response(result, error)
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn cardano_native_script_clone(
   native_script: NativeScript, result: &mut NativeScript, error: &mut CError,
@@ -392,36 +484,89 @@ impl From<RTimelockExpiry> for TimelockExpiry {
 
 #[repr(C)]
 #[derive(Copy)]
-pub struct PlutusScript(CData);
+pub struct PlutusScript {
+  bytes: CData,
+  language: Language,
+}
 
 impl Free for PlutusScript {
   unsafe fn free(&mut self) {
-    self.0.free()
+    self.bytes.free()
   }
 }
 
 impl Clone for PlutusScript {
   fn clone(&self) -> Self {
-    let bytes = unsafe { self.0.unowned().expect("Bad bytes pointer").into() };
-    Self(bytes)
+    let bytes = unsafe { self.bytes.unowned().expect("Bad bytes pointer").into() };
+    Self {
+      bytes,
+      language: self.language,
+    }
   }
 }
 
+impl PlutusScript {
+  pub fn new(bytes: Vec<u8>, language: Language) -> Self {
+    Self {
+      bytes: bytes.into(),
+      language,
+    }
+  }
+
+  pub fn bytes(&self) -> Result<Vec<u8>> {
+    unsafe { self.bytes.unowned().map(|bytes| bytes.to_vec()) }
+  }
+
+  pub fn language(&self) -> Language {
+    self.language
+  }
+}
+
+// cardano-serialization-lib knows only Plutus V1 scripts
 impl TryFrom<PlutusScript> for RPlutusScript {
   type Error = CError;
 
   fn try_from(plutus_script: PlutusScript) -> Result<Self> {
-    let bytes = unsafe { plutus_script.0.unowned()? };
-    Ok(Self::new(bytes.to_vec()))
+    match plutus_script.language {
+      Language::PlutusV1 => plutus_script.bytes().map(Self::new),
+      _ => Err("Only Plutus V1 scripts are supported here".into()),
+    }
   }
 }
 
 impl From<RPlutusScript> for PlutusScript {
   fn from(plutus_script: RPlutusScript) -> Self {
-    Self(plutus_script.bytes().into())
+    Self::new(plutus_script.bytes(), Language::PlutusV1)
   }
 }
 
+// Script bytes prefixed with the namespace tag of the language
+pub fn plutus_script_hash(plutus_script: &[u8], language: Language) -> RScriptHash {
+  let mut bytes = vec![match language {
+    Language::PlutusV1 => 1,
+    Language::PlutusV2 => 2,
+    Language::PlutusV3 => 3,
+  }];
+  bytes.extend(plutus_script);
+  let mut hash = [0; 28];
+  Blake2b::blake2b(&mut hash, &bytes, &[]);
+  hash.into()
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_plutus_script_hash(
+  plutus_script: PlutusScript, result: &mut ScriptHash, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    plutus_script
+      .bytes()
+      .map(|bytes| plutus_script_hash(&bytes, plutus_script.language))
+      .and_then(|hash| hash.try_into())
+  })
+  .response(result, error)
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn cardano_plutus_script_clone(
   plutus_script: PlutusScript, result: &mut PlutusScript, error: &mut CError,
@@ -464,6 +609,30 @@ impl From<RPlutusScripts> for PlutusScripts {
   }
 }
 
+// Witness set entries of the scripts, one list per language
+pub fn plutus_script_entries(scripts: &[(Language, Vec<u8>)]) -> Result<Vec<(u64, Vec<u8>)>> {
+  let mut entries = Vec::new();
+  for language in [Language::PlutusV1, Language::PlutusV2, Language::PlutusV3] {
+    let items = scripts
+      .iter()
+      .filter(|(script_language, _)| *script_language == language)
+      .map(|(_, bytes)| bytes.to_cbor())
+      .collect::<Result<Vec<Vec<u8>>>>()?;
+    if !items.is_empty() {
+      entries.push((plutus_scripts_key(language), encode_array(items)?));
+    }
+  }
+  Ok(entries)
+}
+
+pub fn plutus_scripts_key(language: Language) -> u64 {
+  match language {
+    Language::PlutusV1 => 3,
+    Language::PlutusV2 => 6,
+    Language::PlutusV3 => 7,
+  }
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn cardano_plutus_scripts_free(plutus_scripts: &mut PlutusScripts) {
   plutus_scripts.free()
@@ -573,3 +742,85 @@ pub unsafe extern "C" fn cardano_auxiliary_data_clone(
 pub unsafe extern "C" fn cardano_auxiliary_data_free(auxiliary_data: &mut AuxiliaryData) {
   auxiliary_data.free()
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_utils::hex;
+  use cardano_serialization_lib::address::Address as RAddress;
+  use cardano_serialization_lib::crypto::Ed25519KeyHash as REd25519KeyHash;
+
+  #[test]
+  fn plutus_script_hash_is_tagged_by_language() {
+    // Always succeeding Plutus V1 script
+    let script = hex("4d01000033222220051200120011");
+    let hash = plutus_script_hash(&script, Language::PlutusV1);
+    assert_eq!(hash.to_bytes(), hex("67f33146617a5e61936081db3b2117cbf59bd2123748f58ac9678656"));
+    let v2 = plutus_script_hash(&script, Language::PlutusV2);
+    let v3 = plutus_script_hash(&script, Language::PlutusV3);
+    assert!(v2.to_bytes() != hash.to_bytes() && v3.to_bytes() != v2.to_bytes());
+  }
+
+  #[test]
+  fn only_v1_scripts_convert_to_the_library_type() {
+    let script = PlutusScript::new(vec![1, 2], Language::PlutusV1);
+    assert_eq!(RPlutusScript::try_from(script).unwrap().bytes(), vec![1, 2]);
+    let script = PlutusScript::new(vec![1, 2], Language::PlutusV2);
+    assert!(RPlutusScript::try_from(script).is_err());
+  }
+
+  fn key_hash(byte: u8) -> Ed25519KeyHash {
+    Ed25519KeyHash::try_from(REd25519KeyHash::from_bytes(vec![byte; 28]).unwrap()).unwrap()
+  }
+
+  // Two of the three keys, valid from slot 100
+  fn multisig() -> NativeScript {
+    let mut keys = RNativeScripts::new();
+    for byte in 1..=3 {
+      let hash = REd25519KeyHash::from_bytes(vec![byte; 28]).unwrap();
+      keys.add(&RNativeScript::new_script_pubkey(&RScriptPubkey::new(&hash)));
+    }
+    let mut scripts = RNativeScripts::new();
+    scripts.add(&RNativeScript::new_script_n_of_k(&RScriptNOfK::new(2, &keys)));
+    scripts.add(&RNativeScript::new_timelock_start(&RTimelockStart::new(100)));
+    RNativeScript::new_script_all(&RScriptAll::new(&scripts)).try_into().unwrap()
+  }
+
+  #[test]
+  fn multisig_needs_enough_signers_after_its_start() {
+    let script = multisig();
+    assert!(script.evaluate(&[key_hash(1), key_hash(3)], 100).unwrap());
+    assert!(!script.evaluate(&[key_hash(1), key_hash(3)], 99).unwrap());
+    assert!(!script.evaluate(&[key_hash(2), key_hash(4)], 100).unwrap());
+  }
+
+  #[test]
+  fn multisig_address_and_signers() {
+    let script = multisig();
+    let hash = RNativeScript::try_from(script)
+      .unwrap()
+      .hash(RScriptHashNamespace::NativeScript);
+    let mut address = std::mem::MaybeUninit::uninit();
+    let mut signers = std::mem::MaybeUninit::uninit();
+    let mut error = CError::NullPtr;
+    unsafe {
+      assert!(cardano_native_script_to_address(
+        script,
+        0,
+        COption::None,
+        &mut *address.as_mut_ptr(),
+        &mut error,
+      ));
+      let address = RAddress::try_from(address.assume_init()).unwrap();
+      let payment = REnterpriseAddress::from_address(&address).unwrap().payment_cred();
+      assert_eq!(payment.to_scripthash().unwrap().to_bytes(), hash.to_bytes());
+      assert!(cardano_native_script_required_signers(
+        script,
+        &mut *signers.as_mut_ptr(),
+        &mut error,
+      ));
+      let signers: Ed25519KeyHashes = signers.assume_init();
+      assert_eq!(signers.unowned().unwrap().len(), 3);
+    }
+  }
+}
@@ -1,15 +1,25 @@
 use crate::address::address::Address;
 use crate::array::CArray;
+use crate::cbor::*;
 use crate::data::CData;
 use crate::error::CError;
+use crate::linear_fee::Coin;
 use crate::option::COption;
 use crate::panic::*;
+use crate::protocol_param_update::Language;
 use crate::ptr::*;
+use crate::transaction_metadata::{NativeScript, PlutusScript};
+use crate::transaction_witness_set::PlutusData;
 use crate::value::Value;
 use cardano_serialization_lib::{
-  crypto::DataHash as RDataHash, TransactionOutput as RTransactionOutput,
+  address::Address as RAddress,
+  crypto::DataHash as RDataHash,
+  plutus::PlutusData as RPlutusData,
+  utils::{from_bignum, min_ada_required, to_bignum, Value as RValue},
+  NativeScript as RNativeScript, TransactionOutput as RTransactionOutput,
   TransactionOutputs as RTransactionOutputs,
 };
+use cbor_event::{Len, Type};
 use std::convert::{TryFrom, TryInto};
 
 #[repr(C)]
@@ -52,21 +62,237 @@ pub unsafe extern "C" fn cardano_data_hash_from_bytes(
   .response(result, error)
 }
 
+impl CborSerialize for DataHash {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    se.write_bytes(self.0)?;
+    Ok(())
+  }
+}
+
+impl CborDeserialize for DataHash {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    de.bytes()?
+      .try_into()
+      .map(Self)
+      .map_err(|_| CError::DataLengthMismatch)
+  }
+}
+
+// Item wrapped in bytes under tag 24, as inline datums and script references are
+fn write_embedded(se: &mut CborSerializer, bytes: Vec<u8>) -> Result<()> {
+  se.write_tag(24)?;
+  se.write_bytes(bytes)?;
+  Ok(())
+}
+
+fn read_embedded(de: &mut CborDeserializer) -> Result<Vec<u8>> {
+  match de.tag()? {
+    24 => Ok(de.bytes()?),
+    tag => Err(format!("Wrong embedded CBOR tag {}", tag).into()),
+  }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub enum DatumOption {
+  DataHashKind(DataHash),
+  DataKind(PlutusData),
+}
+
+impl Free for DatumOption {
+  unsafe fn free(&mut self) {
+    if let DatumOption::DataKind(data) = self {
+      data.free();
+    }
+  }
+}
+
+impl CborSerialize for DatumOption {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    se.write_array(Len::Len(2))?;
+    match self {
+      DatumOption::DataHashKind(hash) => {
+        se.write_unsigned_integer(0)?;
+        hash.serialize(se)
+      }
+      DatumOption::DataKind(data) => {
+        let data: RPlutusData = (*data).try_into()?;
+        se.write_unsigned_integer(1)?;
+        write_embedded(se, data.to_bytes())
+      }
+    }
+  }
+}
+
+impl CborDeserialize for DatumOption {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    let kind = peek_kind(de)?;
+    let len = read_kind_array(de, kind, 1, "DatumOption")?;
+    let datum = match kind {
+      0 => DataHash::deserialize(de).map(Self::DataHashKind),
+      1 => read_embedded(de)
+        .and_then(|bytes| RPlutusData::from_bytes(bytes).into_result())
+        .and_then(|data| data.try_into())
+        .map(Self::DataKind),
+      _ => Err(format!("Wrong DatumOption kind {}", kind).into()),
+    }?;
+    read_array_end(de, len)?;
+    Ok(datum)
+  }
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc, clippy::clone_on_copy)]
+pub unsafe extern "C" fn cardano_datum_option_clone(
+  datum_option: DatumOption, result: &mut DatumOption, error: &mut CError,
+) -> bool {
+  handle_exception(|| datum_option.clone()).response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_datum_option_free(datum_option: &mut DatumOption) {
+  datum_option.free()
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub enum ScriptRef {
+  NativeScriptRefKind(NativeScript),
+  PlutusScriptRefKind(PlutusScript),
+}
+
+impl Free for ScriptRef {
+  unsafe fn free(&mut self) {
+    match self {
+      ScriptRef::NativeScriptRefKind(native_script) => native_script.free(),
+      ScriptRef::PlutusScriptRefKind(plutus_script) => plutus_script.free(),
+    }
+  }
+}
+
+impl ScriptRef {
+  // Size counted by the Conway reference script fee
+  pub fn size(&self) -> Result<usize> {
+    match self {
+      ScriptRef::NativeScriptRefKind(native_script) => {
+        RNativeScript::try_from(*native_script).map(|native_script| native_script.to_bytes().len())
+      }
+      ScriptRef::PlutusScriptRefKind(plutus_script) => {
+        plutus_script.bytes().map(|bytes| bytes.len())
+      }
+    }
+  }
+
+  pub fn plutus_script(&self) -> Option<PlutusScript> {
+    match self {
+      ScriptRef::PlutusScriptRefKind(plutus_script) => Some(*plutus_script),
+      _ => None,
+    }
+  }
+}
+
+impl CborSerialize for ScriptRef {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    let mut script = CborSerializer::new_vec();
+    script.write_array(Len::Len(2))?;
+    match self {
+      ScriptRef::NativeScriptRefKind(native_script) => {
+        let native_script: RNativeScript = (*native_script).try_into()?;
+        script.write_unsigned_integer(0)?;
+        script.write_raw_bytes(&native_script.to_bytes())?;
+      }
+      ScriptRef::PlutusScriptRefKind(plutus_script) => {
+        let kind = match plutus_script.language() {
+          Language::PlutusV1 => 1,
+          Language::PlutusV2 => 2,
+          Language::PlutusV3 => 3,
+        };
+        script.write_unsigned_integer(kind)?;
+        script.write_bytes(plutus_script.bytes()?)?;
+      }
+    }
+    write_embedded(se, script.finalize())
+  }
+}
+
+impl CborDeserialize for ScriptRef {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    let bytes = read_embedded(de)?;
+    let mut script = CborDeserializer::from(std::io::Cursor::new(bytes.as_slice()));
+    let kind = peek_kind(&script)?;
+    let len = read_kind_array(&mut script, kind, 1, "ScriptRef")?;
+    let script_ref = match kind {
+      0 => raw_item(&mut script)
+        .and_then(|bytes| RNativeScript::from_bytes(bytes).into_result())
+        .and_then(|native_script| native_script.try_into())
+        .map(Self::NativeScriptRefKind),
+      1..=3 => Vec::<u8>::deserialize(&mut script).map(|bytes| {
+        let language = match kind {
+          1 => Language::PlutusV1,
+          2 => Language::PlutusV2,
+          _ => Language::PlutusV3,
+        };
+        Self::PlutusScriptRefKind(PlutusScript::new(bytes, language))
+      }),
+      _ => Err(format!("Wrong ScriptRef kind {}", kind).into()),
+    }?;
+    read_array_end(&mut script, len)?;
+    Ok(script_ref)
+  }
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc, clippy::clone_on_copy)]
+pub unsafe extern "C" fn cardano_script_ref_clone(
+  script_ref: ScriptRef, result: &mut ScriptRef, error: &mut CError,
+) -> bool {
+  handle_exception(|| script_ref.clone()).response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_script_ref_free(script_ref: &mut ScriptRef) {
+  script_ref.free()
+}
+
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub struct TransactionOutput {
   address: Address,
   amount: Value,
-  data_hash: COption<DataHash>,
+  datum: COption<DatumOption>,
+  script_ref: COption<ScriptRef>,
 }
 
 impl Free for TransactionOutput {
   unsafe fn free(&mut self) {
     self.address.free();
     self.amount.free();
+    self.datum.free();
+    self.script_ref.free();
+  }
+}
+
+impl TransactionOutput {
+  // Inline datum and script reference need the post-Alonzo map format
+  pub fn is_legacy(&self) -> bool {
+    let datum: Option<DatumOption> = self.datum.into();
+    let script_ref: Option<ScriptRef> = self.script_ref.into();
+    script_ref.is_none() && !matches!(datum, Some(DatumOption::DataKind(_)))
+  }
+
+  pub fn address(&self) -> Address {
+    self.address
+  }
+
+  pub fn script_ref(&self) -> Option<ScriptRef> {
+    self.script_ref.into()
   }
 }
 
+// cardano-serialization-lib knows only legacy outputs, so it gets the output
+// without the inline datum and script reference. Encoding is done here.
 impl TryFrom<TransactionOutput> for RTransactionOutput {
   type Error = CError;
 
@@ -77,8 +303,10 @@ impl TryFrom<TransactionOutput> for RTransactionOutput {
       .zip(transaction_output.amount.try_into())
       .map(|(address, amount)| {
         let mut to = Self::new(&address, &amount);
-        let data_hash: Option<DataHash> = transaction_output.data_hash.into();
-        data_hash.map(|data_hash| to.set_data_hash(&data_hash.into()));
+        let datum: Option<DatumOption> = transaction_output.datum.into();
+        if let Some(DatumOption::DataHashKind(data_hash)) = datum {
+          to.set_data_hash(&data_hash.into());
+        }
         to
       })
   }
@@ -95,27 +323,148 @@ impl TryFrom<RTransactionOutput> for TransactionOutput {
       .map(|(address, amount)| Self {
         address,
         amount,
-        data_hash: transaction_output
+        datum: transaction_output
           .data_hash()
-          .map(|data_hash| data_hash.into())
+          .map(|data_hash| DatumOption::DataHashKind(data_hash.into()))
           .into(),
+        script_ref: COption::None,
       })
   }
 }
 
+impl CborSerialize for TransactionOutput {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    if self.is_legacy() {
+      let transaction_output: RTransactionOutput = (*self).try_into()?;
+      se.write_raw_bytes(&transaction_output.to_bytes())?;
+      return Ok(());
+    }
+    let address: RAddress = self.address.try_into()?;
+    let amount: RValue = self.amount.try_into()?;
+    let datum: Option<DatumOption> = self.datum.into();
+    let script_ref: Option<ScriptRef> = self.script_ref.into();
+    let len = 2 + datum.is_some() as u64 + script_ref.is_some() as u64;
+    se.write_map(Len::Len(len))?;
+    se.write_unsigned_integer(0)?;
+    se.write_bytes(address.to_bytes())?;
+    se.write_unsigned_integer(1)?;
+    se.write_raw_bytes(&amount.to_bytes())?;
+    if let Some(datum) = datum {
+      se.write_unsigned_integer(2)?;
+      datum.serialize(se)?;
+    }
+    if let Some(script_ref) = script_ref {
+      se.write_unsigned_integer(3)?;
+      script_ref.serialize(se)?;
+    }
+    Ok(())
+  }
+}
+
+impl CborDeserialize for TransactionOutput {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    if de.cbor_type()? == Type::Array {
+      return raw_item(de)
+        .and_then(|bytes| RTransactionOutput::from_bytes(bytes).into_result())
+        .and_then(|transaction_output| transaction_output.try_into());
+    }
+    let mut entries = read_map(de, |de| Ok((de.unsigned_integer()?, raw_item(de)?)))?;
+    let address = take_entry(&mut entries, 0)
+      .ok_or("TransactionOutput address is missing")
+      .map(|address| Vec::<u8>::from_cbor(&address))??;
+    let amount = take_entry(&mut entries, 1).ok_or("TransactionOutput amount is missing")?;
+    let datum = take_entry(&mut entries, 2)
+      .map(|datum| DatumOption::from_cbor(&datum))
+      .transpose()?;
+    let script_ref = take_entry(&mut entries, 3)
+      .map(|script_ref| ScriptRef::from_cbor(&script_ref))
+      .transpose()?;
+    if let Some((key, _)) = entries.first() {
+      return Err(format!("Unknown TransactionOutput key {}", key).into());
+    }
+    RAddress::from_bytes(address)
+      .into_result()
+      .and_then(|address| address.try_into())
+      .zip(
+        RValue::from_bytes(amount)
+          .into_result()
+          .and_then(|amount| amount.try_into()),
+      )
+      .map(|(address, amount)| Self {
+        address,
+        amount,
+        datum: datum.into(),
+        script_ref: script_ref.into(),
+      })
+  }
+}
+
+// Babbage min ADA: the size of the output with its entry overhead, times coins per byte.
+// The coin is raised to the min ADA until its own encoding is paid for too.
+pub fn output_min_ada_required_per_byte(
+  transaction_output: &TransactionOutput, coins_per_utxo_byte: Coin,
+) -> Result<Coin> {
+  let mut output = *transaction_output;
+  loop {
+    let size = output.to_cbor()?.len() as Coin;
+    let min_ada = (160 + size)
+      .checked_mul(coins_per_utxo_byte)
+      .ok_or("Min ADA is too big")?;
+    if output.amount.coin() >= min_ada {
+      return Ok(min_ada);
+    }
+    output.amount = output.amount.with_coin(min_ada);
+  }
+}
+
+// Legacy outputs keep the Alonzo formula. Post-Alonzo ones are sized as Babbage does,
+// with coins per byte taken as coins per word / 8 like the Babbage hard fork did.
+pub fn output_min_ada_required(
+  transaction_output: &TransactionOutput, coins_per_utxo_word: Coin,
+) -> Result<Coin> {
+  if transaction_output.is_legacy() {
+    let transaction_output: RTransactionOutput = (*transaction_output).try_into()?;
+    return min_ada_required(
+      &transaction_output.amount(),
+      transaction_output.data_hash().is_some(),
+      &to_bignum(coins_per_utxo_word),
+    )
+    .map(|coin| from_bignum(&coin))
+    .into_result();
+  }
+  output_min_ada_required_per_byte(transaction_output, coins_per_utxo_word / 8)
+}
+
 #[no_mangle]
-pub unsafe extern "C" fn cardano_transaction_output_to_bytes(
-  transaction_output: TransactionOutput, result: &mut CData, error: &mut CError,
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_output_min_ada_required(
+  transaction_output: TransactionOutput, coins_per_utxo_word: Coin, result: &mut Coin,
+  error: &mut CError,
+) -> bool {
+  handle_exception_result(|| output_min_ada_required(&transaction_output, coins_per_utxo_word))
+    .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_output_min_ada_required_per_byte(
+  transaction_output: TransactionOutput, coins_per_utxo_byte: Coin, result: &mut Coin,
+  error: &mut CError,
 ) -> bool {
   handle_exception_result(|| {
-    transaction_output
-      .try_into()
-      .map(|transaction_output: RTransactionOutput| transaction_output.to_bytes())
-      .map(|bytes| bytes.into())
+    output_min_ada_required_per_byte(&transaction_output, coins_per_utxo_byte)
   })
   .response(result, error)
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn cardano_transaction_output_to_bytes(
+  transaction_output: TransactionOutput, result: &mut CData, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| transaction_output.to_cbor().map(|bytes| bytes.into()))
+    .response(result, error)
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn cardano_transaction_output_from_bytes(
   data: CData, result: &mut TransactionOutput, error: &mut CError,
@@ -123,8 +472,7 @@ pub unsafe extern "C" fn cardano_transaction_output_from_bytes(
   handle_exception_result(|| {
     data
       .unowned()
-      .and_then(|bytes| RTransactionOutput::from_bytes(bytes.to_vec()).into_result())
-      .and_then(|transaction_output| transaction_output.try_into())
+      .and_then(TransactionOutput::from_cbor)
   })
   .response(result, error)
 }
@@ -171,9 +519,68 @@ impl TryFrom<RTransactionOutputs> for TransactionOutputs {
   }
 }
 
+impl CborSerialize for TransactionOutputs {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    let transaction_outputs = unsafe { self.unowned()? };
+    se.write_array(Len::Len(transaction_outputs.len() as u64))?;
+    for transaction_output in transaction_outputs {
+      transaction_output.serialize(se)?;
+    }
+    Ok(())
+  }
+}
+
+impl CborDeserialize for TransactionOutputs {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    read_array(de, TransactionOutput::deserialize)
+      .map(|transaction_outputs| transaction_outputs.into())
+  }
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn cardano_transaction_outputs_free(
   transaction_outputs: &mut TransactionOutputs,
 ) {
   transaction_outputs.free();
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_utils::hex;
+
+  // Enterprise testnet address, inline datum 42 and a Plutus V2 reference script
+  const BABBAGE_OUTPUT: &str = concat!(
+    "a4",
+    "00581d6000000000000000000000000000000000000000000000000000000000",
+    "011a000f4240",
+    "028201d81842182a",
+    "03d81846820243010203",
+  );
+
+  #[test]
+  fn babbage_output_round_trips() {
+    let bytes = hex(BABBAGE_OUTPUT);
+    let output = TransactionOutput::from_cbor(&bytes).unwrap();
+    assert!(!output.is_legacy());
+    let datum: Option<DatumOption> = output.datum.into();
+    assert!(matches!(datum, Some(DatumOption::DataKind(_))));
+    let script = output.script_ref().and_then(|script_ref| script_ref.plutus_script()).unwrap();
+    assert!(script.language() == Language::PlutusV2);
+    assert_eq!(script.bytes().unwrap(), vec![1, 2, 3]);
+    assert_eq!(output.to_cbor().unwrap(), bytes);
+  }
+
+  #[test]
+  fn data_hash_output_stays_legacy() {
+    let bytes = hex(concat!(
+      "83",
+      "581d6000000000000000000000000000000000000000000000000000000000",
+      "1a000f4240",
+      "58200707070707070707070707070707070707070707070707070707070707070707",
+    ));
+    let output = TransactionOutput::from_cbor(&bytes).unwrap();
+    assert!(output.is_legacy());
+    assert_eq!(output.to_cbor().unwrap(), bytes);
+  }
+}
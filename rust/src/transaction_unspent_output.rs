@@ -17,8 +17,8 @@ use crate::{
 #[repr(C)]
 #[derive(Copy, Clone)]
 pub struct TransactionUnspentOutput {
-  input: TransactionInput,
-  output: TransactionOutput,
+  pub input: TransactionInput,
+  pub output: TransactionOutput,
 }
 
 impl Free for TransactionUnspentOutput {
@@ -1,4 +1,5 @@
 use crate::array::CArray;
+use crate::cbor::*;
 use crate::constr_plutus_data::ConstrPlutusData;
 use crate::data::CData;
 use crate::error::CError;
@@ -7,12 +8,17 @@ use crate::option::COption;
 use crate::panic::*;
 use crate::plutus_list::PlutusList;
 use crate::plutus_map::PlutusMap;
-use crate::protocol_param_update::ExUnits;
+use crate::protocol_param_update::{ExUnits, Language};
 use crate::ptr::*;
 use crate::transaction_builder::BigNum;
-use crate::transaction_metadata::NativeScripts;
-use crate::vkeywitness::Vkeywitnesses;
-use crate::{bootstrap_witness::BootstrapWitnesses, transaction_metadata::PlutusScripts};
+use crate::transaction_metadata::{
+  plutus_script_entries, plutus_scripts_key, NativeScripts, PlutusScript,
+};
+use crate::vkeywitness::{Vkeywitness, Vkeywitnesses};
+use crate::{
+  bootstrap_witness::{BootstrapWitness, BootstrapWitnesses},
+  transaction_metadata::PlutusScripts,
+};
 use cardano_serialization_lib::{
   plutus::{
     PlutusData as RPlutusData, PlutusDataKind, Redeemer as RRedeemer, RedeemerTag as RRedeemerTag,
@@ -22,25 +28,28 @@ use cardano_serialization_lib::{
   TransactionWitnessSet as RTransactionWitnessSet,
 };
 use num_bigint::BigInt;
+use std::collections::{BTreeMap, BTreeSet};
 use std::convert::{TryFrom, TryInto};
 
-// for transmute
-struct TBigInt(BigInt);
-
 impl TryFrom<CBigInt> for RBigInt {
   type Error = CError;
 
   fn try_from(big_int: CBigInt) -> Result<Self> {
     big_int
       .try_into()
-      .map(|big_int| unsafe { std::mem::transmute(TBigInt(big_int)) })
+      .and_then(|big_int: BigInt| RBigInt::from_str(&big_int.to_string()).into_result())
   }
 }
 
-impl From<RBigInt> for CBigInt {
-  fn from(big_int: RBigInt) -> Self {
-    let big_int: TBigInt = unsafe { std::mem::transmute(big_int) };
-    big_int.0.into()
+impl TryFrom<RBigInt> for CBigInt {
+  type Error = CError;
+
+  fn try_from(big_int: RBigInt) -> Result<Self> {
+    big_int
+      .to_str()
+      .parse::<BigInt>()
+      .map(|big_int| big_int.into())
+      .map_err(|_| "BigInt is not a valid integer".into())
   }
 }
 
@@ -125,7 +134,8 @@ impl TryFrom<RPlutusData> for PlutusData {
       PlutusDataKind::Integer => plutus_data
         .as_integer()
         .ok_or("Empty Integer".into())
-        .map(|integer| Self::IntegerKind(integer.into())),
+        .and_then(|integer| integer.try_into())
+        .map(Self::IntegerKind),
       PlutusDataKind::Bytes => plutus_data
         .as_bytes()
         .ok_or("Empty Bytes".into())
@@ -366,6 +376,191 @@ impl TryFrom<TransactionWitnessSet> for RTransactionWitnessSet {
   }
 }
 
+// Witness set keys holding sets, which Conway allows to be tagged
+const SET_KEYS: [u64; 4] = [0, 1, 2, 4];
+
+const VKEYS_KEY: u64 = 0;
+const BOOTSTRAPS_KEY: u64 = 2;
+const REDEEMERS_KEY: u64 = 5;
+
+// What tells two items of a witness set entry apart: the key of vkey and
+// bootstrap witnesses, the pointer of redeemers and the bytes of the others
+fn witness_identity(key: u64, item: &[u8]) -> Result<Vec<u8>> {
+  let fields = |indices: &[usize]| -> Result<Vec<u8>> {
+    let items = array_items(item)?;
+    let mut identity = Vec::new();
+    for index in indices {
+      identity.extend(items.get(*index).ok_or("Malformed witness")?);
+    }
+    Ok(identity)
+  };
+  match key {
+    VKEYS_KEY => fields(&[0]),
+    BOOTSTRAPS_KEY => fields(&[0, 3]),
+    REDEEMERS_KEY => fields(&[0, 1]),
+    _ => Ok(item.to_vec()),
+  }
+}
+
+// Items of both entries, each identity once
+pub(crate) fn merge_witness_items(
+  key: u64, items: Vec<Vec<u8>>, other: Vec<Vec<u8>>,
+) -> Result<Vec<u8>> {
+  let mut identities = BTreeMap::new();
+  let mut merged = Vec::new();
+  for item in items.into_iter().chain(other) {
+    let identity = witness_identity(key, &item)?;
+    match identities.get(&identity) {
+      Some(known) if key == REDEEMERS_KEY && *known != item => {
+        return Err("Conflicting redeemers for the same pointer".into())
+      }
+      Some(_) => {}
+      None => {
+        identities.insert(identity, item.clone());
+        merged.push(item);
+      }
+    }
+  }
+  encode_array(merged)
+}
+
+impl TransactionWitnessSet {
+  pub fn vkeywitnesses(&self) -> Result<Vec<Vkeywitness>> {
+    let vkeys: Option<Vkeywitnesses> = self.vkeys.into();
+    vkeys
+      .map(|vkeys| unsafe { vkeys.unowned() }.map(|vkeys| vkeys.to_vec()))
+      .unwrap_or_else(|| Ok(Vec::new()))
+  }
+
+  // Union of both sets. Redeemers of the same pointer have to be the same.
+  pub fn merge(&self, other: &Self) -> Result<Self> {
+    let mut entries = map_entries(&self.to_cbor()?)?;
+    let mut merged = Vec::new();
+    for (key, items) in map_entries(&other.to_cbor()?)? {
+      let existing = match take_entry(&mut entries, key) {
+        Some(existing) => array_items(&existing)?,
+        None => Vec::new(),
+      };
+      merged.push((key, merge_witness_items(key, existing, array_items(&items)?)?));
+    }
+    for (key, items) in entries {
+      merged.push((key, merge_witness_items(key, array_items(&items)?, Vec::new())?));
+    }
+    Self::from_cbor(&encode_map(merged)?)
+  }
+
+  // Adds the witnesses of keys the set has no witness for yet,
+  // witnesses the set already repeats are dropped as well
+  #[allow(clippy::clone_on_copy)]
+  pub fn add_witnesses(
+    &mut self, vkeywitnesses: Vec<Vkeywitness>, bootstraps: Vec<BootstrapWitness>,
+  ) -> Result<()> {
+    let mut vkey_hashes = BTreeSet::new();
+    let mut merged_vkeys = Vec::new();
+    for vkeywitness in self.vkeywitnesses()?.into_iter().chain(vkeywitnesses) {
+      if vkey_hashes.insert(vkeywitness.key_hash()?) {
+        merged_vkeys.push(vkeywitness);
+      }
+    }
+    let bootstraps_keys = bootstraps
+      .iter()
+      .map(|bootstrap| bootstrap.key_hash().zip(bootstrap.attributes()))
+      .collect::<Result<Vec<_>>>()?;
+    let mut bootstrap_keys = BTreeSet::new();
+    let mut merged_bootstraps = Vec::new();
+    let existing: Option<BootstrapWitnesses> = self.bootstraps.into();
+    if let Some(existing) = existing {
+      for bootstrap in unsafe { existing.unowned()? } {
+        if bootstrap_keys.insert(bootstrap.key_hash().zip(bootstrap.attributes())?) {
+          merged_bootstraps.push(bootstrap.clone());
+        }
+      }
+    }
+    for (mut bootstrap, key) in bootstraps.into_iter().zip(bootstraps_keys) {
+      if bootstrap_keys.insert(key) {
+        merged_bootstraps.push(bootstrap);
+      } else {
+        unsafe { bootstrap.free() };
+      }
+    }
+    unsafe {
+      self.vkeys.free();
+      self.bootstraps.free();
+    }
+    self.vkeys = match merged_vkeys.is_empty() {
+      true => COption::None,
+      false => COption::Some(merged_vkeys.into()),
+    };
+    self.bootstraps = match merged_bootstraps.is_empty() {
+      true => COption::None,
+      false => COption::Some(merged_bootstraps.into()),
+    };
+    Ok(())
+  }
+}
+
+impl CborSerialize for TransactionWitnessSet {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    // cardano-serialization-lib encodes everything except the Plutus scripts,
+    // which are keyed by their language
+    let mut legacy = *self;
+    legacy.plutus_scripts = COption::None;
+    let legacy: RTransactionWitnessSet = legacy.try_into()?;
+    let mut entries = map_entries(&legacy.to_bytes())?;
+    let plutus_scripts: Option<PlutusScripts> = self.plutus_scripts.into();
+    if let Some(plutus_scripts) = plutus_scripts {
+      let scripts = unsafe { plutus_scripts.unowned()? }
+        .iter()
+        .map(|script| script.bytes().map(|bytes| (script.language(), bytes)))
+        .collect::<Result<Vec<(Language, Vec<u8>)>>>()?;
+      entries.extend(plutus_script_entries(&scripts)?);
+    }
+    se.write_raw_bytes(&encode_map(entries)?)?;
+    Ok(())
+  }
+}
+
+impl CborDeserialize for TransactionWitnessSet {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    let mut entries = map_entries(&raw_item(de)?)?;
+    let mut plutus_scripts = Vec::new();
+    for language in [Language::PlutusV1, Language::PlutusV2, Language::PlutusV3] {
+      if let Some(scripts) = take_entry(&mut entries, plutus_scripts_key(language)) {
+        for script in array_items(&scripts)? {
+          plutus_scripts.push(PlutusScript::new(Vec::<u8>::from_cbor(&script)?, language));
+        }
+      }
+    }
+    let entries = entries
+      .into_iter()
+      .map(|(key, value)| match SET_KEYS.contains(&key) {
+        true => (key, strip_set_tag(value)),
+        false => (key, value),
+      })
+      .collect();
+    let plutus_scripts: Option<PlutusScripts> = match plutus_scripts.is_empty() {
+      true => None,
+      false => Some(plutus_scripts.into()),
+    };
+    RTransactionWitnessSet::from_bytes(encode_map(entries)?)
+      .into_result()
+      .and_then(|witness_set| witness_set.try_into())
+      .map(|witness_set: Self| Self {
+        plutus_scripts: plutus_scripts.into(),
+        ..witness_set
+      })
+  }
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_transaction_witness_set_merge(
+  transaction_witness_set: TransactionWitnessSet, other: TransactionWitnessSet,
+  result: &mut TransactionWitnessSet, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| transaction_witness_set.merge(&other)).response(result, error)
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn cardano_transaction_witness_set_clone(
   transaction_witness_set: TransactionWitnessSet, result: &mut TransactionWitnessSet,
@@ -380,3 +575,69 @@ pub unsafe extern "C" fn cardano_transaction_witness_set_free(
 ) {
   transaction_witness_set.free();
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_utils::hex;
+
+  #[test]
+  fn big_int_round_trips() {
+    for text in ["0", "-1", "340282366920938463463374607431768211457", "-18446744073709551617"] {
+      let big_int: CBigInt = RBigInt::from_str(text).unwrap().try_into().unwrap();
+      let big_int: RBigInt = big_int.try_into().unwrap();
+      assert_eq!(big_int.to_str(), text);
+    }
+  }
+
+  #[test]
+  fn plutus_scripts_are_keyed_by_language() {
+    // V1, V2 and V3 scripts under keys 3, 6 and 7
+    let bytes = hex("a3038142010206814203040781420506");
+    let mut witness_set = TransactionWitnessSet::from_cbor(&bytes).unwrap();
+    let scripts: Option<PlutusScripts> = witness_set.plutus_scripts.into();
+    let languages: Vec<Language> = unsafe { scripts.unwrap().unowned().unwrap() }
+      .iter()
+      .map(|script| script.language())
+      .collect();
+    assert!(languages == vec![Language::PlutusV1, Language::PlutusV2, Language::PlutusV3]);
+    assert_eq!(witness_set.to_cbor().unwrap(), bytes);
+    unsafe { witness_set.free() };
+  }
+
+  // Vkey witness of the key filled with the byte and a zero signature
+  fn vkey_witness(byte: u8) -> Vec<u8> {
+    [hex("825820"), vec![byte; 32], hex("5840"), vec![0; 64]].concat()
+  }
+
+  fn witness_set(entries: Vec<(u64, Vec<Vec<u8>>)>) -> TransactionWitnessSet {
+    let entries = entries
+      .into_iter()
+      .map(|(key, items)| (key, encode_array(items).unwrap()))
+      .collect();
+    TransactionWitnessSet::from_cbor(&encode_map(entries).unwrap()).unwrap()
+  }
+
+  #[test]
+  fn merge_keeps_one_witness_per_key() {
+    let redeemer = hex("84000001820101");
+    let mut own = witness_set(vec![(0, vec![vkey_witness(1)])]);
+    let mut other = witness_set(vec![
+      (0, vec![vkey_witness(1), vkey_witness(2)]),
+      (5, vec![redeemer.clone()]),
+    ]);
+    let mut merged = own.merge(&other).unwrap();
+    let entries = map_entries(&merged.to_cbor().unwrap()).unwrap();
+    assert_eq!(array_items(&entries[0].1).unwrap(), vec![vkey_witness(1), vkey_witness(2)]);
+    assert_eq!(entries[1], (5, encode_array(vec![redeemer]).unwrap()));
+    // Another redeemer for the same pointer can't be merged
+    let mut conflicting = witness_set(vec![(5, vec![hex("84000002820101")])]);
+    assert!(merged.merge(&conflicting).is_err());
+    unsafe {
+      own.free();
+      other.free();
+      merged.free();
+      conflicting.free();
+    }
+  }
+}
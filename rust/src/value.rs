@@ -54,6 +54,17 @@ impl Free for Value {
   }
 }
 
+impl Value {
+  pub fn coin(&self) -> Coin {
+    self.coin
+  }
+
+  // Copy sharing the assets of the value, with another coin
+  pub fn with_coin(&self, coin: Coin) -> Self {
+    Self { coin, ..*self }
+  }
+}
+
 impl TryFrom<Value> for RValue {
   type Error = CError;
 
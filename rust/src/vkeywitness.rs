@@ -8,7 +8,10 @@ use crate::ptr::*;
 use crate::transaction_hash::TransactionHash;
 use crate::vkey::Vkey;
 use cardano_serialization_lib::{
-  crypto::{Vkeywitness as RVkeywitness, Vkeywitnesses as RVkeywitnesses},
+  crypto::{
+    Ed25519KeyHash as REd25519KeyHash, Ed25519Signature as REd25519Signature,
+    PublicKey as RPublicKey, Vkeywitness as RVkeywitness, Vkeywitnesses as RVkeywitnesses,
+  },
   utils::make_vkey_witness,
 };
 use std::convert::{TryFrom, TryInto};
@@ -45,6 +48,24 @@ impl From<RVkeywitness> for Vkeywitness {
   }
 }
 
+impl Vkeywitness {
+  pub fn key_hash(&self) -> Result<REd25519KeyHash> {
+    self.vkey.0.try_into().map(|public_key: RPublicKey| public_key.hash())
+  }
+
+  // Whether the signature is the one of the key over the transaction body hash
+  pub fn verify(&self, tx_body_hash: &TransactionHash) -> Result<bool> {
+    self
+      .vkey
+      .0
+      .try_into()
+      .zip(self.signature.try_into())
+      .map(|(public_key, signature): (RPublicKey, REd25519Signature)| {
+        public_key.verify(&tx_body_hash.0, &signature)
+      })
+  }
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn cardano_vkeywitness_make_vkey_witness(
   tx_body_hash: TransactionHash, sk: PrivateKey, result: &mut Vkeywitness, error: &mut CError,
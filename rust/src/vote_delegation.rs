@@ -0,0 +1,40 @@
+use crate::cbor::*;
+use crate::drep::DRep;
+use crate::panic::*;
+use crate::stake_credential::StakeCredential;
+use cbor_event::Len;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct VoteDelegation {
+  stake_credential: StakeCredential,
+  drep: DRep,
+}
+
+impl VoteDelegation {
+  pub fn stake_credential(&self) -> StakeCredential {
+    self.stake_credential
+  }
+}
+
+impl CborSerialize for VoteDelegation {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    se.write_array(Len::Len(3))?;
+    se.write_unsigned_integer(9)?;
+    self.stake_credential.serialize(se)?;
+    self.drep.serialize(se)
+  }
+}
+
+impl CborDeserialize for VoteDelegation {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    let len = read_kind_array(de, 9, 2, "VoteDelegation")?;
+    let stake_credential = StakeCredential::deserialize(de)?;
+    let drep = DRep::deserialize(de)?;
+    read_array_end(de, len)?;
+    Ok(Self {
+      stake_credential,
+      drep,
+    })
+  }
+}
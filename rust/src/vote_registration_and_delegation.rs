@@ -0,0 +1,50 @@
+use crate::cbor::*;
+use crate::drep::DRep;
+use crate::linear_fee::Coin;
+use crate::panic::*;
+use crate::stake_credential::StakeCredential;
+use cbor_event::Len;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct VoteRegistrationAndDelegation {
+  stake_credential: StakeCredential,
+  drep: DRep,
+  deposit: Coin,
+}
+
+impl VoteRegistrationAndDelegation {
+  pub fn stake_credential(&self) -> StakeCredential {
+    self.stake_credential
+  }
+
+  pub fn deposit(&self) -> Coin {
+    self.deposit
+  }
+}
+
+impl CborSerialize for VoteRegistrationAndDelegation {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    se.write_array(Len::Len(4))?;
+    se.write_unsigned_integer(12)?;
+    self.stake_credential.serialize(se)?;
+    self.drep.serialize(se)?;
+    se.write_unsigned_integer(self.deposit)?;
+    Ok(())
+  }
+}
+
+impl CborDeserialize for VoteRegistrationAndDelegation {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    let len = read_kind_array(de, 12, 3, "VoteRegistrationAndDelegation")?;
+    let stake_credential = StakeCredential::deserialize(de)?;
+    let drep = DRep::deserialize(de)?;
+    let deposit = de.unsigned_integer()?;
+    read_array_end(de, len)?;
+    Ok(Self {
+      stake_credential,
+      drep,
+      deposit,
+    })
+  }
+}
@@ -0,0 +1,268 @@
+use crate::anchor::Anchor;
+use crate::array::*;
+use crate::cbor::*;
+use crate::data::CData;
+use crate::error::CError;
+use crate::option::COption;
+use crate::panic::*;
+use crate::ptr::*;
+use crate::stake_credential::{Ed25519KeyHash, ScriptHash};
+use crate::transaction_hash::TransactionHash;
+use crate::transaction_input::TransactionIndex;
+use cbor_event::Len;
+use std::convert::TryInto;
+
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum Voter {
+  ConstitutionalCommitteeHotKeyHash(Ed25519KeyHash),
+  ConstitutionalCommitteeHotScriptHash(ScriptHash),
+  DRepKeyHash(Ed25519KeyHash),
+  DRepScriptHash(ScriptHash),
+  StakingPoolKeyHash(Ed25519KeyHash),
+}
+
+impl Free for Voter {
+  unsafe fn free(&mut self) {}
+}
+
+impl Voter {
+  // Key which has to sign the transaction for the votes to be valid
+  pub fn key_hash(&self) -> Option<Ed25519KeyHash> {
+    match self {
+      Voter::ConstitutionalCommitteeHotKeyHash(hash) => Some(*hash),
+      Voter::DRepKeyHash(hash) => Some(*hash),
+      Voter::StakingPoolKeyHash(hash) => Some(*hash),
+      _ => None,
+    }
+  }
+}
+
+impl CborSerialize for Voter {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    se.write_array(Len::Len(2))?;
+    match self {
+      Voter::ConstitutionalCommitteeHotKeyHash(hash) => {
+        se.write_unsigned_integer(0)?;
+        hash.serialize(se)
+      }
+      Voter::ConstitutionalCommitteeHotScriptHash(hash) => {
+        se.write_unsigned_integer(1)?;
+        hash.serialize(se)
+      }
+      Voter::DRepKeyHash(hash) => {
+        se.write_unsigned_integer(2)?;
+        hash.serialize(se)
+      }
+      Voter::DRepScriptHash(hash) => {
+        se.write_unsigned_integer(3)?;
+        hash.serialize(se)
+      }
+      Voter::StakingPoolKeyHash(hash) => {
+        se.write_unsigned_integer(4)?;
+        hash.serialize(se)
+      }
+    }
+  }
+}
+
+impl CborDeserialize for Voter {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    let kind = peek_kind(de)?;
+    let len = read_kind_array(de, kind, 1, "Voter")?;
+    let voter = match kind {
+      0 => Ed25519KeyHash::deserialize(de).map(Self::ConstitutionalCommitteeHotKeyHash),
+      1 => ScriptHash::deserialize(de).map(Self::ConstitutionalCommitteeHotScriptHash),
+      2 => Ed25519KeyHash::deserialize(de).map(Self::DRepKeyHash),
+      3 => ScriptHash::deserialize(de).map(Self::DRepScriptHash),
+      4 => Ed25519KeyHash::deserialize(de).map(Self::StakingPoolKeyHash),
+      _ => Err(format!("Wrong Voter kind {}", kind).into()),
+    }?;
+    read_array_end(de, len)?;
+    Ok(voter)
+  }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct GovernanceActionId {
+  transaction_id: TransactionHash,
+  index: TransactionIndex,
+}
+
+impl Free for GovernanceActionId {
+  unsafe fn free(&mut self) {}
+}
+
+impl CborSerialize for GovernanceActionId {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    se.write_array(Len::Len(2))?;
+    self.transaction_id.serialize(se)?;
+    se.write_unsigned_integer(self.index.into())?;
+    Ok(())
+  }
+}
+
+impl CborDeserialize for GovernanceActionId {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    let len = de.array()?;
+    if len != Len::Len(2) && len != Len::Indefinite {
+      return Err("Wrong GovernanceActionId array length".into());
+    }
+    let transaction_id = TransactionHash::deserialize(de)?;
+    let index = de.unsigned_integer()?.try_into().map_err(|_| "Action index is too big")?;
+    read_array_end(de, len)?;
+    Ok(Self { transaction_id, index })
+  }
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_governance_action_id_to_bytes(
+  governance_action_id: GovernanceActionId, result: &mut CData, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| governance_action_id.to_cbor().map(|bytes| bytes.into()))
+    .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_governance_action_id_from_bytes(
+  data: CData, result: &mut GovernanceActionId, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    data
+      .unowned()
+      .and_then(GovernanceActionId::from_cbor)
+  })
+  .response(result, error)
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum Vote {
+  No,
+  Yes,
+  Abstain,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct VotingProcedure {
+  vote: Vote,
+  anchor: COption<Anchor>,
+}
+
+impl Free for VotingProcedure {
+  unsafe fn free(&mut self) {
+    self.anchor.free()
+  }
+}
+
+impl CborSerialize for VotingProcedure {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    se.write_array(Len::Len(2))?;
+    se.write_unsigned_integer(self.vote as u64)?;
+    write_nullable(se, self.anchor.into(), |se, anchor: Anchor| anchor.serialize(se))
+  }
+}
+
+impl CborDeserialize for VotingProcedure {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    let len = de.array()?;
+    if len != Len::Len(2) && len != Len::Indefinite {
+      return Err("Wrong VotingProcedure array length".into());
+    }
+    let vote = match de.unsigned_integer()? {
+      0 => Ok(Vote::No),
+      1 => Ok(Vote::Yes),
+      2 => Ok(Vote::Abstain),
+      vote => Err(CError::from(format!("Wrong Vote {}", vote))),
+    }?;
+    let anchor = read_nullable(de, Anchor::deserialize)?.into();
+    read_array_end(de, len)?;
+    Ok(Self { vote, anchor })
+  }
+}
+
+pub type GovernanceActionVotesKeyValue = CKeyValue<GovernanceActionId, VotingProcedure>;
+pub type GovernanceActionVotes = CArray<GovernanceActionVotesKeyValue>;
+
+pub type VotingProceduresKeyValue = CKeyValue<Voter, GovernanceActionVotes>;
+pub type VotingProcedures = CArray<VotingProceduresKeyValue>;
+
+impl CborSerialize for VotingProcedures {
+  fn serialize(&self, se: &mut CborSerializer) -> Result<()> {
+    let voters = unsafe { self.unowned()? };
+    se.write_map(Len::Len(voters.len() as u64))?;
+    for voter in voters {
+      let votes = unsafe { voter.val.unowned()? };
+      voter.key.serialize(se)?;
+      se.write_map(Len::Len(votes.len() as u64))?;
+      for vote in votes {
+        vote.key.serialize(se)?;
+        vote.val.serialize(se)?;
+      }
+    }
+    Ok(())
+  }
+}
+
+impl CborDeserialize for VotingProcedures {
+  fn deserialize(de: &mut CborDeserializer) -> Result<Self> {
+    read_map(de, |de| {
+      let voter = Voter::deserialize(de)?;
+      let votes = read_map(de, |de| {
+        GovernanceActionId::deserialize(de).zip(VotingProcedure::deserialize(de))
+      })?;
+      let votes: Vec<GovernanceActionVotesKeyValue> =
+        votes.into_iter().map(|vote| vote.into()).collect();
+      Ok((voter, votes.into()))
+    })
+    .map(|voters| {
+      voters
+        .into_iter()
+        .map(|voter| voter.into())
+        .collect::<Vec<VotingProceduresKeyValue>>()
+        .into()
+    })
+  }
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_voting_procedures_to_bytes(
+  voting_procedures: VotingProcedures, result: &mut CData, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| voting_procedures.to_cbor().map(|bytes| bytes.into()))
+    .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_voting_procedures_from_bytes(
+  data: CData, result: &mut VotingProcedures, error: &mut CError,
+) -> bool {
+  handle_exception_result(|| {
+    data
+      .unowned()
+      .and_then(VotingProcedures::from_cbor)
+  })
+  .response(result, error)
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_voting_procedures_free(
+  voting_procedures: &mut VotingProcedures,
+) {
+  voting_procedures.free()
+}
+
+#[no_mangle]
+#[allow(clippy::missing_safety_doc)]
+pub unsafe extern "C" fn cardano_governance_action_votes_free(
+  governance_action_votes: &mut GovernanceActionVotes,
+) {
+  governance_action_votes.free()
+}